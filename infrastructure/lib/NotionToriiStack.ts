@@ -69,6 +69,39 @@ export class NotionToriiStack extends Stack {
             projectionType: ProjectionType.ALL
         } )
 
+        toriiTable.addGlobalSecondaryIndex( {
+            indexName: 'email_index',
+            partitionKey: {
+                name: 'email',
+                type: AttributeType.STRING
+            },
+            projectionType: ProjectionType.ALL
+        } )
+
+        toriiTable.addGlobalSecondaryIndex( {
+            indexName: 'app_id_index',
+            partitionKey: {
+                name: 'appId',
+                type: AttributeType.NUMBER
+            },
+            projectionType: ProjectionType.ALL
+        } )
+
+        // DynamoDB creates one index per table update, so a table that already exists without
+        // app_id_index and user_id_index needs two deployments: first
+        // `cdk deploy -c skipUserIdIndex=true`, then a plain `cdk deploy` once app_id_index is
+        // active. New tables get every index in one go
+        if ( this.node.tryGetContext( 'skipUserIdIndex' ) !== 'true' ) {
+            toriiTable.addGlobalSecondaryIndex( {
+                indexName: 'user_id_index',
+                partitionKey: {
+                    name: 'userId',
+                    type: AttributeType.NUMBER
+                },
+                projectionType: ProjectionType.ALL
+            } )
+        }
+
         toriiTable.grantReadWriteData( mockFunction.role! )
 
         const notionApiSecret = new Secret( this, 'NotionSecret', {
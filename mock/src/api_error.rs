@@ -1,4 +1,8 @@
-use axum::{http::StatusCode, response::IntoResponse, Json};
+use axum::{
+    http::{header, StatusCode},
+    response::IntoResponse,
+    Json,
+};
 use serde_json::json;
 use validator::{ValidationError, ValidationErrors};
 
@@ -13,27 +17,61 @@ pub enum ApiError {
     #[error("Validation error: {0}")]
     ValidationError(String),
 
+    #[error("Precondition failed: {0}")]
+    PreconditionFailed(String),
+
+    #[error("Unsupported media type: {0}")]
+    UnsupportedMediaType(String),
+
+    #[error("Failed dependency: {0}")]
+    FailedDependency(String),
+
+    #[error("Response too large: {0}")]
+    ResponseTooLarge(String),
+
+    /// Seconds until the client may try again
+    #[error("Too many requests, retry after {0} seconds")]
+    TooManyRequests(u64),
+
     #[error("Unexpected error occurred")]
     InternalServerError,
 }
 
+impl ApiError {
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            ApiError::ResourceNotFound(_) => StatusCode::NOT_FOUND,
+            ApiError::ResourceAlreadyExists(_) => StatusCode::CONFLICT,
+            ApiError::ValidationError(_) => StatusCode::BAD_REQUEST,
+            ApiError::PreconditionFailed(_) => StatusCode::PRECONDITION_FAILED,
+            ApiError::UnsupportedMediaType(_) => StatusCode::UNSUPPORTED_MEDIA_TYPE,
+            ApiError::FailedDependency(_) => StatusCode::FAILED_DEPENDENCY,
+            ApiError::ResponseTooLarge(_) => StatusCode::PAYLOAD_TOO_LARGE,
+            ApiError::TooManyRequests(_) => StatusCode::TOO_MANY_REQUESTS,
+            ApiError::InternalServerError => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
 impl IntoResponse for ApiError {
     fn into_response(self) -> axum::response::Response {
-        let (status, error_message) = match &self {
-            ApiError::ResourceNotFound(_) => (StatusCode::NOT_FOUND, &self.to_string()),
-            ApiError::ResourceAlreadyExists(_) => (StatusCode::CONFLICT, &self.to_string()),
-            ApiError::ValidationError(_) => (StatusCode::BAD_REQUEST, &self.to_string()),
-            ApiError::InternalServerError => (
-                StatusCode::INTERNAL_SERVER_ERROR,
-                &"An unexpected error has occurred".to_string(),
-            ),
+        let error_message = match &self {
+            ApiError::InternalServerError => "An unexpected error has occurred".to_string(),
+            _ => self.to_string(),
         };
 
         let body = json!({
             "error": error_message
         });
 
-        (status, Json(body)).into_response()
+        let mut response = (self.status_code(), Json(body)).into_response();
+        if let ApiError::TooManyRequests(retry_after) = self {
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, retry_after.into());
+        }
+
+        response
     }
 }
 
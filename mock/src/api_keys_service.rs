@@ -0,0 +1,256 @@
+use chrono::{Duration, Utc};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use tracing::{error, warn};
+use uuid::Uuid;
+
+use crate::{
+    repository::{
+        ApiKey, ApiKeyScope, ApiKeysRepository, AuthenticateError, CreateApiKeyError,
+        CreatedApiKey, DeleteApiKeyError, GetApiKeyError, ListApiKeysError, RotateApiKeyError,
+        StoredApiKey,
+    },
+    routes::{CreateApiKeyHttpRequestBody, RotateApiKeyHttpRequestBody},
+    tenant::{Tenant, TenantKeys},
+};
+
+/// Secrets look like `torii_<key id>_<random hex>` so the key can be read by ID rather than
+/// found with a scan of every key
+const SECRET_PREFIX: &str = "torii_";
+
+/// Who a request was made by, as far as authorization is concerned
+#[derive(Debug, Clone)]
+pub struct Principal {
+    pub tenant: Tenant,
+    pub scopes: Vec<ApiKeyScope>,
+    /// Who is calling within the tenant: `api_key:<id>`, `bootstrap:<tenant>:<hash>` where
+    /// `<hash>` starts the SHA-256 of the secret, or `client:<sub>` for JWTs
+    pub subject: String,
+}
+
+impl Principal {
+    pub fn allows(&self, required: ApiKeyScope) -> bool {
+        self.scopes.iter().any(|scope| *scope >= required)
+    }
+}
+
+/// Issues, rotates and checks API keys. The keys set with `TORII_API_KEY`, `TORII_SECRET` and
+/// `TORII_TENANTS` keep working as admin keys that never expire, so there is always a key to
+/// create the others with
+#[derive(Debug, Clone)]
+pub struct ApiKeysService<R>
+where
+    R: ApiKeysRepository,
+{
+    pub repo: R,
+    bootstrap_keys: TenantKeys,
+}
+
+impl<R> ApiKeysService<R>
+where
+    R: ApiKeysRepository,
+{
+    pub fn new(repo: R, bootstrap_keys: TenantKeys) -> Self {
+        Self {
+            repo,
+            bootstrap_keys,
+        }
+    }
+
+    /// Resolves the secret sent as bearer token and records that the key was used
+    #[tracing::instrument(skip(self, secret))]
+    pub async fn authenticate(&self, secret: &str) -> Result<Principal, AuthenticateError> {
+        if let Some(tenant) = self.bootstrap_keys.tenant(secret) {
+            return Ok(Principal {
+                tenant: tenant.clone(),
+                scopes: vec![ApiKeyScope::Admin],
+                subject: format!("bootstrap:{tenant}:{}", &hash_secret(secret)[..12]),
+            });
+        }
+
+        let id = secret
+            .strip_prefix(SECRET_PREFIX)
+            .and_then(|rest| rest.split_once('_'))
+            .map(|(id, _)| id)
+            .ok_or(AuthenticateError::UnknownKey)?;
+
+        let stored = match self.repo.get_api_key(id).await {
+            Ok(stored) => stored,
+            Err(GetApiKeyError::ResourceNotFound(_)) => return Err(AuthenticateError::UnknownKey),
+            Err(e) => {
+                error!("{}", e);
+                return Err(AuthenticateError::UnexpectedError);
+            }
+        };
+
+        if stored.secret_hash != hash_secret(secret) {
+            return Err(AuthenticateError::UnknownKey);
+        }
+
+        let now = Utc::now();
+        if stored.key.is_expired(now) {
+            return Err(AuthenticateError::Expired(stored.key.id));
+        }
+
+        // The request goes ahead even when the timestamp can't be written
+        if let Err(e) = self.repo.record_use(id, now).await {
+            warn!("Unable to record use of API key {}: {}", id, e);
+        }
+
+        Ok(Principal {
+            tenant: stored.key.tenant,
+            scopes: stored.key.scopes,
+            subject: format!("api_key:{}", stored.key.id),
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn create_api_key(
+        &self,
+        tenant: &Tenant,
+        request: CreateApiKeyHttpRequestBody,
+    ) -> Result<CreatedApiKey, CreateApiKeyError> {
+        let now = Utc::now();
+
+        if let Some(expires_at) = request.expires_at {
+            if expires_at <= now {
+                return Err(CreateApiKeyError::InvalidExpiry(expires_at.to_rfc3339()));
+            }
+        }
+
+        let mut scopes = request.scopes;
+        scopes.sort();
+        scopes.dedup();
+
+        let key = ApiKey {
+            id: Uuid::new_v4().to_string(),
+            name: request.name,
+            tenant: tenant.clone(),
+            scopes,
+            creation_time: now,
+            expires_at: request.expires_at,
+            last_used_at: None,
+        };
+
+        self.issue(key).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn get_api_key(&self, tenant: &Tenant, id: &str) -> Result<ApiKey, GetApiKeyError> {
+        self.get_stored(tenant, id).await.map(|stored| stored.key)
+    }
+
+    /// Oldest first
+    #[tracing::instrument(skip(self))]
+    pub async fn list_api_keys(&self, tenant: &Tenant) -> Result<Vec<ApiKey>, ListApiKeysError> {
+        let mut keys: Vec<ApiKey> = self
+            .repo
+            .list_api_keys(tenant)
+            .await?
+            .into_iter()
+            .map(|stored| stored.key)
+            .collect();
+        keys.sort_by(|a, b| {
+            a.creation_time
+                .cmp(&b.creation_time)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        Ok(keys)
+    }
+
+    #[tracing::instrument(skip(self))]
+    pub async fn delete_api_key(&self, tenant: &Tenant, id: &str) -> Result<(), DeleteApiKeyError> {
+        self.get_stored(tenant, id).await.map_err(|e| match e {
+            GetApiKeyError::ResourceNotFound(id) => DeleteApiKeyError::ResourceNotFound(id),
+            _ => {
+                error!("{}", e);
+                DeleteApiKeyError::UnexpectedError
+            }
+        })?;
+
+        self.repo.delete_api_key(id).await
+    }
+
+    /// Issues a new key with the same name, scopes and expiry. The old key keeps working for
+    /// the grace period so callers can switch over without downtime
+    #[tracing::instrument(skip(self))]
+    pub async fn rotate_api_key(
+        &self,
+        tenant: &Tenant,
+        id: &str,
+        request: RotateApiKeyHttpRequestBody,
+    ) -> Result<CreatedApiKey, RotateApiKeyError> {
+        let mut old = self.get_stored(tenant, id).await.map_err(|e| match e {
+            GetApiKeyError::ResourceNotFound(id) => RotateApiKeyError::ResourceNotFound(id),
+            _ => {
+                error!("{}", e);
+                RotateApiKeyError::UnexpectedError
+            }
+        })?;
+
+        let now = Utc::now();
+        let key = ApiKey {
+            id: Uuid::new_v4().to_string(),
+            creation_time: now,
+            last_used_at: None,
+            ..old.key.clone()
+        };
+
+        let created = self.issue(key).await.map_err(|e| {
+            error!("{}", e);
+            RotateApiKeyError::UnexpectedError
+        })?;
+
+        let grace_end =
+            now + Duration::seconds(i64::from(request.grace_period_seconds.unwrap_or(0)));
+        old.key.expires_at = Some(
+            old.key
+                .expires_at
+                .map_or(grace_end, |expires_at| expires_at.min(grace_end)),
+        );
+
+        self.repo.update_api_key(old).await.map_err(|e| {
+            error!("{}", e);
+            RotateApiKeyError::UnexpectedError
+        })?;
+
+        Ok(created)
+    }
+
+    /// Keys of other tenants are reported as missing rather than forbidden
+    async fn get_stored(&self, tenant: &Tenant, id: &str) -> Result<StoredApiKey, GetApiKeyError> {
+        let stored = self.repo.get_api_key(id).await?;
+
+        if stored.key.tenant != *tenant {
+            return Err(GetApiKeyError::ResourceNotFound(id.to_string()));
+        }
+
+        Ok(stored)
+    }
+
+    async fn issue(&self, key: ApiKey) -> Result<CreatedApiKey, CreateApiKeyError> {
+        let secret = format!(
+            "{SECRET_PREFIX}{}_{}",
+            key.id,
+            hex::encode(rand::thread_rng().gen::<[u8; 24]>())
+        );
+
+        let stored = self
+            .repo
+            .create_api_key(StoredApiKey {
+                key,
+                secret_hash: hash_secret(&secret),
+            })
+            .await?;
+
+        Ok(CreatedApiKey {
+            key: stored.key,
+            secret,
+        })
+    }
+}
+
+fn hash_secret(secret: &str) -> String {
+    hex::encode(Sha256::digest(secret.as_bytes()))
+}
@@ -0,0 +1,85 @@
+use std::collections::BTreeMap;
+
+use json_patch::Patch;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
+
+use crate::repository::{App, AppCategory, AppState};
+
+pub const MERGE_PATCH_CONTENT_TYPE: &str = "application/merge-patch+json";
+pub const JSON_PATCH_CONTENT_TYPE: &str = "application/json-patch+json";
+
+/// Change sent to `PATCH /v1.0/apps/:id`
+#[derive(Debug, Clone)]
+pub enum AppPatch {
+    /// RFC 7396, members left out keep their value and `null` clears one
+    Merge(Value),
+    /// RFC 6902, operations are applied in order and the whole patch fails if any of them does
+    Json(Patch),
+}
+
+/// The attributes of an app a patch may change. Patches are applied to this document instead of
+/// the whole app so read-only attributes such as `id` or `version` can't be touched
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+#[serde(deny_unknown_fields)]
+pub struct AppPatchDocument {
+    pub name: String,
+    pub state: AppState,
+    #[validate(url)]
+    pub url: String,
+    pub category: AppCategory,
+    pub description: Option<String>,
+    pub tags: Option<String>,
+    #[serde(default)]
+    pub fields: BTreeMap<String, Value>,
+}
+
+impl From<&App> for AppPatchDocument {
+    fn from(app: &App) -> Self {
+        Self {
+            name: app.name.clone(),
+            state: app.state.clone(),
+            url: app.url.clone(),
+            category: app.category.clone(),
+            description: app.description.clone(),
+            tags: app.tags.clone(),
+            fields: app.fields.clone(),
+        }
+    }
+}
+
+impl AppPatchDocument {
+    /// Applies `patch` to the patchable attributes of `app`, the error explains why the patch
+    /// can't be applied or why the result isn't a valid app
+    pub fn patched(app: &App, patch: &AppPatch) -> Result<Self, String> {
+        let mut document = serde_json::to_value(Self::from(app)).map_err(|e| e.to_string())?;
+
+        match patch {
+            AppPatch::Merge(patch) => json_patch::merge(&mut document, patch),
+            AppPatch::Json(patch) => {
+                json_patch::patch(&mut document, patch).map_err(|e| e.to_string())?
+            }
+        }
+
+        serde_json::from_value(document).map_err(|e| format!("Patched app is invalid: {e}"))
+    }
+
+    /// Custom field values that differ from `app`, in the shape `apply_values` takes, so
+    /// removed fields are `null`
+    pub fn field_changes(&self, app: &App) -> BTreeMap<String, Value> {
+        let removed = app
+            .fields
+            .keys()
+            .filter(|key| !self.fields.contains_key(*key))
+            .map(|key| (key.clone(), Value::Null));
+
+        let changed = self
+            .fields
+            .iter()
+            .filter(|(key, value)| app.fields.get(*key) != Some(value))
+            .map(|(key, value)| (key.clone(), value.clone()));
+
+        removed.chain(changed).collect()
+    }
+}
@@ -1,94 +1,329 @@
-use std::collections::HashMap;
+use std::{
+    cmp::Reverse,
+    collections::{BTreeMap, HashMap, HashSet},
+    sync::Arc,
+};
 
 use crate::{
+    app_patch::{AppPatch, AppPatchDocument},
+    catalog::{KnownApp, KnownAppsCatalog, KnownAppsFilter, KnownAppsPage},
+    custom_fields::{apply_values, validate_key, validate_options, validate_value},
+    import::{ColumnMapping, ImportRowResult, ImportRowStatus, ImportSheet},
     repository::{
-        AddAppError, App, AppCategory, AppState, AppsRepository, CreateAppError, DeleteAppError,
-        GetAppError, ListAppsError, UpdateAppError,
+        AddAppError, App, AppField, AppFieldsRepository, AppUser, AppUsersRepository, AppWrite,
+        AppsFilter, AppsPage, AppsRepository, AuditAction, AuditChange, AuditEvent, AuditFilter,
+        AuditRepository, BulkAppOperationError, ContractsRepository, CreateAppError,
+        CreateAppFieldError, CreateWebhookError, DeleteAppError, DeleteAppFieldError,
+        DeleteAppUserError, DeleteWebhookError, GetAppError, GetAppFieldError, GetKnownAppError,
+        GetUserError, GetWebhookError, ImportAppsError, LicenseStatus, ListAppFieldsError,
+        ListAppUsersError, ListAppsError, ListAuditEventsError, ListKnownAppsError,
+        ListWebhookDeliveriesError, ListWebhooksError, PutAppUserError, UpdateAppError,
+        UpdateAppFieldError, UpdateWebhookError, User, UsersRepository, Webhook, WebhookDelivery,
+        WebhookEventType, WebhooksRepository, WriteAppsError,
     },
+    request_context::RequestContext,
     routes::{
-        AddAppHttpRequestBody, CreateAppHttpRequestBody, SearchAppsQueryParams,
-        UpdateAppHttpRequestBody,
+        AddAppHttpRequestBody, BulkAppOperation, CreateAppFieldHttpRequestBody,
+        CreateAppHttpRequestBody, CreateWebhookHttpRequestBody, ListAppsQueryParams,
+        ListAuditEventsQueryParams, ListKnownAppsQueryParams, PutAppUserHttpRequestBody,
+        SearchAppsQueryParams, UpdateAppFieldHttpRequestBody, UpdateAppHttpRequestBody,
+        UpdateWebhookHttpRequestBody,
     },
+    search::score_app,
+    sorting::sort_apps,
+    tenant::{Tenant, TenantScoped},
+    webhooks::{RetryPolicy, WebhookDispatcher},
 };
 use async_trait::async_trait;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use rand::Rng;
 use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
 use tracing::error;
 use utoipa::ToSchema;
+use uuid::Uuid;
+use validator::Validate;
+
+/// How often an app changed by someone else is read again while removing a deleted field's value
+const FIELD_REMOVAL_ATTEMPTS: u32 = 3;
+
+/// Search result, `score` is between 0 and 1 with higher being a closer match
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct ScoredApp {
+    #[serde(flatten)]
+    pub app: App,
+    pub score: f32,
+}
+
+/// A user of an app along with their license for it
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct AppUserDetails {
+    #[serde(flatten)]
+    pub user: User,
+    #[serde(rename = "licenseStatus")]
+    pub license_status: LicenseStatus,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
 
+/// An app used by a user along with their license for it
 #[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
-pub struct KnownApp {
-    id: u16,
-    name: String,
-    category: AppCategory,
-    url: String,
+pub struct UserAppDetails {
+    #[serde(flatten)]
+    pub app: App,
+    #[serde(rename = "licenseStatus")]
+    pub license_status: LicenseStatus,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<DateTime<Utc>>,
 }
 
 #[async_trait]
-pub trait AppsServiceTrait: std::fmt::Debug + Send + Sync + Clone {
+pub trait AppsServiceTrait: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
     async fn get_app(&self, id: u16) -> Result<App, GetAppError>;
-    async fn add_app(&self, request: AddAppHttpRequestBody) -> Result<App, AddAppError>;
-    async fn create_app(&self, request: CreateAppHttpRequestBody) -> Result<App, CreateAppError>;
-    async fn delete_app(&self, id: u16) -> Result<(), DeleteAppError>;
-    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError>;
-    async fn list_known_apps(&self) -> Vec<KnownApp>;
+    async fn add_app(
+        &self,
+        request: AddAppHttpRequestBody,
+        context: RequestContext,
+    ) -> Result<App, AddAppError>;
+    async fn create_app(
+        &self,
+        request: CreateAppHttpRequestBody,
+        context: RequestContext,
+    ) -> Result<App, CreateAppError>;
+    async fn delete_app(&self, id: u16, context: RequestContext) -> Result<(), DeleteAppError>;
+    async fn list_apps(&self, params: ListAppsQueryParams) -> Result<AppsPage, ListAppsError>;
+    async fn list_known_apps(
+        &self,
+        params: ListKnownAppsQueryParams,
+    ) -> Result<KnownAppsPage, ListKnownAppsError>;
+    async fn get_known_app(&self, id: u16) -> Result<KnownApp, GetKnownAppError>;
     async fn update_app(
         &self,
         request: UpdateAppHttpRequestBody,
         id: u16,
+        expected_version: Option<u64>,
+        context: RequestContext,
     ) -> Result<App, UpdateAppError>;
-    async fn search_apps(&self, params: SearchAppsQueryParams) -> Result<Vec<App>, ListAppsError>;
+    /// Applies a JSON Merge Patch or JSON Patch to the app's editable attributes
+    async fn patch_app(
+        &self,
+        patch: AppPatch,
+        id: u16,
+        expected_version: Option<u64>,
+        context: RequestContext,
+    ) -> Result<App, UpdateAppError>;
+    /// Results are in the same order as `operations`, puts and updates return the stored app
+    async fn bulk_apps(
+        &self,
+        operations: Vec<BulkAppOperation>,
+        transactional: bool,
+        context: RequestContext,
+    ) -> Vec<Result<Option<App>, BulkAppOperationError>>;
+    /// Creates a custom app for every valid row whose name isn't taken yet, a dry run only
+    /// reports what would happen
+    async fn import_apps(
+        &self,
+        sheet: ImportSheet,
+        mapping: BTreeMap<String, String>,
+        dry_run: bool,
+        context: RequestContext,
+    ) -> Result<Vec<ImportRowResult>, ImportAppsError>;
+    async fn search_apps(
+        &self,
+        params: SearchAppsQueryParams,
+    ) -> Result<Vec<ScoredApp>, ListAppsError>;
+    async fn list_app_users(&self, id: u16) -> Result<Vec<AppUserDetails>, ListAppUsersError>;
+    async fn list_user_apps(&self, user_id: u32) -> Result<Vec<UserAppDetails>, ListAppUsersError>;
+    async fn put_app_user(
+        &self,
+        request: PutAppUserHttpRequestBody,
+        id: u16,
+        user_id: u32,
+    ) -> Result<AppUser, PutAppUserError>;
+    async fn delete_app_user(&self, id: u16, user_id: u32) -> Result<(), DeleteAppUserError>;
+    async fn get_app_field(&self, key: &str) -> Result<AppField, GetAppFieldError>;
+    async fn create_app_field(
+        &self,
+        request: CreateAppFieldHttpRequestBody,
+    ) -> Result<AppField, CreateAppFieldError>;
+    async fn delete_app_field(
+        &self,
+        key: &str,
+        context: RequestContext,
+    ) -> Result<(), DeleteAppFieldError>;
+    async fn list_app_fields(&self) -> Result<Vec<AppField>, ListAppFieldsError>;
+    async fn update_app_field(
+        &self,
+        request: UpdateAppFieldHttpRequestBody,
+        key: &str,
+    ) -> Result<AppField, UpdateAppFieldError>;
+    async fn list_audit_events(
+        &self,
+        params: ListAuditEventsQueryParams,
+    ) -> Result<Vec<AuditEvent>, ListAuditEventsError>;
+    async fn get_webhook(&self, id: &str) -> Result<Webhook, GetWebhookError>;
+    async fn create_webhook(
+        &self,
+        request: CreateWebhookHttpRequestBody,
+    ) -> Result<Webhook, CreateWebhookError>;
+    async fn delete_webhook(&self, id: &str) -> Result<(), DeleteWebhookError>;
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>, ListWebhooksError>;
+    async fn update_webhook(
+        &self,
+        request: UpdateWebhookHttpRequestBody,
+        id: &str,
+    ) -> Result<Webhook, UpdateWebhookError>;
+    async fn list_webhook_deliveries(
+        &self,
+        id: &str,
+    ) -> Result<Vec<WebhookDelivery>, ListWebhookDeliveriesError>;
 }
 
 #[derive(Debug, Clone)]
-pub struct AppsService<R>
+pub struct AppsService<R, U, M, C, F, A, W>
 where
     R: AppsRepository,
+    U: UsersRepository,
+    M: AppUsersRepository,
+    C: ContractsRepository,
+    F: AppFieldsRepository,
+    A: AuditRepository,
+    W: WebhooksRepository,
 {
     pub repo: R,
+    pub users_repo: U,
+    pub app_users_repo: M,
+    pub contracts_repo: C,
+    pub fields_repo: F,
+    pub audit_repo: A,
+    pub webhooks_repo: W,
+    pub dispatcher: WebhookDispatcher<W>,
+    pub catalog: Arc<KnownAppsCatalog>,
 }
 
-impl<R> AppsService<R>
+impl<R, U, M, C, F, A, W> AppsService<R, U, M, C, F, A, W>
 where
     R: AppsRepository,
+    U: UsersRepository,
+    M: AppUsersRepository,
+    C: ContractsRepository,
+    F: AppFieldsRepository,
+    A: AuditRepository,
+    W: WebhooksRepository,
 {
-    pub fn new(repo: R) -> Self {
-        Self { repo }
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repo: R,
+        users_repo: U,
+        app_users_repo: M,
+        contracts_repo: C,
+        fields_repo: F,
+        audit_repo: A,
+        webhooks_repo: W,
+        catalog: Arc<KnownAppsCatalog>,
+    ) -> Self {
+        Self {
+            repo,
+            users_repo,
+            app_users_repo,
+            contracts_repo,
+            fields_repo,
+            audit_repo,
+            dispatcher: WebhookDispatcher::new(webhooks_repo.clone(), RetryPolicy::default()),
+            webhooks_repo,
+            catalog,
+        }
     }
-}
 
-#[async_trait]
-impl<R> AppsServiceTrait for AppsService<R>
-where
-    R: AppsRepository,
-{
-    #[tracing::instrument(skip(self))]
-    async fn get_app(&self, id: u16) -> Result<App, GetAppError> {
-        self.repo.get_app(id).await
+    /// Appends an audit event for a change that already happened, a failure is only logged so
+    /// the caller still sees the outcome of the change itself
+    #[tracing::instrument(skip(self, before, after))]
+    async fn record_audit_event(
+        &self,
+        context: &RequestContext,
+        action: AuditAction,
+        app_id: u16,
+        before: Option<&App>,
+        after: Option<&App>,
+    ) {
+        let event = AuditEvent {
+            id: Uuid::new_v4().to_string(),
+            app_id,
+            action,
+            actor: context.actor.clone(),
+            on_behalf_of: context.on_behalf_of.clone(),
+            request_id: context.request_id.clone(),
+            timestamp: Utc::now(),
+            changes: diff_apps(before, after),
+        };
+
+        if let Err(e) = self.audit_repo.append_event(event).await {
+            error!("{}", e);
+        }
     }
 
+    /// Reads the app an update starts from, failing early when `If-Match` already doesn't match
     #[tracing::instrument(skip(self))]
-    async fn add_app(&self, body: AddAppHttpRequestBody) -> Result<App, AddAppError> {
-        if let Some(app) = get_default_app(body.id_app) {
-            self.repo.add_app(app).await
-        } else {
-            Err(AddAppError::ResourceNotFound(body.id_app))
+    async fn get_app_for_update(
+        &self,
+        id: u16,
+        expected_version: Option<u64>,
+    ) -> Result<App, UpdateAppError> {
+        let app = self.get_app(id).await.map_err(|e| match e {
+            GetAppError::ResourceNotFound(id) => UpdateAppError::ResourceNotFound(id),
+            _ => {
+                error!("{}", e);
+                UpdateAppError::UnexpectedError
+            }
+        })?;
+
+        if expected_version.is_some_and(|version| version != app.version) {
+            return Err(UpdateAppError::VersionConflict(app.id));
         }
+
+        Ok(app)
+    }
+
+    /// Reads the app a delete removes, so the audit event can show what was removed
+    #[tracing::instrument(skip(self))]
+    async fn get_app_for_delete(&self, id: u16) -> Result<App, DeleteAppError> {
+        self.repo.get_app(id).await.map_err(|e| match e {
+            GetAppError::ResourceNotFound(id) => DeleteAppError::ResourceNotFound(id),
+            _ => {
+                error!("{}", e);
+                DeleteAppError::UnexpectedError
+            }
+        })
     }
 
+    /// Builds a custom app from the request, allocating its ID
     #[tracing::instrument(skip(self))]
-    async fn create_app(&self, request: CreateAppHttpRequestBody) -> Result<App, CreateAppError> {
-        if let Ok(_) = self.repo.get_app_by_name(&request.name).await {
+    async fn prepare_create(
+        &self,
+        request: CreateAppHttpRequestBody,
+    ) -> Result<App, CreateAppError> {
+        if self.repo.get_app_by_name(&request.name).await.is_ok() {
             return Err(CreateAppError::ResourceAlreadyExists { name: request.name });
         }
 
+        let values = request.fields.unwrap_or_default();
+        self.validate_custom_fields(&values)
+            .await
+            .map_err(|e| match e {
+                Some(msg) => CreateAppError::InvalidCustomField(msg),
+                None => CreateAppError::UnexpectedError,
+            })?;
+
+        let mut fields = BTreeMap::new();
+        apply_values(&mut fields, values);
+
         let id = self
             .repo
             .get_id()
             .await
             .map_err(|_| CreateAppError::UnexpectedError)?;
 
-        let app = App {
+        Ok(App {
             id,
             name: request.name,
             state: request.state,
@@ -96,35 +331,540 @@ where
             category: request.category,
             tags: request.tags,
             is_custom: true,
+            users: Some(0),
+            fields,
             ..Default::default()
+        })
+    }
+
+    /// Returns the app as it is now and as it will be stored once the update is applied
+    #[tracing::instrument(skip(self))]
+    async fn prepare_update(
+        &self,
+        request: UpdateAppHttpRequestBody,
+        id: u16,
+        expected_version: Option<u64>,
+    ) -> Result<(App, App), UpdateAppError> {
+        let before = self.get_app_for_update(id, expected_version).await?;
+        let mut app = before.clone();
+
+        if let Some(name) = request.name {
+            app.name = name
+        };
+
+        if let Some(state) = request.state {
+            app.state = state
+        };
+
+        if let Some(url) = request.url {
+            app.url = url
+        };
+
+        if let Some(category) = request.category {
+            app.category = category
+        };
+
+        if let Some(description) = request.description {
+            app.description = Some(description)
+        };
+
+        if let Some(tags) = request.tags {
+            app.tags = Some(tags)
+        };
+
+        if let Some(values) = request.fields {
+            self.validate_custom_fields(&values)
+                .await
+                .map_err(|e| match e {
+                    Some(msg) => UpdateAppError::InvalidCustomField(msg),
+                    None => UpdateAppError::UnexpectedError,
+                })?;
+
+            apply_values(&mut app.fields, values);
         };
 
-        self.repo.create_app(app).await
+        app.last_updated_at = Utc::now();
+
+        Ok((before, app))
     }
 
+    /// Checks a single operation of a bulk transaction and turns it into the write it makes
     #[tracing::instrument(skip(self))]
-    async fn delete_app(&self, id: u16) -> Result<(), DeleteAppError> {
-        self.repo.delete_app(id).await
+    async fn prepare_bulk_operation(
+        &self,
+        operation: BulkAppOperation,
+    ) -> Result<PreparedWrite, BulkAppOperationError> {
+        operation
+            .validate()
+            .map_err(|e| BulkAppOperationError::InvalidRequest(e.to_string()))?;
+
+        let prepared = match operation {
+            BulkAppOperation::Add { app } => PreparedWrite {
+                action: AuditAction::Add,
+                before: None,
+                write: AppWrite::Put(prepare_add(&self.catalog, &app)?),
+            },
+            BulkAppOperation::Create { app } => PreparedWrite {
+                action: AuditAction::Create,
+                before: None,
+                write: AppWrite::Put(self.prepare_create(app).await?),
+            },
+            BulkAppOperation::Update { id, version, app } => {
+                let (before, app) = self.prepare_update(app, id, version).await?;
+
+                PreparedWrite {
+                    action: AuditAction::Update,
+                    before: Some(before),
+                    write: AppWrite::Update(app),
+                }
+            }
+            BulkAppOperation::Delete { id } => PreparedWrite {
+                action: AuditAction::Delete,
+                before: Some(self.get_app_for_delete(id).await?),
+                write: AppWrite::Delete(id),
+            },
+        };
+
+        Ok(prepared)
     }
 
     #[tracing::instrument(skip(self))]
-    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError> {
-        self.repo.list_apps().await
+    async fn bulk_apps_transaction(
+        &self,
+        operations: Vec<BulkAppOperation>,
+        context: RequestContext,
+    ) -> Vec<Result<Option<App>, BulkAppOperationError>> {
+        let mut prepared = Vec::with_capacity(operations.len());
+        let mut ids = HashSet::new();
+        let mut names = HashSet::new();
+
+        for operation in operations {
+            let result = self
+                .prepare_bulk_operation(operation)
+                .await
+                .and_then(|prepared| {
+                    if !ids.insert(prepared.write.id()) {
+                        return Err(BulkAppOperationError::DuplicateApp(prepared.write.id()));
+                    }
+
+                    // Names are only checked against stored apps, so two creates in the same
+                    // transaction could otherwise both claim a name
+                    match &prepared.write {
+                        AppWrite::Put(app)
+                            if prepared.action == AuditAction::Create
+                                && !names.insert(app.name.clone()) =>
+                        {
+                            Err(CreateAppError::ResourceAlreadyExists {
+                                name: app.name.clone(),
+                            }
+                            .into())
+                        }
+                        _ => Ok(prepared),
+                    }
+                });
+
+            prepared.push(result);
+        }
+
+        if let Some(failed) = prepared.iter().position(Result::is_err) {
+            return prepared
+                .into_iter()
+                .map(|result| match result {
+                    Ok(_) => Err(BulkAppOperationError::NotApplied(failed)),
+                    Err(e) => Err(e),
+                })
+                .collect();
+        }
+
+        let prepared: Vec<PreparedWrite> = prepared.into_iter().flatten().collect();
+        let writes = prepared.iter().map(|p| p.write.clone()).collect();
+
+        let written = match self.repo.write_apps(writes).await {
+            Ok(written) => written,
+            Err(WriteAppsError::ConditionFailed(failed)) => {
+                return prepared
+                    .iter()
+                    .enumerate()
+                    .map(|(index, p)| {
+                        Err(if index == failed {
+                            p.condition_failed()
+                        } else {
+                            BulkAppOperationError::NotApplied(failed)
+                        })
+                    })
+                    .collect();
+            }
+            Err(e) => {
+                error!("{}", e);
+                return prepared.iter().map(|p| Err(p.unexpected())).collect();
+            }
+        };
+
+        let mut results = Vec::with_capacity(written.len());
+
+        for (p, app) in prepared.into_iter().zip(written) {
+            match (&p.before, &app) {
+                (None, Some(app)) => self.app_created(&context, p.action, app).await,
+                (Some(before), Some(app)) => self.app_updated(&context, before, app).await,
+                (Some(before), None) => self.app_deleted(&context, before).await,
+                (None, None) => {}
+            }
+
+            results.push(Ok(app));
+        }
+
+        results
     }
 
+    /// Removes the value of field `key` from `before`, reading the app again when it was changed
+    /// concurrently. An app deleted in the meantime has nothing left to remove
+    async fn remove_app_field_value(
+        &self,
+        mut before: App,
+        key: &str,
+        context: &RequestContext,
+    ) -> Result<(), DeleteAppFieldError> {
+        for _ in 0..FIELD_REMOVAL_ATTEMPTS {
+            let mut app = before.clone();
+            if app.fields.remove(key).is_none() {
+                return Ok(());
+            }
+            app.last_updated_at = Utc::now();
+
+            match self.repo.update_app(app).await {
+                Ok(app) => {
+                    self.app_updated(context, &before, &app).await;
+                    return Ok(());
+                }
+                Err(UpdateAppError::VersionConflict(id)) => {
+                    before = match self.repo.get_app(id).await {
+                        Ok(app) => app,
+                        Err(GetAppError::ResourceNotFound(_)) => return Ok(()),
+                        Err(e) => {
+                            error!("{}", e);
+                            return Err(DeleteAppFieldError::UnexpectedError);
+                        }
+                    };
+                }
+                Err(UpdateAppError::ResourceNotFound(_)) => return Ok(()),
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(DeleteAppFieldError::UnexpectedError);
+                }
+            }
+        }
+
+        error!("App {} kept changing while removing field {key}", before.id);
+
+        Err(DeleteAppFieldError::UnexpectedError)
+    }
+
+    /// Emits the audit event and webhook for an app that was just added or created
+    #[tracing::instrument(skip(self, app))]
+    async fn app_created(&self, context: &RequestContext, action: AuditAction, app: &App) {
+        self.record_audit_event(context, action, app.id, None, Some(app))
+            .await;
+        self.dispatcher
+            .dispatch(context, WebhookEventType::Created, json!({ "app": app }))
+            .await;
+    }
+
+    /// Emits the audit event and webhooks for an app that was just updated
+    #[tracing::instrument(skip(self, before, app))]
+    async fn app_updated(&self, context: &RequestContext, before: &App, app: &App) {
+        self.record_audit_event(
+            context,
+            AuditAction::Update,
+            app.id,
+            Some(before),
+            Some(app),
+        )
+        .await;
+        self.dispatcher
+            .dispatch(
+                context,
+                WebhookEventType::Updated,
+                json!({ "app": app, "previous": before }),
+            )
+            .await;
+
+        if app.state != before.state {
+            self.dispatcher
+                .dispatch(
+                    context,
+                    WebhookEventType::StateChanged,
+                    json!({ "app": app, "previousState": before.state }),
+                )
+                .await;
+        }
+    }
+
+    /// Emits the audit event and webhook for an app that was just deleted and removes its users
+    /// and contracts
+    #[tracing::instrument(skip(self, before))]
+    async fn app_deleted(&self, context: &RequestContext, before: &App) {
+        let id = before.id;
+
+        self.record_audit_event(context, AuditAction::Delete, id, Some(before), None)
+            .await;
+        self.dispatcher
+            .dispatch(context, WebhookEventType::Deleted, json!({ "app": before }))
+            .await;
+
+        // Links are cleaned up after the app is gone, a failure here leaves orphans that are
+        // skipped when listing a user's apps
+        match self.app_users_repo.list_app_users(id).await {
+            Ok(app_users) => {
+                for app_user in app_users {
+                    if let Err(e) = self
+                        .app_users_repo
+                        .delete_app_user(app_user.app_id, app_user.user_id)
+                        .await
+                    {
+                        error!("{}", e);
+                    }
+                }
+            }
+            Err(e) => error!("{}", e),
+        }
+
+        // Contracts go the same way, a failure leaves contracts of an app that no longer exists
+        match self.contracts_repo.list_contracts().await {
+            Ok(contracts) => {
+                for contract in contracts
+                    .into_iter()
+                    .filter(|contract| contract.app_id == id)
+                {
+                    if let Err(e) = self.contracts_repo.delete_contract(contract.id).await {
+                        error!("{}", e);
+                    }
+                }
+            }
+            Err(e) => error!("{}", e),
+        }
+    }
+
+    /// Checks custom field values against their definitions, `null` is always accepted since it
+    /// clears the field. `Err(None)` means the check itself failed
     #[tracing::instrument(skip(self))]
-    async fn list_known_apps(&self) -> Vec<KnownApp> {
-        let known_apps: Vec<KnownApp> = default_apps()
-            .values()
-            .map(|item| KnownApp {
-                id: item.id,
-                category: item.category.clone(),
-                name: item.name.clone(),
-                url: item.url.clone(),
-            })
+    async fn validate_custom_fields(
+        &self,
+        values: &BTreeMap<String, Value>,
+    ) -> Result<(), Option<String>> {
+        if values.is_empty() {
+            return Ok(());
+        }
+
+        let fields: HashMap<String, AppField> = self
+            .fields_repo
+            .list_app_fields()
+            .await
+            .map_err(|e| {
+                error!("{}", e);
+                None
+            })?
+            .into_iter()
+            .map(|field| (field.key.clone(), field))
             .collect();
 
-        known_apps
+        for (key, value) in values {
+            let field = fields
+                .get(key)
+                .ok_or_else(|| Some(format!("Custom field {key} is not defined")))?;
+
+            if value.is_null() {
+                continue;
+            }
+
+            if let Some(user_id) = validate_value(field, value).map_err(Some)? {
+                match self.users_repo.get_user(user_id).await {
+                    Ok(_) => {}
+                    Err(GetUserError::ResourceNotFound(_)) => {
+                        return Err(Some(format!(
+                            "User {user_id} set on field {key} does not exist"
+                        )))
+                    }
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(None);
+                    }
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Fills in `App.users` for every app with a single read of the app user links
+    #[tracing::instrument(skip(self, apps))]
+    async fn with_user_counts(&self, mut apps: Vec<App>) -> Result<Vec<App>, ListAppsError> {
+        let counts = self
+            .app_users_repo
+            .count_active_users()
+            .await
+            .map_err(|e| {
+                error!("{}", e);
+                ListAppsError::UnexpectedError
+            })?;
+
+        for app in apps.iter_mut() {
+            app.users = Some(counts.get(&app.id).copied().unwrap_or_default());
+        }
+
+        Ok(apps)
+    }
+}
+
+/// The known apps catalog is the same for every tenant
+impl<R, U, M, C, F, A, W> TenantScoped for AppsService<R, U, M, C, F, A, W>
+where
+    R: AppsRepository,
+    U: UsersRepository,
+    M: AppUsersRepository,
+    C: ContractsRepository,
+    F: AppFieldsRepository,
+    A: AuditRepository,
+    W: WebhooksRepository,
+{
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            repo: self.repo.for_tenant(tenant),
+            users_repo: self.users_repo.for_tenant(tenant),
+            app_users_repo: self.app_users_repo.for_tenant(tenant),
+            contracts_repo: self.contracts_repo.for_tenant(tenant),
+            fields_repo: self.fields_repo.for_tenant(tenant),
+            audit_repo: self.audit_repo.for_tenant(tenant),
+            webhooks_repo: self.webhooks_repo.for_tenant(tenant),
+            dispatcher: self.dispatcher.for_tenant(tenant),
+            catalog: self.catalog.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl<R, U, M, C, F, A, W> AppsServiceTrait for AppsService<R, U, M, C, F, A, W>
+where
+    R: AppsRepository,
+    U: UsersRepository,
+    M: AppUsersRepository,
+    C: ContractsRepository,
+    F: AppFieldsRepository,
+    A: AuditRepository,
+    W: WebhooksRepository,
+{
+    #[tracing::instrument(skip(self))]
+    async fn get_app(&self, id: u16) -> Result<App, GetAppError> {
+        let mut app = self.repo.get_app(id).await?;
+
+        let app_users = self.app_users_repo.list_app_users(id).await.map_err(|e| {
+            error!("{}", e);
+            GetAppError::UnexpectedError
+        })?;
+        let active_users = app_users
+            .iter()
+            .filter(|app_user| app_user.license_status == LicenseStatus::Active)
+            .count();
+        app.users = Some(u32::try_from(active_users).unwrap_or(u32::MAX));
+
+        Ok(app)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn add_app(
+        &self,
+        body: AddAppHttpRequestBody,
+        context: RequestContext,
+    ) -> Result<App, AddAppError> {
+        let app = prepare_add(&self.catalog, &body)?;
+
+        let app = self.repo.add_app(app).await?;
+        self.app_created(&context, AuditAction::Add, &app).await;
+
+        Ok(app)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn create_app(
+        &self,
+        request: CreateAppHttpRequestBody,
+        context: RequestContext,
+    ) -> Result<App, CreateAppError> {
+        let app = self.prepare_create(request).await?;
+
+        let app = self.repo.create_app(app).await?;
+        self.app_created(&context, AuditAction::Create, &app).await;
+
+        Ok(app)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_app(&self, id: u16, context: RequestContext) -> Result<(), DeleteAppError> {
+        let before = self.get_app_for_delete(id).await?;
+
+        self.repo.delete_app(id).await?;
+        self.app_deleted(&context, &before).await;
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_apps(&self, params: ListAppsQueryParams) -> Result<AppsPage, ListAppsError> {
+        let filter = AppsFilter::from(&params);
+
+        if let Some(sort) = params.sort {
+            // Scan order can't be sorted, so the whole catalog is read and the cursor becomes
+            // an offset into the sorted list
+            let mut apps = self.repo.list_filtered_apps(&filter).await?;
+            sort_apps(&mut apps, sort, params.order.unwrap_or_default(), |app| app);
+
+            let page = paginate(apps, params.size, params.cursor)?;
+
+            return Ok(AppsPage {
+                apps: self.with_user_counts(page.apps).await?,
+                ..page
+            });
+        }
+
+        if params.size.is_none() && params.cursor.is_none() {
+            let mut apps = self.repo.list_filtered_apps(&filter).await?;
+            apps.sort_by_key(|app| app.id);
+
+            return Ok(AppsPage {
+                apps: self.with_user_counts(apps).await?,
+                next_cursor: None,
+            });
+        }
+
+        let page = self
+            .repo
+            .list_apps_page(params.size, params.cursor, &filter)
+            .await?;
+
+        Ok(AppsPage {
+            apps: self.with_user_counts(page.apps).await?,
+            ..page
+        })
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_known_apps(
+        &self,
+        params: ListKnownAppsQueryParams,
+    ) -> Result<KnownAppsPage, ListKnownAppsError> {
+        let filter = KnownAppsFilter {
+            name: params.name,
+            category: params.category,
+        };
+
+        self.catalog.page(&filter, params.size, params.cursor)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_known_app(&self, id: u16) -> Result<KnownApp, GetKnownAppError> {
+        self.catalog
+            .get(id)
+            .cloned()
+            .ok_or(GetKnownAppError::ResourceNotFound(id))
     }
 
     #[tracing::instrument(skip(self))]
@@ -132,109 +872,804 @@ where
         &self,
         request: UpdateAppHttpRequestBody,
         id: u16,
+        expected_version: Option<u64>,
+        context: RequestContext,
     ) -> Result<App, UpdateAppError> {
-        let mut app = self.get_app(id).await.map_err(|e| match e {
-            GetAppError::ResourceNotFound(id) => UpdateAppError::ResourceNotFound(id),
+        let (before, app) = self.prepare_update(request, id, expected_version).await?;
+
+        let app = self.repo.update_app(app).await?;
+        self.app_updated(&context, &before, &app).await;
+
+        Ok(app)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn patch_app(
+        &self,
+        patch: AppPatch,
+        id: u16,
+        expected_version: Option<u64>,
+        context: RequestContext,
+    ) -> Result<App, UpdateAppError> {
+        let before = self.get_app_for_update(id, expected_version).await?;
+
+        let document =
+            AppPatchDocument::patched(&before, &patch).map_err(UpdateAppError::InvalidPatch)?;
+        document
+            .validate()
+            .map_err(|e| UpdateAppError::InvalidPatch(e.to_string()))?;
+
+        let values = document.field_changes(&before);
+        self.validate_custom_fields(&values)
+            .await
+            .map_err(|e| match e {
+                Some(msg) => UpdateAppError::InvalidCustomField(msg),
+                None => UpdateAppError::UnexpectedError,
+            })?;
+
+        let mut app = before.clone();
+        app.name = document.name;
+        app.state = document.state;
+        app.url = document.url;
+        app.category = document.category;
+        app.description = document.description;
+        app.tags = document.tags;
+        app.last_updated_at = Utc::now();
+        apply_values(&mut app.fields, values);
+
+        let app = self.repo.update_app(app).await?;
+        self.app_updated(&context, &before, &app).await;
+
+        Ok(app)
+    }
+
+    /// Operations run in order. Outside a transaction each one is applied on its own, exactly
+    /// as the single app route would. In a transaction every operation is checked first and
+    /// the writes are then applied together, so either all of them succeed or none are applied
+    #[tracing::instrument(skip(self))]
+    async fn bulk_apps(
+        &self,
+        operations: Vec<BulkAppOperation>,
+        transactional: bool,
+        context: RequestContext,
+    ) -> Vec<Result<Option<App>, BulkAppOperationError>> {
+        if transactional {
+            return self.bulk_apps_transaction(operations, context).await;
+        }
+
+        let mut results = Vec::with_capacity(operations.len());
+
+        for operation in operations {
+            if let Err(e) = operation.validate() {
+                results.push(Err(BulkAppOperationError::InvalidRequest(e.to_string())));
+                continue;
+            }
+
+            let result = match operation {
+                BulkAppOperation::Add { app } => self
+                    .add_app(app, context.clone())
+                    .await
+                    .map(Some)
+                    .map_err(Into::into),
+                BulkAppOperation::Create { app } => self
+                    .create_app(app, context.clone())
+                    .await
+                    .map(Some)
+                    .map_err(Into::into),
+                BulkAppOperation::Update { id, version, app } => self
+                    .update_app(app, id, version, context.clone())
+                    .await
+                    .map(Some)
+                    .map_err(Into::into),
+                BulkAppOperation::Delete { id } => self
+                    .delete_app(id, context.clone())
+                    .await
+                    .map(|_| None)
+                    .map_err(Into::into),
+            };
+
+            results.push(result);
+        }
+
+        results
+    }
+
+    #[tracing::instrument(skip(self, sheet))]
+    async fn import_apps(
+        &self,
+        sheet: ImportSheet,
+        mapping: BTreeMap<String, String>,
+        dry_run: bool,
+        context: RequestContext,
+    ) -> Result<Vec<ImportRowResult>, ImportAppsError> {
+        let fields = self.fields_repo.list_app_fields().await.map_err(|e| {
+            error!("{}", e);
+            ImportAppsError::UnexpectedError
+        })?;
+
+        let columns = ColumnMapping::resolve(&sheet.headers, &mapping, &fields)
+            .map_err(ImportAppsError::InvalidMapping)?;
+        let fields: HashMap<String, AppField> = fields
+            .into_iter()
+            .map(|field| (field.key.clone(), field))
+            .collect();
+
+        let mut names = HashSet::new();
+        let mut results = Vec::with_capacity(sheet.rows.len());
+
+        for row in sheet.rows {
+            let result = |status, name, app, errors| ImportRowResult {
+                row: row.row,
+                name,
+                status,
+                app,
+                errors,
+            };
+
+            let request = match columns.request(&row, &fields) {
+                Ok(request) => request,
+                Err(errors) => {
+                    results.push(result(ImportRowStatus::Invalid, None, None, errors));
+                    continue;
+                }
+            };
+            let name = Some(request.name.clone());
+
+            let mut errors = Vec::new();
+
+            if let Err(e) = request.validate() {
+                errors.push(e.to_string());
+            }
+
+            match self
+                .validate_custom_fields(request.fields.as_ref().unwrap_or(&BTreeMap::new()))
+                .await
+            {
+                Ok(()) => {}
+                Err(Some(msg)) => errors.push(msg),
+                Err(None) => return Err(ImportAppsError::UnexpectedError),
+            }
+
+            if !errors.is_empty() {
+                results.push(result(ImportRowStatus::Invalid, name, None, errors));
+                continue;
+            }
+
+            let duplicate = !names.insert(request.name.clone())
+                || match self.repo.get_app_by_name(&request.name).await {
+                    Ok(_) => true,
+                    Err(GetAppError::ResourceNotFound(_)) => false,
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(ImportAppsError::UnexpectedError);
+                    }
+                };
+
+            if duplicate {
+                let errors = vec![format!("App {} already exists", request.name)];
+                results.push(result(ImportRowStatus::Duplicate, name, None, errors));
+                continue;
+            }
+
+            if dry_run {
+                results.push(result(ImportRowStatus::Valid, name, None, Vec::new()));
+                continue;
+            }
+
+            results.push(match self.create_app(request, context.clone()).await {
+                Ok(app) => result(ImportRowStatus::Created, name, Some(app), Vec::new()),
+                Err(e @ CreateAppError::ResourceAlreadyExists { .. }) => {
+                    result(ImportRowStatus::Duplicate, name, None, vec![e.to_string()])
+                }
+                Err(e) => result(ImportRowStatus::Failed, name, None, vec![e.to_string()]),
+            });
+        }
+
+        Ok(results)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn search_apps(
+        &self,
+        params: SearchAppsQueryParams,
+    ) -> Result<Vec<ScoredApp>, ListAppsError> {
+        let apps = self.with_user_counts(self.repo.list_apps().await?).await?;
+
+        let mut found_apps: Vec<ScoredApp> = apps
+            .into_iter()
+            .map(|app| ScoredApp {
+                score: score_app(&app, &params.query),
+                app,
+            })
+            .filter(|scored| scored.score > 0.0)
+            .collect();
+
+        if let Some(sort) = params.sort {
+            sort_apps(
+                &mut found_apps,
+                sort,
+                params.order.unwrap_or_default(),
+                |scored| &scored.app,
+            );
+        } else {
+            found_apps.sort_by(|a, b| {
+                b.score
+                    .total_cmp(&a.score)
+                    .then_with(|| a.app.name.cmp(&b.app.name))
+            });
+        }
+
+        Ok(found_apps)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_app_users(&self, id: u16) -> Result<Vec<AppUserDetails>, ListAppUsersError> {
+        self.repo.get_app(id).await.map_err(|e| match e {
+            GetAppError::ResourceNotFound(id) => {
+                ListAppUsersError::ResourceNotFound(format!("App {id}"))
+            }
             _ => {
                 error!("{}", e);
-                UpdateAppError::UnexpectedError
+                ListAppUsersError::UnexpectedError
+            }
+        })?;
+
+        let mut users = Vec::new();
+
+        for app_user in self.app_users_repo.list_app_users(id).await? {
+            match self.users_repo.get_user(app_user.user_id).await {
+                Ok(user) => users.push(AppUserDetails {
+                    user,
+                    license_status: app_user.license_status,
+                    last_used_at: app_user.last_used_at,
+                }),
+                // Left behind by a user deletion that failed part way through
+                Err(GetUserError::ResourceNotFound(_)) => {}
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(ListAppUsersError::UnexpectedError);
+                }
+            }
+        }
+
+        users.sort_by(|a, b| a.user.email.cmp(&b.user.email));
+
+        Ok(users)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_user_apps(&self, user_id: u32) -> Result<Vec<UserAppDetails>, ListAppUsersError> {
+        self.users_repo
+            .get_user(user_id)
+            .await
+            .map_err(|e| match e {
+                GetUserError::ResourceNotFound(id) => {
+                    ListAppUsersError::ResourceNotFound(format!("User {id}"))
+                }
+                _ => {
+                    error!("{}", e);
+                    ListAppUsersError::UnexpectedError
+                }
+            })?;
+
+        let mut apps = Vec::new();
+
+        for app_user in self.app_users_repo.list_user_apps(user_id).await? {
+            match self.get_app(app_user.app_id).await {
+                Ok(app) => apps.push(UserAppDetails {
+                    app,
+                    license_status: app_user.license_status,
+                    last_used_at: app_user.last_used_at,
+                }),
+                // Left behind by an app deletion that failed part way through
+                Err(GetAppError::ResourceNotFound(_)) => {}
+                Err(e) => {
+                    error!("{}", e);
+                    return Err(ListAppUsersError::UnexpectedError);
+                }
+            }
+        }
+
+        apps.sort_by_key(|details| details.app.id);
+
+        Ok(apps)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn put_app_user(
+        &self,
+        request: PutAppUserHttpRequestBody,
+        id: u16,
+        user_id: u32,
+    ) -> Result<AppUser, PutAppUserError> {
+        self.repo.get_app(id).await.map_err(|e| match e {
+            GetAppError::ResourceNotFound(id) => {
+                PutAppUserError::ResourceNotFound(format!("App {id}"))
+            }
+            _ => {
+                error!("{}", e);
+                PutAppUserError::UnexpectedError
+            }
+        })?;
+
+        self.users_repo
+            .get_user(user_id)
+            .await
+            .map_err(|e| match e {
+                GetUserError::ResourceNotFound(id) => {
+                    PutAppUserError::ResourceNotFound(format!("User {id}"))
+                }
+                _ => {
+                    error!("{}", e);
+                    PutAppUserError::UnexpectedError
+                }
+            })?;
+
+        let existing = self
+            .app_users_repo
+            .list_user_apps(user_id)
+            .await
+            .map_err(|e| {
+                error!("{}", e);
+                PutAppUserError::UnexpectedError
+            })?
+            .into_iter()
+            .find(|app_user| app_user.app_id == id);
+
+        let now = Utc::now();
+        let mut app_user = existing.unwrap_or_else(|| AppUser {
+            app_id: id,
+            user_id,
+            license_status: LicenseStatus::default(),
+            last_used_at: None,
+            creation_time: now,
+            last_updated_at: now,
+        });
+
+        if let Some(license_status) = request.license_status {
+            app_user.license_status = license_status
+        };
+
+        if let Some(last_used_at) = request.last_used_at {
+            app_user.last_used_at = Some(last_used_at)
+        };
+
+        app_user.last_updated_at = now;
+
+        self.app_users_repo.put_app_user(app_user).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_app_user(&self, id: u16, user_id: u32) -> Result<(), DeleteAppUserError> {
+        self.app_users_repo.delete_app_user(id, user_id).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_app_field(&self, key: &str) -> Result<AppField, GetAppFieldError> {
+        self.fields_repo.get_app_field(key).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn create_app_field(
+        &self,
+        request: CreateAppFieldHttpRequestBody,
+    ) -> Result<AppField, CreateAppFieldError> {
+        let options = request.options.unwrap_or_default();
+
+        validate_key(&request.key).map_err(CreateAppFieldError::InvalidField)?;
+        validate_options(request.field_type, &options)
+            .map_err(CreateAppFieldError::InvalidField)?;
+
+        let now = Utc::now();
+        let field = AppField {
+            key: request.key,
+            name: request.name,
+            field_type: request.field_type,
+            options,
+            creation_time: now,
+            last_updated_at: now,
+        };
+
+        self.fields_repo.create_app_field(field).await
+    }
+
+    /// Removes the field's value from every app before the definition goes, so no app is left
+    /// with a value for a field that doesn't exist. Each app that had one is audited and
+    /// announced like any other update, the definition is kept when an app can't be rewritten
+    #[tracing::instrument(skip(self))]
+    async fn delete_app_field(
+        &self,
+        key: &str,
+        context: RequestContext,
+    ) -> Result<(), DeleteAppFieldError> {
+        self.fields_repo
+            .get_app_field(key)
+            .await
+            .map_err(|e| match e {
+                GetAppFieldError::ResourceNotFound(key) => {
+                    DeleteAppFieldError::ResourceNotFound(key)
+                }
+                _ => {
+                    error!("{}", e);
+                    DeleteAppFieldError::UnexpectedError
+                }
+            })?;
+
+        let apps = self.repo.list_apps().await.map_err(|e| {
+            error!("{}", e);
+            DeleteAppFieldError::UnexpectedError
+        })?;
+
+        for app in apps {
+            self.remove_app_field_value(app, key, &context).await?;
+        }
+
+        self.fields_repo.delete_app_field(key).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_app_fields(&self) -> Result<Vec<AppField>, ListAppFieldsError> {
+        let mut fields = self.fields_repo.list_app_fields().await?;
+        fields.sort_by(|a, b| a.key.cmp(&b.key));
+
+        Ok(fields)
+    }
+
+    /// The type is fixed once created since existing values would no longer match it
+    #[tracing::instrument(skip(self))]
+    async fn update_app_field(
+        &self,
+        request: UpdateAppFieldHttpRequestBody,
+        key: &str,
+    ) -> Result<AppField, UpdateAppFieldError> {
+        let mut field = self.get_app_field(key).await.map_err(|e| match e {
+            GetAppFieldError::ResourceNotFound(key) => UpdateAppFieldError::ResourceNotFound(key),
+            _ => {
+                error!("{}", e);
+                UpdateAppFieldError::UnexpectedError
             }
         })?;
 
         if let Some(name) = request.name {
-            app.name = name
+            field.name = name
         };
 
-        if let Some(state) = request.state {
-            app.state = state
+        if let Some(options) = request.options {
+            validate_options(field.field_type, &options)
+                .map_err(UpdateAppFieldError::InvalidField)?;
+            field.options = options
+        };
+
+        field.last_updated_at = Utc::now();
+
+        self.fields_repo.update_app_field(field).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_audit_events(
+        &self,
+        params: ListAuditEventsQueryParams,
+    ) -> Result<Vec<AuditEvent>, ListAuditEventsError> {
+        let filter = AuditFilter {
+            app_id: params.app_id,
+            action: params.action,
+            from: params.from,
+            to: params.to,
+        };
+
+        let mut events = self.audit_repo.list_events(&filter).await?;
+        events.sort_by(|a, b| a.timestamp.cmp(&b.timestamp).then_with(|| a.id.cmp(&b.id)));
+
+        Ok(events)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_webhook(&self, id: &str) -> Result<Webhook, GetWebhookError> {
+        self.webhooks_repo.get_webhook(id).await
+    }
+
+    /// A random secret is generated when the caller doesn't provide one
+    #[tracing::instrument(skip(self))]
+    async fn create_webhook(
+        &self,
+        request: CreateWebhookHttpRequestBody,
+    ) -> Result<Webhook, CreateWebhookError> {
+        let now = Utc::now();
+        let webhook = Webhook {
+            id: Uuid::new_v4().to_string(),
+            url: request.url,
+            events: dedup_events(request.events),
+            secret: request.secret.unwrap_or_else(generate_secret),
+            active: request.active.unwrap_or(true),
+            creation_time: now,
+            last_updated_at: now,
         };
 
+        self.webhooks_repo.create_webhook(webhook).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_webhook(&self, id: &str) -> Result<(), DeleteWebhookError> {
+        self.webhooks_repo.delete_webhook(id).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>, ListWebhooksError> {
+        let mut webhooks = self.webhooks_repo.list_webhooks().await?;
+        webhooks.sort_by(|a, b| {
+            a.creation_time
+                .cmp(&b.creation_time)
+                .then_with(|| a.id.cmp(&b.id))
+        });
+
+        Ok(webhooks)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn update_webhook(
+        &self,
+        request: UpdateWebhookHttpRequestBody,
+        id: &str,
+    ) -> Result<Webhook, UpdateWebhookError> {
+        let mut webhook = self.get_webhook(id).await.map_err(|e| match e {
+            GetWebhookError::ResourceNotFound(id) => UpdateWebhookError::ResourceNotFound(id),
+            _ => {
+                error!("{}", e);
+                UpdateWebhookError::UnexpectedError
+            }
+        })?;
+
         if let Some(url) = request.url {
-            app.url = url
+            webhook.url = url
         };
 
-        if let Some(category) = request.category {
-            app.category = category
+        if let Some(events) = request.events {
+            webhook.events = dedup_events(events)
         };
 
-        app.description = request.description;
-        app.tags = request.tags;
-        app.last_updated_at = Utc::now();
+        if let Some(secret) = request.secret {
+            webhook.secret = secret
+        };
+
+        if let Some(active) = request.active {
+            webhook.active = active
+        };
 
-        self.repo.update_app(app).await
+        webhook.last_updated_at = Utc::now();
+
+        self.webhooks_repo.update_webhook(webhook).await
     }
 
+    /// Newest first so the latest failures are at the top
     #[tracing::instrument(skip(self))]
-    async fn search_apps(&self, params: SearchAppsQueryParams) -> Result<Vec<App>, ListAppsError> {
-        let apps = self.repo.list_apps().await?;
+    async fn list_webhook_deliveries(
+        &self,
+        id: &str,
+    ) -> Result<Vec<WebhookDelivery>, ListWebhookDeliveriesError> {
+        self.get_webhook(id).await.map_err(|e| match e {
+            GetWebhookError::ResourceNotFound(id) => {
+                ListWebhookDeliveriesError::ResourceNotFound(id)
+            }
+            _ => {
+                error!("{}", e);
+                ListWebhookDeliveriesError::UnexpectedError
+            }
+        })?;
 
-        let found_apps = apps
-            .into_iter()
-            .filter(|app| app.name.contains(&params.query))
-            .collect();
+        let mut deliveries = self.webhooks_repo.list_deliveries(id).await?;
+        deliveries.sort_by_key(|delivery| Reverse(delivery.creation_time));
 
-        Ok(found_apps)
+        Ok(deliveries)
     }
 }
 
-/// Pre-defined apps that can be added
-#[tracing::instrument]
-fn get_default_app(id: u16) -> Option<App> {
-    let default_apps = default_apps();
+/// Keeps the first occurrence of every event type
+fn dedup_events(events: Vec<WebhookEventType>) -> Vec<WebhookEventType> {
+    let mut unique = Vec::with_capacity(events.len());
 
-    default_apps.get(&id).cloned()
+    for event in events {
+        if !unique.contains(&event) {
+            unique.push(event);
+        }
+    }
+
+    unique
 }
 
-fn default_apps() -> HashMap<u16, App> {
-    let mut default_apps: HashMap<u16, App> = HashMap::new();
+fn generate_secret() -> String {
+    hex::encode(rand::thread_rng().gen::<[u8; 32]>())
+}
 
-    default_apps.insert(
-        1000,
-        App {
-            id: 1000,
-            name: "Salesforce".to_string(),
-            category: AppCategory::SalesAndMarketing,
-            url: "https://salesforce.com".to_string(),
-            state: AppState::Sanctioned,
-            ..Default::default()
-        },
-    );
-
-    default_apps.insert(
-        1001,
-        App {
-            id: 1001,
-            name: "Zoom".to_string(),
-            category: AppCategory::Productivity,
-            url: "https://zoom.com".to_string(),
-            state: AppState::Sanctioned,
-            ..Default::default()
-        },
-    );
-
-    default_apps.insert(
-        1002,
-        App {
-            id: 1002,
-            name: "GitHub".to_string(),
-            category: AppCategory::DeveloperTools,
-            url: "https://github.com".to_string(),
-            state: AppState::Sanctioned,
-            ..Default::default()
-        },
-    );
-
-    default_apps.insert(
-        1003,
-        App {
-            id: 1003,
-            name: "Cats".to_string(),
-            category: AppCategory::Other,
-            url: "https://cats.com".to_string(),
-            state: AppState::Sanctioned,
-            ..Default::default()
+/// Field by field difference between two versions of an app, a missing version counts as every
+/// field being `null`. The user count is left out since it is computed rather than stored
+fn diff_apps(before: Option<&App>, after: Option<&App>) -> BTreeMap<String, AuditChange> {
+    let to_fields = |app: Option<&App>| match app.map(serde_json::to_value) {
+        Some(Ok(Value::Object(fields))) => fields,
+        _ => serde_json::Map::new(),
+    };
+
+    let before = to_fields(before);
+    let after = to_fields(after);
+
+    before
+        .keys()
+        .chain(after.keys())
+        .filter(|key| key.as_str() != "users")
+        .filter_map(|key| {
+            let old = before.get(key).cloned().unwrap_or(Value::Null);
+            let new = after.get(key).cloned().unwrap_or(Value::Null);
+
+            (old != new).then(|| {
+                (
+                    key.clone(),
+                    AuditChange {
+                        before: old,
+                        after: new,
+                    },
+                )
+            })
+        })
+        .collect()
+}
+
+/// Pages through an already sorted list, the cursor is the offset of the next app
+fn paginate(
+    apps: Vec<App>,
+    size: Option<u16>,
+    cursor: Option<String>,
+) -> Result<AppsPage, ListAppsError> {
+    let offset = match cursor {
+        Some(cursor) => cursor
+            .parse::<usize>()
+            .map_err(|_| ListAppsError::InvalidCursor(cursor))?,
+        None => 0,
+    };
+
+    let end = size.map_or(apps.len(), |size| offset.saturating_add(usize::from(size)));
+    let next_cursor = (end < apps.len()).then(|| end.to_string());
+    let apps = apps
+        .into_iter()
+        .skip(offset)
+        .take(end.saturating_sub(offset))
+        .collect();
+
+    Ok(AppsPage { apps, next_cursor })
+}
+
+/// Looks up the known app `add_app` stores
+fn prepare_add(
+    catalog: &KnownAppsCatalog,
+    body: &AddAppHttpRequestBody,
+) -> Result<App, AddAppError> {
+    catalog
+        .app(body.id_app)
+        .ok_or(AddAppError::ResourceNotFound(body.id_app))
+}
+
+/// A checked operation of a bulk transaction, `before` is the stored app for updates and
+/// deletes
+#[derive(Debug)]
+struct PreparedWrite {
+    action: AuditAction,
+    before: Option<App>,
+    write: AppWrite,
+}
+
+impl PreparedWrite {
+    /// The error the single app operation reports when its write condition fails
+    fn condition_failed(&self) -> BulkAppOperationError {
+        match (&self.write, self.action) {
+            (AppWrite::Put(app), AuditAction::Add) => AddAppError::ResourceAlreadyExists {
+                name: app.name.clone(),
+            }
+            .into(),
+            (AppWrite::Put(app), _) => CreateAppError::ResourceAlreadyExists {
+                name: app.name.clone(),
+            }
+            .into(),
+            (AppWrite::Update(app), _) => UpdateAppError::VersionConflict(app.id).into(),
+            (AppWrite::Delete(id), _) => DeleteAppError::ResourceNotFound(*id).into(),
+        }
+    }
+
+    fn unexpected(&self) -> BulkAppOperationError {
+        match self.action {
+            AuditAction::Add => AddAppError::UnexpectedError.into(),
+            AuditAction::Create => CreateAppError::UnexpectedError.into(),
+            AuditAction::Update => UpdateAppError::UnexpectedError.into(),
+            AuditAction::Delete => DeleteAppError::UnexpectedError.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::{
+        api_keys_service::Principal,
+        repository::{
+            AppCategory, AppState, DeliveryStatus, InMemoryAppFieldsRepository,
+            InMemoryAppUsersRepository, InMemoryAppsRepository, InMemoryAuditRepository,
+            InMemoryContractsRepository, InMemoryUsersRepository, InMemoryWebhooksRepository,
         },
-    );
+    };
+
+    fn context() -> RequestContext {
+        RequestContext::from_headers(
+            &Default::default(),
+            None,
+            &Principal {
+                tenant: Tenant::default(),
+                scopes: Vec::new(),
+                subject: "test".to_string(),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn bulk_request_with_unresponsive_webhook_stops_at_the_deadline() {
+        // Accepts connections but never answers, every attempt hangs until it times out
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let webhooks_repo = InMemoryWebhooksRepository::new();
+        let mut service = AppsService::new(
+            InMemoryAppsRepository::new(),
+            InMemoryUsersRepository::new(),
+            InMemoryAppUsersRepository::new(),
+            InMemoryContractsRepository::new(),
+            InMemoryAppFieldsRepository::new(),
+            InMemoryAuditRepository::new(),
+            webhooks_repo.clone(),
+            Arc::new(KnownAppsCatalog::default()),
+        );
+        service.dispatcher = WebhookDispatcher::new(
+            webhooks_repo.clone(),
+            RetryPolicy {
+                deadline: Duration::from_millis(300),
+                ..Default::default()
+            },
+        );
+
+        let now = Utc::now();
+        webhooks_repo
+            .create_webhook(Webhook {
+                id: "hook".to_string(),
+                url: format!("http://{}/", listener.local_addr().unwrap()),
+                events: vec![WebhookEventType::Created],
+                secret: "secret".to_string(),
+                active: true,
+                creation_time: now,
+                last_updated_at: now,
+            })
+            .await
+            .unwrap();
 
-    default_apps
+        let operations = (0..20)
+            .map(|i| BulkAppOperation::Create {
+                app: CreateAppHttpRequestBody {
+                    name: format!("App {i}"),
+                    state: AppState::Discovered,
+                    url: format!("https://app{i}.example.com"),
+                    category: AppCategory::Operations,
+                    description: None,
+                    tags: None,
+                    fields: None,
+                },
+            })
+            .collect();
+
+        let started = std::time::Instant::now();
+        let results = service.bulk_apps(operations, false, context()).await;
+
+        assert!(started.elapsed() < Duration::from_secs(2));
+        assert!(results.iter().all(Result::is_ok));
+
+        let deliveries = webhooks_repo.list_deliveries("hook").await.unwrap();
+
+        assert_eq!(deliveries.len(), 20);
+        assert!(deliveries
+            .iter()
+            .all(|delivery| delivery.status == DeliveryStatus::Failed));
+    }
 }
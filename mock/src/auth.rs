@@ -1,20 +1,35 @@
 use axum::{
     extract::{Request, State},
-    http::StatusCode,
+    http::{Method, StatusCode},
     middleware::Next,
     response::Response,
 };
+use lambda_http::RequestExt;
 use tracing::{debug, warn};
 
-use crate::{apps_service::AppsServiceTrait, backend::Backend};
+use crate::{
+    apps_service::AppsServiceTrait,
+    backend::{AuthMode, Backend},
+    contracts_service::ContractsServiceTrait,
+    faults::FAULTS_PATH,
+    jwt::JwtAuth,
+    repository::{ApiKeyScope, AuthenticateError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+/// Routes under this prefix manage access to the mock rather than tenant data
+const ADMIN_PATH_PREFIX: &str = "/v1.0/admin/";
 
-pub async fn auth<AS>(
-    State(state): State<Backend<AS>>,
-    req: Request,
+pub async fn auth<AS, US, CS>(
+    State(state): State<Backend<AS, US, CS>>,
+    mut req: Request,
     next: Next,
 ) -> Result<Response, StatusCode>
 where
     AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
 {
     let auth_header = req
         .headers()
@@ -23,14 +38,56 @@ where
 
     debug!("Auth header: {:#?}", auth_header);
 
-    if let Some(auth_header) = auth_header {
-        if auth_header != format!("Bearer {}", state.auth_api_key) {
-            return Err(StatusCode::UNAUTHORIZED);
-        }
-    } else {
+    let Some(auth_header) = auth_header else {
         warn!("No authorization header provided");
         return Err(StatusCode::UNAUTHORIZED);
     };
 
+    let token = auth_header
+        .strip_prefix("Bearer ")
+        .ok_or(StatusCode::UNAUTHORIZED)?;
+
+    let principal = match (state.auth_mode, &state.jwt) {
+        (AuthMode::Jwt, Some(jwt)) => jwt.authenticate(token),
+        (AuthMode::Both, Some(jwt)) if JwtAuth::looks_like_jwt(token) => jwt.authenticate(token),
+        _ => state.api_keys_service.authenticate(token).await,
+    }
+    .map_err(|e| match e {
+        AuthenticateError::UnexpectedError => StatusCode::INTERNAL_SERVER_ERROR,
+        _ => {
+            warn!("{}", e);
+            StatusCode::UNAUTHORIZED
+        }
+    })?;
+
+    let required = required_scope(req.method(), req.uri().path());
+    if !principal.allows(required) {
+        warn!("Token lacks the {} scope", required);
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    // Faults hit every tenant, so other tenants' admins can't be allowed to set them
+    if req.uri().path() == FAULTS_PATH && !principal.tenant.is_default() {
+        warn!("Tenant {} can't manage faults", principal.tenant);
+        return Err(StatusCode::FORBIDDEN);
+    }
+
+    let context =
+        RequestContext::from_headers(req.headers(), req.request_context_ref(), &principal);
+    req.extensions_mut().insert(context);
+    req.extensions_mut().insert(principal);
+
     Ok(next.run(req).await)
 }
+
+/// Reads only need `read`, anything else needs `write` apart from managing keys which needs
+/// `admin`
+fn required_scope(method: &Method, path: &str) -> ApiKeyScope {
+    if path.starts_with(ADMIN_PATH_PREFIX) {
+        ApiKeyScope::Admin
+    } else if matches!(*method, Method::GET | Method::HEAD | Method::OPTIONS) {
+        ApiKeyScope::Read
+    } else {
+        ApiKeyScope::Write
+    }
+}
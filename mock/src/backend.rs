@@ -1,15 +1,66 @@
-use std::{env, sync::Arc};
+use std::{env, path::PathBuf, sync::Arc};
 
 use aws_config::{self, BehaviorVersion};
-use aws_sdk_dynamodb;
 use serde::{Deserialize, Serialize};
 use tracing::instrument;
+use validator::Validate;
 
 use crate::{
+    api_keys_service::ApiKeysService,
     apps_service::{AppsService, AppsServiceTrait},
-    repository::DynamoAppsRepository,
+    catalog::KnownAppsCatalog,
+    contracts_service::{ContractsService, ContractsServiceTrait},
+    faults::{FaultConfig, FaultInjector},
+    jwt::{JwtAuth, JwtSettings},
+    rate_limit::RateLimiter,
+    repository::{
+        ConfiguredApiKeysRepository, ConfiguredAppFieldsRepository, ConfiguredAppUsersRepository,
+        ConfiguredAppsRepository, ConfiguredAuditRepository, ConfiguredContractsRepository,
+        ConfiguredUsersRepository, ConfiguredWebhooksRepository, DynamoApiKeysRepository,
+        DynamoAppFieldsRepository, DynamoAppUsersRepository, DynamoAppsRepository,
+        DynamoAuditRepository, DynamoContractsRepository, DynamoUsersRepository,
+        DynamoWebhooksRepository, FileState, InMemoryApiKeysRepository,
+        InMemoryAppFieldsRepository, InMemoryAppUsersRepository, InMemoryAppsRepository,
+        InMemoryAuditRepository, InMemoryContractsRepository, InMemoryUsersRepository,
+        InMemoryWebhooksRepository,
+    },
+    tenant::TenantKeys,
+    users_service::{UsersService, UsersServiceTrait},
 };
 
+/// Which `AppsRepository` implementation backs the service, set with `REPOSITORY_BACKEND`
+#[derive(Serialize, Deserialize, Clone, Debug, Default, strum::EnumString, strum::Display)]
+#[strum(serialize_all = "lowercase")]
+pub enum RepositoryBackend {
+    #[default]
+    Dynamo,
+    Memory,
+    File,
+}
+
+/// Which bearer tokens the auth middleware accepts, set with `AUTH_MODE`
+#[derive(
+    Serialize,
+    Deserialize,
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    PartialEq,
+    strum::EnumString,
+    strum::Display,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum AuthMode {
+    /// API keys only
+    #[default]
+    ApiKey,
+    /// JWTs only, signed with `JWT_SECRET` or a key from `JWT_JWKS_FILE`
+    Jwt,
+    /// JWTs and API keys, told apart by their shape
+    Both,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct TableSettings {
     pub table_name: String,
@@ -19,54 +70,272 @@ pub struct TableSettings {
 #[derive(Serialize, Deserialize, Clone, Debug)]
 pub struct Settings {
     pub environment: String,
+    pub repository: RepositoryBackend,
     pub table: TableSettings,
+    pub state_file: PathBuf,
+    /// Catalog of known apps, the built-in `catalog/known_apps.yaml` is used when unset
+    pub known_apps_file: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
-pub struct Backend<AS>
+pub struct Backend<AS, US, CS>
 where
     AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
 {
     pub apps_service: Arc<AS>,
-    pub auth_api_key: String,
+    pub users_service: Arc<US>,
+    pub contracts_service: Arc<CS>,
+    pub api_keys_service: Arc<ApiKeysService<ConfiguredApiKeysRepository>>,
+    pub auth_mode: AuthMode,
+    /// Set unless `AUTH_MODE` is `api_key`, it also issues the tokens of `/oauth/token`
+    pub jwt: Option<Arc<JwtAuth>>,
+    /// Set when `RATE_LIMIT_PER_MINUTE` is, requests are never limited otherwise
+    pub rate_limiter: Option<Arc<RateLimiter>>,
+    pub faults: Arc<FaultInjector>,
 }
 
 #[instrument]
-pub async fn setup() -> Backend<impl AppsServiceTrait> {
+pub async fn setup(
+) -> Backend<impl AppsServiceTrait, impl UsersServiceTrait, impl ContractsServiceTrait> {
     let environment = env::var_os("APP_ENVIRONMENT")
         .unwrap_or_default()
         .into_string()
         .unwrap();
 
-    let endpoint_url = if let Some(endpoint_url) = env::var_os("ENDPOINT_URL") {
-        Some(endpoint_url.into_string().unwrap())
-    } else {
-        None
-    };
+    let endpoint_url =
+        env::var_os("ENDPOINT_URL").map(|endpoint_url| endpoint_url.into_string().unwrap());
 
-    let api_key_secret = env::var_os("TORII_SECRET").unwrap().into_string().unwrap();
+    let repository = env::var("REPOSITORY_BACKEND")
+        .map(|backend| {
+            backend
+                .parse()
+                .unwrap_or_else(|_| panic!("Unknown REPOSITORY_BACKEND: {backend}"))
+        })
+        .unwrap_or_default();
+
+    let auth_mode = env::var("AUTH_MODE")
+        .map(|mode| {
+            mode.parse()
+                .unwrap_or_else(|_| panic!("Unknown AUTH_MODE: {mode}"))
+        })
+        .unwrap_or_default();
+    let jwt = get_jwt_auth(auth_mode).map(Arc::new);
 
     let settings = Settings {
         environment,
+        repository,
         table: TableSettings {
             endpoint_url,
             table_name: "torii-table".to_string(),
         },
+        state_file: env::var_os("REPOSITORY_FILE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|| PathBuf::from("torii-state.json")),
+        known_apps_file: env::var_os("KNOWN_APPS_FILE").map(PathBuf::from),
     };
 
-    let dynamo_client = get_dynamo_client(&settings).await;
-    let apps_repo = DynamoAppsRepository::new(dynamo_client, settings.table.table_name.clone());
+    let (
+        apps_repo,
+        users_repo,
+        app_users_repo,
+        contracts_repo,
+        fields_repo,
+        audit_repo,
+        webhooks_repo,
+        api_keys_repo,
+    ) = match settings.repository {
+        RepositoryBackend::Dynamo => {
+            let dynamo_client = get_dynamo_client(&settings).await;
+            let table_name = &settings.table.table_name;
+            let apps_repo = DynamoAppsRepository::new(dynamo_client.clone(), table_name.clone());
+            let users_repo = DynamoUsersRepository::new(dynamo_client.clone(), table_name.clone());
+            let app_users_repo =
+                DynamoAppUsersRepository::new(dynamo_client.clone(), table_name.clone());
+            let contracts_repo =
+                DynamoContractsRepository::new(dynamo_client.clone(), table_name.clone());
+            let fields_repo =
+                DynamoAppFieldsRepository::new(dynamo_client.clone(), table_name.clone());
+            let audit_repo = DynamoAuditRepository::new(dynamo_client.clone(), table_name.clone());
+            let webhooks_repo =
+                DynamoWebhooksRepository::new(dynamo_client.clone(), table_name.clone());
+            let api_keys_repo = DynamoApiKeysRepository::new(dynamo_client, table_name.clone());
+
+            apps_repo.create_atomic_counter().await;
+            users_repo.create_user_counter().await;
+            contracts_repo.create_contract_counter().await;
+
+            (
+                ConfiguredAppsRepository::Dynamo(apps_repo),
+                ConfiguredUsersRepository::Dynamo(users_repo),
+                ConfiguredAppUsersRepository::Dynamo(app_users_repo),
+                ConfiguredContractsRepository::Dynamo(contracts_repo),
+                ConfiguredAppFieldsRepository::Dynamo(fields_repo),
+                ConfiguredAuditRepository::Dynamo(audit_repo),
+                ConfiguredWebhooksRepository::Dynamo(webhooks_repo),
+                ConfiguredApiKeysRepository::Dynamo(api_keys_repo),
+            )
+        }
+        RepositoryBackend::Memory => (
+            ConfiguredAppsRepository::InMemory(InMemoryAppsRepository::new()),
+            ConfiguredUsersRepository::InMemory(InMemoryUsersRepository::new()),
+            ConfiguredAppUsersRepository::InMemory(InMemoryAppUsersRepository::new()),
+            ConfiguredContractsRepository::InMemory(InMemoryContractsRepository::new()),
+            ConfiguredAppFieldsRepository::InMemory(InMemoryAppFieldsRepository::new()),
+            ConfiguredAuditRepository::InMemory(InMemoryAuditRepository::new()),
+            ConfiguredWebhooksRepository::InMemory(InMemoryWebhooksRepository::new()),
+            ConfiguredApiKeysRepository::InMemory(InMemoryApiKeysRepository::new()),
+        ),
+        RepositoryBackend::File => {
+            let state = FileState::load(settings.state_file.clone());
+
+            (
+                ConfiguredAppsRepository::File(state.apps()),
+                ConfiguredUsersRepository::File(state.users()),
+                ConfiguredAppUsersRepository::File(state.app_users()),
+                ConfiguredContractsRepository::File(state.contracts()),
+                ConfiguredAppFieldsRepository::File(state.app_fields()),
+                ConfiguredAuditRepository::File(state.audit()),
+                ConfiguredWebhooksRepository::File(state.webhooks()),
+                ConfiguredApiKeysRepository::File(state.api_keys()),
+            )
+        }
+    };
 
-    apps_repo.create_atomic_counter().await;
+    let catalog = settings
+        .known_apps_file
+        .as_deref()
+        .map(KnownAppsCatalog::load)
+        .unwrap_or_default();
 
-    let apps_service = AppsService::new(apps_repo);
+    let apps_service = AppsService::new(
+        apps_repo.clone(),
+        users_repo.clone(),
+        app_users_repo.clone(),
+        contracts_repo.clone(),
+        fields_repo,
+        audit_repo,
+        webhooks_repo,
+        Arc::new(catalog),
+    );
+    let users_service = UsersService::new(users_repo, app_users_repo);
+    let contracts_service = ContractsService::new(contracts_repo, apps_repo);
 
-    let app_state = Backend {
+    Backend {
         apps_service: Arc::new(apps_service),
-        auth_api_key: get_secret_value(&api_key_secret).await,
+        users_service: Arc::new(users_service),
+        contracts_service: Arc::new(contracts_service),
+        api_keys_service: Arc::new(ApiKeysService::new(
+            api_keys_repo,
+            get_bootstrap_keys().await,
+        )),
+        auth_mode,
+        jwt,
+        rate_limiter: get_rate_limiter().map(Arc::new),
+        faults: Arc::new(FaultInjector::new(get_fault_config())),
+    }
+}
+
+/// Faults from `FAULTS`, the same JSON `/v1.0/admin/faults` takes. No faults when unset
+#[instrument]
+fn get_fault_config() -> FaultConfig {
+    let Ok(faults) = env::var("FAULTS") else {
+        return FaultConfig::default();
+    };
+
+    let config: FaultConfig =
+        serde_json::from_str(&faults).unwrap_or_else(|e| panic!("Invalid FAULTS: {e}"));
+    config
+        .validate()
+        .unwrap_or_else(|e| panic!("Invalid FAULTS: {e}"));
+
+    config
+}
+
+/// `RATE_LIMIT_BURST` defaults to the per minute rate, so a minute's worth of requests can be
+/// made at once
+#[instrument]
+fn get_rate_limiter() -> Option<RateLimiter> {
+    let parse = |name: &str| {
+        env::var(name).ok().map(|value| {
+            value
+                .parse::<u32>()
+                .unwrap_or_else(|_| panic!("Invalid {name}: {value}"))
+        })
+    };
+
+    let per_minute = parse("RATE_LIMIT_PER_MINUTE")?;
+    let burst = parse("RATE_LIMIT_BURST").unwrap_or(per_minute);
+
+    if per_minute == 0 {
+        panic!("RATE_LIMIT_PER_MINUTE must be at least 1");
+    }
+
+    if burst == 0 {
+        panic!("RATE_LIMIT_BURST must be at least 1");
+    }
+
+    Some(RateLimiter::new(per_minute, burst))
+}
+
+/// `JWT_SECRET` or `JWT_JWKS_FILE` has to be set when JWTs are accepted
+#[instrument]
+fn get_jwt_auth(auth_mode: AuthMode) -> Option<JwtAuth> {
+    if auth_mode == AuthMode::ApiKey {
+        return None;
+    }
+
+    let settings = JwtSettings {
+        secret: env::var("JWT_SECRET").ok(),
+        jwks_file: env::var_os("JWT_JWKS_FILE").map(PathBuf::from),
+        issuer: env::var("JWT_ISSUER").unwrap_or_else(|_| "torii-mock".to_string()),
+        audience: env::var("JWT_AUDIENCE").ok(),
+        tenant_claim: env::var("JWT_TENANT_CLAIM").unwrap_or_else(|_| "tenant".to_string()),
+        default_tenant_fallback: env::var("JWT_DEFAULT_TENANT_FALLBACK")
+            .map(|fallback| {
+                fallback
+                    .parse()
+                    .unwrap_or_else(|_| panic!("Invalid JWT_DEFAULT_TENANT_FALLBACK: {fallback}"))
+            })
+            .unwrap_or(false),
+        token_ttl_seconds: env::var("JWT_TOKEN_TTL_SECONDS")
+            .map(|ttl| {
+                ttl.parse()
+                    .unwrap_or_else(|_| panic!("Invalid JWT_TOKEN_TTL_SECONDS: {ttl}"))
+            })
+            .unwrap_or(3600),
+        clients: env::var("OAUTH_CLIENTS").ok(),
     };
 
-    app_state
+    Some(JwtAuth::new(settings).unwrap_or_else(|e| panic!("Invalid JWT settings: {e}")))
+}
+
+/// The API key of the default tenant, plus the keys of every tenant listed in `TORII_TENANTS`.
+/// Every other key is created through `/v1.0/admin/api-keys` with one of these
+#[instrument]
+async fn get_bootstrap_keys() -> TenantKeys {
+    let tenant_keys = TenantKeys::new(get_api_key().await);
+
+    match env::var("TORII_TENANTS") {
+        Ok(tenants) => tenant_keys
+            .with_tenants(&tenants)
+            .unwrap_or_else(|e| panic!("Invalid TORII_TENANTS: {e}")),
+        Err(_) => tenant_keys,
+    }
+}
+
+/// `TORII_API_KEY` is used as-is when set so the mock can run without Secrets Manager,
+/// otherwise the key is read from the secret named by `TORII_SECRET`
+#[instrument]
+async fn get_api_key() -> String {
+    if let Ok(api_key) = env::var("TORII_API_KEY") {
+        return api_key;
+    }
+
+    let api_key_secret = env::var_os("TORII_SECRET").unwrap().into_string().unwrap();
+
+    get_secret_value(&api_key_secret).await
 }
 
 #[instrument]
@@ -0,0 +1,182 @@
+use std::{collections::BTreeMap, fs, path::Path};
+
+use serde::{Deserialize, Serialize};
+use tracing::info;
+use utoipa::ToSchema;
+
+use crate::repository::{App, AppCategory, AppState, ListKnownAppsError};
+
+/// Custom app IDs start at 1100, known app IDs stay below it so adding one never collides with
+/// a custom app
+const MAX_KNOWN_APP_ID: u16 = 1099;
+
+const BUILT_IN_CATALOG: &str = include_str!("../catalog/known_apps.yaml");
+
+/// An app of the Torii catalog that can be added by ID
+#[derive(Clone, Debug, Serialize, Deserialize, ToSchema)]
+pub struct KnownApp {
+    pub id: u16,
+    pub name: String,
+    pub category: AppCategory,
+    pub url: String,
+    #[serde(rename = "imageUrl", default)]
+    pub image_url: Option<String>,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub vendor: Option<String>,
+}
+
+impl From<&KnownApp> for App {
+    fn from(known: &KnownApp) -> Self {
+        App {
+            id: known.id,
+            name: known.name.clone(),
+            category: known.category.clone(),
+            url: known.url.clone(),
+            image_url: known.image_url.clone(),
+            description: known.description.clone(),
+            state: AppState::Sanctioned,
+            ..Default::default()
+        }
+    }
+}
+
+/// Narrows the catalog, every given criterion has to match
+#[derive(Debug, Clone, Default)]
+pub struct KnownAppsFilter {
+    /// Matched case insensitively against any part of the name
+    pub name: Option<String>,
+    pub category: Option<AppCategory>,
+}
+
+impl KnownAppsFilter {
+    fn matches(&self, app: &KnownApp) -> bool {
+        let name = self.name.as_deref().map(str::trim).unwrap_or_default();
+
+        (name.is_empty() || app.name.to_lowercase().contains(&name.to_lowercase()))
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|category| *category == app.category)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct KnownAppsPage {
+    pub apps: Vec<KnownApp>,
+    pub next_cursor: Option<String>,
+}
+
+/// The apps `list_known_apps` returns and `add_app` accepts, keyed by ID
+#[derive(Clone, Debug)]
+pub struct KnownAppsCatalog {
+    apps: BTreeMap<u16, KnownApp>,
+}
+
+impl Default for KnownAppsCatalog {
+    fn default() -> Self {
+        Self::built_in()
+    }
+}
+
+impl KnownAppsCatalog {
+    pub fn new(apps: Vec<KnownApp>) -> Result<Self, String> {
+        let mut catalog = BTreeMap::new();
+
+        for app in apps {
+            if app.id > MAX_KNOWN_APP_ID {
+                return Err(format!(
+                    "Known app {} has ID {}, known app IDs must be at most {MAX_KNOWN_APP_ID}",
+                    app.name, app.id
+                ));
+            }
+
+            if let Some(existing) = catalog.insert(app.id, app) {
+                return Err(format!("Known app ID {} is used twice", existing.id));
+            }
+        }
+
+        Ok(Self { apps: catalog })
+    }
+
+    /// Reads a list of known apps from a `.json`, `.yaml` or `.yml` file, the mock can't do
+    /// anything sensible with a broken catalog so any problem with it panics
+    pub fn load(path: &Path) -> Self {
+        info!("Loading known apps from {}", path.display());
+
+        let contents = fs::read_to_string(path).expect("Unable to read known apps file");
+
+        let extension = path
+            .extension()
+            .and_then(|extension| extension.to_str())
+            .map(str::to_ascii_lowercase);
+
+        let apps: Vec<KnownApp> = match extension.as_deref() {
+            Some("json") => {
+                serde_json::from_str(&contents).expect("Known apps file is not valid JSON")
+            }
+            Some("yaml" | "yml") => {
+                serde_yaml::from_str(&contents).expect("Known apps file is not valid YAML")
+            }
+            _ => panic!("Known apps file must be a .json, .yaml or .yml file"),
+        };
+
+        let catalog = Self::new(apps).unwrap_or_else(|e| panic!("Invalid known apps file: {e}"));
+
+        info!("Loaded {} known apps", catalog.apps.len());
+
+        catalog
+    }
+
+    /// The catalog compiled into the binary, used when no catalog file is configured so the
+    /// Lambda knows every app without the file being deployed next to it
+    pub fn built_in() -> Self {
+        let apps: Vec<KnownApp> =
+            serde_yaml::from_str(BUILT_IN_CATALOG).expect("Built-in known apps are valid YAML");
+
+        Self::new(apps).expect("Built-in known apps are valid")
+    }
+
+    /// Matching apps in ID order, the cursor is the offset of the next match. Without `size`
+    /// every match after the cursor is returned
+    pub fn page(
+        &self,
+        filter: &KnownAppsFilter,
+        size: Option<u16>,
+        cursor: Option<String>,
+    ) -> Result<KnownAppsPage, ListKnownAppsError> {
+        let offset = match cursor {
+            Some(cursor) => cursor
+                .parse::<usize>()
+                .map_err(|_| ListKnownAppsError::InvalidCursor(cursor))?,
+            None => 0,
+        };
+
+        let mut matches = self
+            .apps
+            .values()
+            .filter(|app| filter.matches(app))
+            .skip(offset);
+
+        let apps: Vec<KnownApp> = match size {
+            Some(size) => matches.by_ref().take(usize::from(size)).cloned().collect(),
+            None => matches.by_ref().cloned().collect(),
+        };
+        let next_cursor = matches
+            .next()
+            .is_some()
+            .then(|| (offset + apps.len()).to_string());
+
+        Ok(KnownAppsPage { apps, next_cursor })
+    }
+
+    pub fn get(&self, id: u16) -> Option<&KnownApp> {
+        self.apps.get(&id)
+    }
+
+    /// The app `add_app` stores for a known app
+    pub fn app(&self, id: u16) -> Option<App> {
+        self.get(id).map(App::from)
+    }
+}
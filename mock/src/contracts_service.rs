@@ -0,0 +1,250 @@
+use crate::{
+    repository::{
+        AppsRepository, Contract, ContractsRepository, CreateContractError, DeleteContractError,
+        GetAppError, GetContractError, ListContractsError, UpdateContractError,
+    },
+    routes::{
+        CreateContractHttpRequestBody, ListContractsQueryParams, UpdateContractHttpRequestBody,
+    },
+    tenant::{Tenant, TenantScoped},
+};
+use async_trait::async_trait;
+use chrono::{NaiveDate, Utc};
+use tracing::error;
+
+#[async_trait]
+pub trait ContractsServiceTrait: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    async fn get_contract(&self, id: u32) -> Result<Contract, GetContractError>;
+    async fn create_contract(
+        &self,
+        request: CreateContractHttpRequestBody,
+    ) -> Result<Contract, CreateContractError>;
+    async fn delete_contract(&self, id: u32) -> Result<(), DeleteContractError>;
+    async fn list_contracts(
+        &self,
+        params: ListContractsQueryParams,
+    ) -> Result<Vec<Contract>, ListContractsError>;
+    async fn update_contract(
+        &self,
+        request: UpdateContractHttpRequestBody,
+        id: u32,
+    ) -> Result<Contract, UpdateContractError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct ContractsService<R, A>
+where
+    R: ContractsRepository,
+    A: AppsRepository,
+{
+    pub repo: R,
+    pub apps_repo: A,
+}
+
+impl<R, A> ContractsService<R, A>
+where
+    R: ContractsRepository,
+    A: AppsRepository,
+{
+    pub fn new(repo: R, apps_repo: A) -> Self {
+        Self { repo, apps_repo }
+    }
+
+    /// `Ok(false)` when the app does not exist
+    #[tracing::instrument(skip(self))]
+    async fn app_exists(&self, app_id: u16) -> Result<bool, ()> {
+        match self.apps_repo.get_app(app_id).await {
+            Ok(_) => Ok(true),
+            Err(GetAppError::ResourceNotFound(_)) => Ok(false),
+            Err(e) => {
+                error!("{}", e);
+                Err(())
+            }
+        }
+    }
+}
+
+impl<R, A> TenantScoped for ContractsService<R, A>
+where
+    R: ContractsRepository,
+    A: AppsRepository,
+{
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            repo: self.repo.for_tenant(tenant),
+            apps_repo: self.apps_repo.for_tenant(tenant),
+        }
+    }
+}
+
+#[async_trait]
+impl<R, A> ContractsServiceTrait for ContractsService<R, A>
+where
+    R: ContractsRepository,
+    A: AppsRepository,
+{
+    #[tracing::instrument(skip(self))]
+    async fn get_contract(&self, id: u32) -> Result<Contract, GetContractError> {
+        self.repo.get_contract(id).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn create_contract(
+        &self,
+        request: CreateContractHttpRequestBody,
+    ) -> Result<Contract, CreateContractError> {
+        let currency =
+            normalize_currency(&request.currency).map_err(CreateContractError::InvalidContract)?;
+        validate_dates(request.start_date, request.end_date, request.renewal_date)
+            .map_err(CreateContractError::InvalidContract)?;
+
+        match self.app_exists(request.app_id).await {
+            Ok(true) => {}
+            Ok(false) => return Err(CreateContractError::AppNotFound(request.app_id)),
+            Err(_) => return Err(CreateContractError::UnexpectedError),
+        }
+
+        let id = self
+            .repo
+            .get_contract_id()
+            .await
+            .map_err(|_| CreateContractError::UnexpectedError)?;
+
+        let now = Utc::now();
+        let contract = Contract {
+            id,
+            app_id: request.app_id,
+            amount: request.amount,
+            currency,
+            start_date: request.start_date,
+            end_date: request.end_date,
+            renewal_date: request.renewal_date,
+            auto_renew: request.auto_renew.unwrap_or_default(),
+            owner: request.owner,
+            creation_time: now,
+            last_updated_at: now,
+        };
+
+        self.repo.create_contract(contract).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_contract(&self, id: u32) -> Result<(), DeleteContractError> {
+        self.repo.delete_contract(id).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_contracts(
+        &self,
+        params: ListContractsQueryParams,
+    ) -> Result<Vec<Contract>, ListContractsError> {
+        let mut contracts: Vec<Contract> = self
+            .repo
+            .list_contracts()
+            .await?
+            .into_iter()
+            .filter(|contract| params.app_id.is_none_or(|app_id| contract.app_id == app_id))
+            .filter(|contract| {
+                params
+                    .renewal_from
+                    .is_none_or(|from| contract.renewal_date.is_some_and(|renewal| renewal >= from))
+            })
+            .filter(|contract| {
+                params
+                    .renewal_to
+                    .is_none_or(|to| contract.renewal_date.is_some_and(|renewal| renewal <= to))
+            })
+            .collect();
+
+        contracts.sort_by_key(|contract| contract.id);
+
+        Ok(contracts)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn update_contract(
+        &self,
+        request: UpdateContractHttpRequestBody,
+        id: u32,
+    ) -> Result<Contract, UpdateContractError> {
+        let mut contract = self.get_contract(id).await.map_err(|e| match e {
+            GetContractError::ResourceNotFound(id) => UpdateContractError::ResourceNotFound(id),
+            _ => {
+                error!("{}", e);
+                UpdateContractError::UnexpectedError
+            }
+        })?;
+
+        if let Some(app_id) = request.app_id {
+            match self.app_exists(app_id).await {
+                Ok(true) => contract.app_id = app_id,
+                Ok(false) => return Err(UpdateContractError::AppNotFound(app_id)),
+                Err(_) => return Err(UpdateContractError::UnexpectedError),
+            }
+        };
+
+        if let Some(amount) = request.amount {
+            contract.amount = amount
+        };
+
+        if let Some(currency) = request.currency {
+            contract.currency =
+                normalize_currency(&currency).map_err(UpdateContractError::InvalidContract)?
+        };
+
+        if let Some(start_date) = request.start_date {
+            contract.start_date = start_date
+        };
+
+        if let Some(end_date) = request.end_date {
+            contract.end_date = end_date
+        };
+
+        if let Some(renewal_date) = request.renewal_date {
+            contract.renewal_date = renewal_date
+        };
+
+        if let Some(auto_renew) = request.auto_renew {
+            contract.auto_renew = auto_renew
+        };
+
+        if let Some(owner) = request.owner {
+            contract.owner = owner
+        };
+
+        validate_dates(
+            contract.start_date,
+            contract.end_date,
+            contract.renewal_date,
+        )
+        .map_err(UpdateContractError::InvalidContract)?;
+
+        contract.last_updated_at = Utc::now();
+
+        self.repo.update_contract(contract).await
+    }
+}
+
+fn normalize_currency(currency: &str) -> Result<String, String> {
+    if currency.len() == 3 && currency.chars().all(|c| c.is_ascii_alphabetic()) {
+        Ok(currency.to_ascii_uppercase())
+    } else {
+        Err(format!("{currency} is not an ISO 4217 currency code"))
+    }
+}
+
+fn validate_dates(
+    start_date: NaiveDate,
+    end_date: Option<NaiveDate>,
+    renewal_date: Option<NaiveDate>,
+) -> Result<(), String> {
+    if end_date.is_some_and(|end| end < start_date) {
+        return Err("endDate must not be before startDate".to_string());
+    }
+
+    if renewal_date.is_some_and(|renewal| renewal < start_date) {
+        return Err("renewalDate must not be before startDate".to_string());
+    }
+
+    Ok(())
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde_json::Value;
+
+use crate::repository::{AppField, AppFieldType};
+
+/// Keys become JSON property names in `App.fields`, so they are kept to identifier characters
+pub fn validate_key(key: &str) -> Result<(), String> {
+    let mut chars = key.chars();
+
+    let valid = key.len() <= 64
+        && chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+        && chars.all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Field key {key} must start with a letter and only contain letters, digits and underscores"
+        ))
+    }
+}
+
+/// Dropdowns need at least one option, every other type takes none
+pub fn validate_options(field_type: AppFieldType, options: &[String]) -> Result<(), String> {
+    match (field_type, options.is_empty()) {
+        (AppFieldType::Dropdown, true) => Err("Dropdown fields need at least one option".into()),
+        (AppFieldType::Dropdown, false) => Ok(()),
+        (_, false) => Err(format!("Fields of type {field_type} can't have options")),
+        (_, true) => Ok(()),
+    }
+}
+
+/// Checks that `value` has the shape `field` expects. User IDs are returned so the caller can
+/// check that the users exist
+pub fn validate_value(field: &AppField, value: &Value) -> Result<Option<u32>, String> {
+    let invalid = || {
+        format!(
+            "Value {value} is not valid for {} field {}",
+            field.field_type, field.key
+        )
+    };
+
+    match field.field_type {
+        AppFieldType::Text => value.is_string().then_some(None).ok_or_else(invalid),
+        AppFieldType::Number => value.is_number().then_some(None).ok_or_else(invalid),
+        AppFieldType::Date => value
+            .as_str()
+            .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+            .map(|_| None)
+            .ok_or_else(invalid),
+        AppFieldType::Dropdown => value
+            .as_str()
+            .filter(|option| field.options.iter().any(|allowed| allowed == option))
+            .map(|_| None)
+            .ok_or_else(invalid),
+        AppFieldType::User => value
+            .as_u64()
+            .and_then(|id| u32::try_from(id).ok())
+            .map(Some)
+            .ok_or_else(invalid),
+    }
+}
+
+/// Merges `values` into `target`, `null` removes the field from the app
+pub fn apply_values(target: &mut BTreeMap<String, Value>, values: BTreeMap<String, Value>) {
+    for (key, value) in values {
+        if value.is_null() {
+            target.remove(&key);
+        } else {
+            target.insert(key, value);
+        }
+    }
+}
+
+/// Compares a stored value against a query string value, strings are compared as-is and any
+/// other value is compared with the query string parsed as JSON
+pub fn value_matches(value: &Value, expected: &str) -> bool {
+    match value {
+        Value::String(value) => value == expected,
+        _ => serde_json::from_str::<Value>(expected).is_ok_and(|expected| expected == *value),
+    }
+}
@@ -0,0 +1,72 @@
+use axum::http::{header, HeaderMap, HeaderName};
+
+use crate::api_error::ApiError;
+
+/// Response header carrying the entity tag
+pub type ETagHeader = [(HeaderName, String); 1];
+
+/// Strong entity tag for an app version, e.g. `"3"`
+pub fn etag(version: u64) -> ETagHeader {
+    [(header::ETAG, format!("\"{version}\""))]
+}
+
+/// Version required by `If-Match`, `None` when the header is missing or `*`. Weak tags are
+/// accepted since the version is the same either way, anything that isn't one of our tags can
+/// never match and fails the precondition
+pub fn parse_if_match(headers: &HeaderMap) -> Result<Option<u64>, ApiError> {
+    let Some(value) = headers.get(header::IF_MATCH) else {
+        return Ok(None);
+    };
+
+    let precondition_failed = || ApiError::PreconditionFailed("If-Match does not match".into());
+    let value = value.to_str().map_err(|_| precondition_failed())?.trim();
+
+    if value == "*" {
+        return Ok(None);
+    }
+
+    value
+        .trim_start_matches("W/")
+        .strip_prefix('"')
+        .and_then(|tag| tag.strip_suffix('"'))
+        .and_then(|version| version.parse().ok())
+        .map(Some)
+        .ok_or_else(precondition_failed)
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::http::HeaderValue;
+
+    use super::*;
+
+    fn if_match(value: &str) -> Result<Option<u64>, ApiError> {
+        let mut headers = HeaderMap::new();
+        headers.insert(header::IF_MATCH, HeaderValue::from_str(value).unwrap());
+
+        parse_if_match(&headers)
+    }
+
+    #[test]
+    fn reads_the_version_of_strong_and_weak_tags() {
+        assert_eq!(if_match("\"3\"").unwrap(), Some(3));
+        assert_eq!(if_match(" W/\"12\" ").unwrap(), Some(12));
+        assert_eq!(if_match(&etag(7)[0].1).unwrap(), Some(7));
+    }
+
+    #[test]
+    fn missing_header_and_wildcard_match_any_version() {
+        assert_eq!(parse_if_match(&HeaderMap::new()).unwrap(), None);
+        assert_eq!(if_match("*").unwrap(), None);
+    }
+
+    #[test]
+    fn anything_else_fails_the_precondition() {
+        for value in ["3", "\"three\"", "\"3", "\"-1\"", "\"1\", \"2\""] {
+            assert!(
+                matches!(if_match(value), Err(ApiError::PreconditionFailed(_))),
+                "{value} should not match"
+            );
+        }
+    }
+}
@@ -0,0 +1,219 @@
+use std::{io, sync::Arc};
+
+use axum::body::Bytes;
+use futures_util::{stream, Stream};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tracing::error;
+
+use crate::{apps_service::AppsServiceTrait, repository::App, routes::ListAppsQueryParams};
+
+/// Apps read from the repository for every chunk of the export
+const PAGE_SIZE: u16 = 100;
+
+/// `lambda_http` buffers the whole response and Lambda refuses ones over 6 MB, the rest is left
+/// for the escaping and encoding of the invocation response
+pub const MAX_EXPORT_BYTES: usize = 5 * 1024 * 1024;
+
+/// Attributes of `App` written to CSV exports, in column order. Custom fields follow as one
+/// column per field key
+const CSV_COLUMNS: [&str; 18] = [
+    "id",
+    "name",
+    "state",
+    "url",
+    "category",
+    "description",
+    "tags",
+    "users",
+    "isCustom",
+    "isHidden",
+    "imageUrl",
+    "primaryOwner",
+    "addedBy",
+    "sources",
+    "creationTime",
+    "lastUpdatedAt",
+    "lastUsageTime",
+    "version",
+];
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// A single JSON array of apps
+    #[default]
+    Json,
+    /// One JSON app per line
+    Ndjson,
+    /// A header row followed by one row per app, custom fields get a column each
+    Csv,
+}
+
+impl ExportFormat {
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            Self::Json => "application/json",
+            Self::Ndjson => "application/x-ndjson",
+            Self::Csv => "text/csv",
+        }
+    }
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            Self::Json => "json",
+            Self::Ndjson => "ndjson",
+            Self::Csv => "csv",
+        }
+    }
+}
+
+struct ExportState<AS> {
+    service: Arc<AS>,
+    format: ExportFormat,
+    field_keys: Vec<String>,
+    cursor: Option<String>,
+    started: bool,
+    written: usize,
+    done: bool,
+}
+
+/// Renders the whole catalog one page at a time. An error part way through ends the stream
+pub fn export_apps<AS>(
+    service: Arc<AS>,
+    format: ExportFormat,
+    field_keys: Vec<String>,
+) -> impl Stream<Item = io::Result<Bytes>> + Send + 'static
+where
+    AS: AppsServiceTrait + 'static,
+{
+    let state = ExportState {
+        service,
+        format,
+        field_keys,
+        cursor: None,
+        started: false,
+        written: 0,
+        done: false,
+    };
+
+    stream::unfold(state, |mut state| async move {
+        if state.done {
+            return None;
+        }
+
+        let params = ListAppsQueryParams {
+            size: Some(PAGE_SIZE),
+            cursor: state.cursor.take(),
+            ..Default::default()
+        };
+
+        let page = match state.service.list_apps(params).await {
+            Ok(page) => page,
+            Err(e) => {
+                error!("{}", e);
+                state.done = true;
+                return Some((Err(io::Error::other(e.to_string())), state));
+            }
+        };
+
+        let mut chunk = Vec::new();
+
+        if !state.started {
+            state.started = true;
+            chunk.extend(state.header());
+        }
+
+        for app in &page.apps {
+            match state.row(app) {
+                Ok(row) => chunk.extend(row),
+                Err(e) => {
+                    error!("{}", e);
+                    state.done = true;
+                    return Some((Err(e), state));
+                }
+            }
+        }
+
+        match page.next_cursor {
+            Some(cursor) => state.cursor = Some(cursor),
+            None => {
+                state.done = true;
+
+                if state.format == ExportFormat::Json {
+                    chunk.extend(b"]\n");
+                }
+            }
+        }
+
+        Some((Ok(Bytes::from(chunk)), state))
+    })
+}
+
+impl<AS> ExportState<AS> {
+    fn header(&self) -> Vec<u8> {
+        match self.format {
+            ExportFormat::Json => b"[".to_vec(),
+            ExportFormat::Ndjson => Vec::new(),
+            ExportFormat::Csv => {
+                let columns = CSV_COLUMNS
+                    .iter()
+                    .map(|column| column.to_string())
+                    .chain(self.field_keys.iter().cloned());
+
+                csv_record(columns).unwrap_or_default()
+            }
+        }
+    }
+
+    fn row(&mut self, app: &App) -> io::Result<Vec<u8>> {
+        let mut row = Vec::new();
+
+        match self.format {
+            ExportFormat::Json => {
+                if self.written > 0 {
+                    row.push(b',');
+                }
+
+                serde_json::to_writer(&mut row, app)?;
+            }
+            ExportFormat::Ndjson => {
+                serde_json::to_writer(&mut row, app)?;
+                row.push(b'\n');
+            }
+            ExportFormat::Csv => {
+                let value = serde_json::to_value(app)?;
+                let cells = CSV_COLUMNS
+                    .iter()
+                    .map(|column| csv_cell(value.get(*column)))
+                    .chain(
+                        self.field_keys
+                            .iter()
+                            .map(|key| csv_cell(app.fields.get(key))),
+                    );
+
+                row = csv_record(cells)?;
+            }
+        }
+
+        self.written += 1;
+
+        Ok(row)
+    }
+}
+
+fn csv_record(cells: impl Iterator<Item = String>) -> io::Result<Vec<u8>> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    writer.write_record(cells)?;
+
+    writer.into_inner().map_err(|e| e.into_error())
+}
+
+/// Strings are written as-is, missing values and `null` leave the cell empty
+fn csv_cell(value: Option<&Value>) -> String {
+    match value {
+        None | Some(Value::Null) => String::new(),
+        Some(Value::String(text)) => text.clone(),
+        Some(value) => value.to_string(),
+    }
+}
@@ -0,0 +1,385 @@
+use std::{
+    sync::{Mutex, RwLock},
+    time::Duration,
+};
+
+use axum::{
+    body::{to_bytes, Body, HttpBody},
+    extract::{Request, State},
+    http::{header, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use futures_util::stream;
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use tracing::{info, warn};
+use validator::{Validate, ValidationError};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, users_service::UsersServiceTrait,
+};
+
+/// Never faulted, so faults can always be turned off again
+pub const FAULTS_PATH: &str = "/v1.0/admin/faults";
+
+/// Bodies larger than this, or streamed ones whose size isn't known upfront, are passed through
+/// whole rather than buffered to be malformed
+const MAX_MALFORMED_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// Which faults are injected into which requests, set with `FAULTS` or through
+/// `/v1.0/admin/faults`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct FaultConfig {
+    /// Makes the faults reproducible, the same seed and requests give the same faults
+    pub seed: Option<u64>,
+    /// The first rule matching a request applies, requests no rule matches are left alone
+    #[validate(nested)]
+    #[serde(default)]
+    pub rules: Vec<FaultRule>,
+}
+
+/// Faults for the requests matching `path` and `methods`, each request gets at most one of
+/// error, drop and malformed
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+#[validate(schema(function = "validate_rates"))]
+pub struct FaultRule {
+    /// Route like `/v1.0/apps/:id`, a trailing `/*` matches anything below. Every route when
+    /// left out
+    pub path: Option<String>,
+    /// Every method when left out
+    #[serde(default)]
+    pub methods: Vec<String>,
+    /// Added to every matching request, faulted or not
+    #[serde(rename = "latencyMs", default)]
+    #[validate(range(max = 60000))]
+    pub latency_ms: u64,
+    /// Up to this much more latency, picked at random
+    #[serde(rename = "latencyJitterMs", default)]
+    #[validate(range(max = 60000))]
+    pub latency_jitter_ms: u64,
+    /// Chance of answering with one of `errorStatuses` instead of running the request
+    #[serde(rename = "errorRate", default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub error_rate: f64,
+    /// Picked at random, 500, 502, 503 and 429 are allowed. Only 500 when left out
+    #[serde(rename = "errorStatuses", default)]
+    pub error_statuses: Vec<u16>,
+    /// Chance of failing after the headers were sent. A plain HTTP server closes the
+    /// connection, behind API Gateway the invocation fails and a 502 is returned
+    #[serde(rename = "dropRate", default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub drop_rate: f64,
+    /// Chance of running the request but cutting its body in half
+    #[serde(rename = "malformedRate", default)]
+    #[validate(range(min = 0.0, max = 1.0))]
+    pub malformed_rate: f64,
+}
+
+fn validate_rates(rule: &FaultRule) -> Result<(), ValidationError> {
+    if rule.error_rate + rule.drop_rate + rule.malformed_rate > 1.0 {
+        return Err(ValidationError::new("rates")
+            .with_message("errorRate, dropRate and malformedRate add up to more than 1".into()));
+    }
+
+    if let Some(status) = rule
+        .error_statuses
+        .iter()
+        .find(|status| !matches!(status, 429 | 500 | 502 | 503))
+    {
+        return Err(ValidationError::new("errorStatuses")
+            .with_message(format!("Status {status} can't be injected").into()));
+    }
+
+    Ok(())
+}
+
+impl FaultRule {
+    fn matches(&self, method: &str, path: &str) -> bool {
+        let method_matches = self.methods.is_empty()
+            || self
+                .methods
+                .iter()
+                .any(|allowed| allowed.eq_ignore_ascii_case(method));
+
+        method_matches
+            && self
+                .path
+                .as_deref()
+                .is_none_or(|route| route_matches(route, path))
+    }
+}
+
+/// `:name` segments match any single segment and a last `*` segment matches the rest
+fn route_matches(route: &str, path: &str) -> bool {
+    let mut route = route.trim_end_matches('/').split('/');
+    let mut path = path.trim_end_matches('/').split('/');
+
+    loop {
+        match (route.next(), path.next()) {
+            (Some("*"), _) => return true,
+            (Some(expected), Some(actual)) => {
+                if !expected.starts_with(':') && expected != actual {
+                    return false;
+                }
+            }
+            (None, None) => return true,
+            _ => return false,
+        }
+    }
+}
+
+/// What happens to a single request
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Fault {
+    None,
+    Error(StatusCode),
+    Drop,
+    Malformed,
+}
+
+/// Holds the fault settings and the random number generator they are rolled with. Faults
+/// apply to every tenant, so only the default tenant may change them. They live in the memory
+/// of one process, which is reliable locally but on Lambda only reaches the instance that
+/// handled the update, set `FAULTS` there instead
+#[derive(Debug)]
+pub struct FaultInjector {
+    config: RwLock<FaultConfig>,
+    rng: Mutex<StdRng>,
+}
+
+impl FaultInjector {
+    pub fn new(config: FaultConfig) -> Self {
+        let rng = Mutex::new(seeded_rng(config.seed));
+
+        Self {
+            config: RwLock::new(config),
+            rng,
+        }
+    }
+
+    pub fn config(&self) -> FaultConfig {
+        self.config.read().unwrap().clone()
+    }
+
+    /// Replaces every rule and starts over from the seed
+    pub fn set_config(&self, config: FaultConfig) {
+        info!("Fault injection set to {:?}", config);
+
+        *self.rng.lock().unwrap() = seeded_rng(config.seed);
+        *self.config.write().unwrap() = config;
+    }
+
+    /// Latency to add and the fault to inject for a request
+    fn roll(&self, method: &str, path: &str) -> (Duration, Fault) {
+        let config = self.config.read().unwrap();
+        let Some(rule) = config.rules.iter().find(|rule| rule.matches(method, path)) else {
+            return (Duration::ZERO, Fault::None);
+        };
+
+        let mut rng = self.rng.lock().unwrap();
+
+        let jitter = if rule.latency_jitter_ms > 0 {
+            rng.gen_range(0..=rule.latency_jitter_ms)
+        } else {
+            0
+        };
+        let latency = Duration::from_millis(rule.latency_ms + jitter);
+
+        let roll: f64 = rng.gen();
+        let fault = if roll < rule.error_rate {
+            let status = if rule.error_statuses.is_empty() {
+                500
+            } else {
+                rule.error_statuses[rng.gen_range(0..rule.error_statuses.len())]
+            };
+            Fault::Error(StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
+        } else if roll < rule.error_rate + rule.drop_rate {
+            Fault::Drop
+        } else if roll < rule.error_rate + rule.drop_rate + rule.malformed_rate {
+            Fault::Malformed
+        } else {
+            Fault::None
+        };
+
+        (latency, fault)
+    }
+}
+
+fn seeded_rng(seed: Option<u64>) -> StdRng {
+    match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    }
+}
+
+/// Runs before rate limiting and auth, so a faulted request never reaches them
+pub async fn inject_faults<AS, US, CS>(
+    State(state): State<Backend<AS, US, CS>>,
+    req: Request,
+    next: Next,
+) -> Response
+where
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+{
+    if req.uri().path() == FAULTS_PATH {
+        return next.run(req).await;
+    }
+
+    let (latency, fault) = state.faults.roll(req.method().as_str(), req.uri().path());
+
+    if !latency.is_zero() {
+        tokio::time::sleep(latency).await;
+    }
+
+    if fault != Fault::None {
+        warn!(
+            "Injecting {:?} into {} {}",
+            fault,
+            req.method(),
+            req.uri().path()
+        );
+    }
+
+    match fault {
+        Fault::None => next.run(req).await,
+        Fault::Error(StatusCode::TOO_MANY_REQUESTS) => ApiError::TooManyRequests(1).into_response(),
+        Fault::Error(status) => {
+            let body = json!({
+                "error": format!("Injected fault: {}", status.canonical_reason().unwrap_or("Error"))
+            });
+
+            (status, Json(body)).into_response()
+        }
+        Fault::Drop => {
+            let body = Body::from_stream(stream::once(async {
+                Err::<Vec<u8>, _>(std::io::Error::new(
+                    std::io::ErrorKind::ConnectionReset,
+                    "Injected dropped connection",
+                ))
+            }));
+
+            (
+                StatusCode::OK,
+                [(header::CONTENT_TYPE, "application/json")],
+                body,
+            )
+                .into_response()
+        }
+        Fault::Malformed => {
+            let (mut parts, body) = next.run(req).await.into_parts();
+
+            let fits = body
+                .size_hint()
+                .exact()
+                .is_some_and(|size| size <= MAX_MALFORMED_BODY_BYTES as u64);
+
+            if !fits {
+                warn!("Passing through a body too large or streamed to malform");
+                return Response::from_parts(parts, body);
+            }
+
+            match to_bytes(body, MAX_MALFORMED_BODY_BYTES).await {
+                Ok(bytes) => {
+                    parts.headers.remove(header::CONTENT_LENGTH);
+                    Response::from_parts(parts, Body::from(bytes.slice(..bytes.len() / 2)))
+                }
+                Err(e) => {
+                    warn!("Unable to read the body to malform: {}", e);
+                    ApiError::InternalServerError.into_response()
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rolls(injector: &FaultInjector, count: usize) -> Vec<(Duration, Fault)> {
+        (0..count)
+            .map(|_| injector.roll("GET", "/v1.0/apps"))
+            .collect()
+    }
+
+    fn config(seed: Option<u64>) -> FaultConfig {
+        FaultConfig {
+            seed,
+            rules: vec![FaultRule {
+                latency_jitter_ms: 1000,
+                error_rate: 0.3,
+                error_statuses: vec![500, 503],
+                drop_rate: 0.2,
+                malformed_rate: 0.2,
+                ..Default::default()
+            }],
+        }
+    }
+
+    #[test]
+    fn route_matches_parameters_and_wildcards() {
+        assert!(route_matches("/v1.0/apps/:id", "/v1.0/apps/1101"));
+        assert!(route_matches("/v1.0/apps/:id/", "/v1.0/apps/1101"));
+        assert!(!route_matches("/v1.0/apps/:id", "/v1.0/apps"));
+        assert!(!route_matches("/v1.0/apps/:id", "/v1.0/apps/1101/users"));
+        assert!(route_matches("/v1.0/apps/*", "/v1.0/apps/1101/users"));
+        assert!(route_matches("/v1.0/apps/*", "/v1.0/apps"));
+        assert!(!route_matches("/v1.0/apps/*", "/v1.0/users"));
+        assert!(!route_matches("/v1.0/apps", "/v1.0/app"));
+    }
+
+    #[test]
+    fn rules_match_methods_case_insensitively() {
+        let rule = FaultRule {
+            path: Some("/v1.0/apps".to_string()),
+            methods: vec!["post".to_string()],
+            ..Default::default()
+        };
+
+        assert!(rule.matches("POST", "/v1.0/apps"));
+        assert!(!rule.matches("GET", "/v1.0/apps"));
+    }
+
+    #[test]
+    fn the_same_seed_gives_the_same_faults() {
+        let first = rolls(&FaultInjector::new(config(Some(42))), 200);
+        let second = rolls(&FaultInjector::new(config(Some(42))), 200);
+        let other = rolls(&FaultInjector::new(config(Some(43))), 200);
+
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+        assert!(first
+            .iter()
+            .any(|(_, fault)| *fault == Fault::Error(StatusCode::SERVICE_UNAVAILABLE)));
+        assert!(first.iter().any(|(_, fault)| *fault == Fault::Drop));
+        assert!(first.iter().any(|(_, fault)| *fault == Fault::Malformed));
+        assert!(first.iter().any(|(_, fault)| *fault == Fault::None));
+    }
+
+    #[test]
+    fn setting_the_config_starts_over_from_the_seed() {
+        let injector = FaultInjector::new(config(Some(7)));
+        let first = rolls(&injector, 50);
+
+        injector.set_config(config(Some(7)));
+
+        assert_eq!(rolls(&injector, 50), first);
+    }
+
+    #[test]
+    fn requests_no_rule_matches_are_left_alone() {
+        let mut config = config(Some(1));
+        config.rules[0].path = Some("/v1.0/users".to_string());
+        let injector = FaultInjector::new(config);
+
+        assert!(rolls(&injector, 20)
+            .iter()
+            .all(|roll| *roll == (Duration::ZERO, Fault::None)));
+    }
+}
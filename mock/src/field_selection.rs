@@ -0,0 +1,77 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+use crate::api_error::ApiError;
+
+/// Attributes returned regardless of what was requested with `fields`
+const ALWAYS_INCLUDED: [&str; 1] = ["id"];
+
+/// Parsed `fields` query parameter, a comma separated list of attribute names such as
+/// `fields=name,state,primaryOwner`. Attributes of a nested object are selected with a dot, so
+/// `fields=name,fields.riskLevel` returns the name and only the `riskLevel` custom field
+#[derive(Debug, Clone, Default)]
+pub struct FieldSelection {
+    fields: Option<Vec<String>>,
+}
+
+impl FieldSelection {
+    pub fn parse(fields: Option<&str>) -> Self {
+        let fields = fields.map(|fields| {
+            fields
+                .split(',')
+                .map(str::trim)
+                .filter(|field| !field.is_empty())
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        });
+
+        Self {
+            fields: fields.filter(|fields| !fields.is_empty()),
+        }
+    }
+
+    /// Serializes `value` and drops every attribute that was not selected. Names that do not
+    /// exist on the item are ignored rather than rejected, as custom fields vary per item
+    pub fn project<T: Serialize>(&self, value: &T) -> Result<Value, ApiError> {
+        let serialized = serde_json::to_value(value).map_err(|e| {
+            tracing::error!("Unable to serialize response: {}", e);
+            ApiError::InternalServerError
+        })?;
+
+        let Some(fields) = &self.fields else {
+            return Ok(serialized);
+        };
+
+        let Value::Object(object) = serialized else {
+            return Ok(serialized);
+        };
+
+        let projected: Map<String, Value> = object
+            .into_iter()
+            .filter_map(|(key, value)| {
+                if ALWAYS_INCLUDED.contains(&key.as_str()) || fields.contains(&key) {
+                    return Some((key, value));
+                }
+
+                let nested: Vec<&str> = fields
+                    .iter()
+                    .filter_map(|field| field.strip_prefix(key.as_str())?.strip_prefix('.'))
+                    .collect();
+
+                match value {
+                    Value::Object(inner) if !nested.is_empty() => {
+                        let inner = inner
+                            .into_iter()
+                            .filter(|(inner_key, _)| nested.contains(&inner_key.as_str()))
+                            .collect();
+
+                        Some((key, Value::Object(inner)))
+                    }
+                    _ => None,
+                }
+            })
+            .collect();
+
+        Ok(Value::Object(projected))
+    }
+}
@@ -0,0 +1,331 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    io::Cursor,
+};
+
+use calamine::{open_workbook_from_rs, Data, DataType, Reader, Xlsx};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::{Number, Value};
+use utoipa::ToSchema;
+
+use crate::{
+    repository::{App, AppField, AppFieldType},
+    routes::CreateAppHttpRequestBody,
+};
+
+pub const CSV_CONTENT_TYPE: &str = "text/csv";
+pub const XLSX_CONTENT_TYPE: &str =
+    "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet";
+
+/// Attributes of `CreateAppHttpRequestBody` a column can be mapped to, custom fields are mapped
+/// as `fields.<key>`
+const ATTRIBUTES: [&str; 6] = ["name", "state", "url", "category", "description", "tags"];
+const REQUIRED_ATTRIBUTES: [&str; 4] = ["name", "state", "url", "category"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    Csv,
+    Xlsx,
+}
+
+impl ImportFormat {
+    /// Uses the content type of the upload and falls back to the file extension, since browsers
+    /// and HTTP clients often send spreadsheets as `application/octet-stream`
+    pub fn detect(content_type: Option<&str>, file_name: Option<&str>) -> Option<Self> {
+        match content_type {
+            Some(CSV_CONTENT_TYPE) => return Some(Self::Csv),
+            Some(XLSX_CONTENT_TYPE) => return Some(Self::Xlsx),
+            _ => {}
+        }
+
+        let extension = file_name?.rsplit_once('.')?.1.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "csv" => Some(Self::Csv),
+            "xlsx" => Some(Self::Xlsx),
+            _ => None,
+        }
+    }
+}
+
+/// An uploaded file read into rows of text cells
+#[derive(Debug, Clone, Default)]
+pub struct ImportSheet {
+    pub headers: Vec<String>,
+    pub rows: Vec<ImportRow>,
+}
+
+/// `row` is the number a spreadsheet shows for it, the header is row 1
+#[derive(Debug, Clone)]
+pub struct ImportRow {
+    pub row: usize,
+    pub cells: Vec<String>,
+}
+
+impl ImportRow {
+    fn cell(&self, column: usize) -> Option<&str> {
+        self.cells
+            .get(column)
+            .map(String::as_str)
+            .filter(|cell| !cell.is_empty())
+    }
+}
+
+/// The first row holds the headers and blank rows are skipped. Only the first worksheet of a
+/// workbook is read
+pub fn read_sheet(format: ImportFormat, bytes: &[u8]) -> Result<ImportSheet, String> {
+    let mut rows = match format {
+        ImportFormat::Csv => read_csv(bytes)?,
+        ImportFormat::Xlsx => read_xlsx(bytes)?,
+    }
+    .into_iter()
+    .enumerate()
+    .map(|(index, cells)| ImportRow {
+        row: index + 1,
+        cells: cells
+            .into_iter()
+            .map(|cell| cell.trim().to_string())
+            .collect(),
+    })
+    .filter(|row| row.cells.iter().any(|cell| !cell.is_empty()));
+
+    let headers = rows.next().ok_or("File has no header row")?.cells;
+
+    Ok(ImportSheet {
+        headers,
+        rows: rows.collect(),
+    })
+}
+
+fn read_csv(bytes: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .from_reader(bytes)
+        .records()
+        .map(|record| {
+            record
+                .map(|record| record.iter().map(str::to_string).collect())
+                .map_err(|e| format!("Invalid CSV: {e}"))
+        })
+        .collect()
+}
+
+fn read_xlsx(bytes: &[u8]) -> Result<Vec<Vec<String>>, String> {
+    let mut workbook: Xlsx<_> = open_workbook_from_rs(Cursor::new(bytes))
+        .map_err(|e| format!("Invalid XLSX workbook: {e}"))?;
+
+    let range = workbook
+        .worksheet_range_at(0)
+        .ok_or("Workbook has no worksheets")?
+        .map_err(|e| format!("Invalid XLSX worksheet: {e}"))?;
+
+    Ok(range
+        .rows()
+        .map(|row| row.iter().map(cell_text).collect())
+        .collect())
+}
+
+/// Excel stores dates as numbers, they are turned into the `YYYY-MM-DD` date fields expect
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::DateTime(_) => cell
+            .as_date()
+            .map(|date| date.format("%Y-%m-%d").to_string())
+            .unwrap_or_else(|| cell.to_string()),
+        _ => cell.to_string(),
+    }
+}
+
+/// Which column each attribute of the created apps is read from
+#[derive(Debug, Clone, Default)]
+pub struct ColumnMapping {
+    attributes: BTreeMap<String, usize>,
+    fields: BTreeMap<String, usize>,
+}
+
+impl ColumnMapping {
+    /// `mapping` goes from attribute to column header. Attributes that aren't mapped are read
+    /// from a column with the same name as the attribute or custom field key, ignoring case
+    pub fn resolve(
+        headers: &[String],
+        mapping: &BTreeMap<String, String>,
+        fields: &[AppField],
+    ) -> Result<Self, String> {
+        let column = |header: &str| {
+            headers
+                .iter()
+                .position(|existing| existing == header)
+                .ok_or_else(|| format!("Column {header} is not in the file"))
+        };
+        let default_column = |name: &str| {
+            headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(name))
+        };
+
+        let mut resolved = Self::default();
+
+        for (attribute, header) in mapping {
+            if let Some(key) = attribute.strip_prefix("fields.") {
+                if !fields.iter().any(|field| field.key == key) {
+                    return Err(format!("Custom field {key} is not defined"));
+                }
+
+                resolved.fields.insert(key.to_string(), column(header)?);
+            } else if ATTRIBUTES.contains(&attribute.as_str()) {
+                resolved
+                    .attributes
+                    .insert(attribute.clone(), column(header)?);
+            } else {
+                return Err(format!("{attribute} can't be imported"));
+            }
+        }
+
+        for attribute in ATTRIBUTES {
+            if let (false, Some(column)) = (
+                resolved.attributes.contains_key(attribute),
+                default_column(attribute),
+            ) {
+                resolved.attributes.insert(attribute.to_string(), column);
+            }
+        }
+
+        for field in fields {
+            if let (false, Some(column)) = (
+                resolved.fields.contains_key(&field.key),
+                default_column(&field.key),
+            ) {
+                resolved.fields.insert(field.key.clone(), column);
+            }
+        }
+
+        match REQUIRED_ATTRIBUTES
+            .iter()
+            .find(|attribute| !resolved.attributes.contains_key(**attribute))
+        {
+            Some(attribute) => Err(format!("No column is mapped to {attribute}")),
+            None => Ok(resolved),
+        }
+    }
+
+    /// Builds the request a row stands for, every problem with the row is reported at once
+    pub fn request(
+        &self,
+        row: &ImportRow,
+        fields: &HashMap<String, AppField>,
+    ) -> Result<CreateAppHttpRequestBody, Vec<String>> {
+        let mut errors = Vec::new();
+
+        let cell = |attribute: &str| {
+            self.attributes
+                .get(attribute)
+                .and_then(|column| row.cell(*column))
+                .map(str::to_string)
+        };
+        let mut required = |attribute: &str| {
+            let value = cell(attribute);
+
+            if value.is_none() {
+                errors.push(format!("{attribute} is required"));
+            }
+
+            value
+        };
+
+        let name = required("name");
+        let url = required("url");
+        let state = required("state");
+        let category = required("category");
+
+        let state = state.and_then(|state| parse_variant(&state, "state", &mut errors));
+        let category =
+            category.and_then(|category| parse_variant(&category, "category", &mut errors));
+
+        let mut values = BTreeMap::new();
+
+        for (key, column) in &self.fields {
+            let (Some(field), Some(text)) = (fields.get(key), row.cell(*column)) else {
+                continue;
+            };
+
+            match field_value(field, text) {
+                Some(value) => {
+                    values.insert(key.clone(), value);
+                }
+                None => errors.push(format!(
+                    "Value {text} is not valid for {} field {key}",
+                    field.field_type
+                )),
+            }
+        }
+
+        match (name, url, state, category) {
+            (Some(name), Some(url), Some(state), Some(category)) if errors.is_empty() => {
+                Ok(CreateAppHttpRequestBody {
+                    name,
+                    state,
+                    url,
+                    category,
+                    description: cell("description"),
+                    tags: cell("tags"),
+                    fields: (!values.is_empty()).then_some(values),
+                })
+            }
+            _ => Err(errors),
+        }
+    }
+}
+
+/// Cells hold the serialized name of the variant, e.g. `Sales & Marketing`
+fn parse_variant<T: DeserializeOwned>(
+    text: &str,
+    attribute: &str,
+    errors: &mut Vec<String>,
+) -> Option<T> {
+    serde_json::from_value(Value::String(text.to_string()))
+        .map_err(|_| errors.push(format!("{text} is not a valid {attribute}")))
+        .ok()
+}
+
+/// Cells are always text, numbers and user IDs are parsed so they match the field type
+fn field_value(field: &AppField, text: &str) -> Option<Value> {
+    match field.field_type {
+        AppFieldType::Number => text.parse::<i64>().map(Value::from).ok().or_else(|| {
+            text.parse::<f64>()
+                .ok()
+                .and_then(Number::from_f64)
+                .map(Value::Number)
+        }),
+        AppFieldType::User => text.parse::<u32>().map(Value::from).ok(),
+        AppFieldType::Text | AppFieldType::Date | AppFieldType::Dropdown => {
+            Some(Value::String(text.to_string()))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum ImportRowStatus {
+    /// The app was created
+    Created,
+    /// Dry run only, the app would have been created
+    Valid,
+    /// An app with the same name already exists or appears earlier in the file
+    Duplicate,
+    /// The row doesn't describe a valid app
+    Invalid,
+    /// The row was valid but creating the app failed
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct ImportRowResult {
+    pub row: usize,
+    pub name: Option<String>,
+    pub status: ImportRowStatus,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<App>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub errors: Vec<String>,
+}
@@ -0,0 +1,541 @@
+use std::{collections::HashMap, fs, path::PathBuf};
+
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use ring::signature::{RsaPublicKeyComponents, RSA_PKCS1_2048_8192_SHA256};
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+use sha2::Sha256;
+use tracing::info;
+
+use crate::{
+    api_keys_service::Principal,
+    repository::{ApiKeyScope, AuthenticateError, IssueTokenError},
+    tenant::Tenant,
+};
+
+/// Clock skew tolerated when checking `exp` and `nbf`
+const LEEWAY_SECONDS: i64 = 60;
+
+/// Where the verification keys and OAuth clients come from, read from the environment by
+/// `backend::setup`
+#[derive(Debug, Clone)]
+pub struct JwtSettings {
+    /// Shared HS256 secret, also used to sign the tokens `/oauth/token` issues
+    pub secret: Option<String>,
+    /// JWKS with the RS256 public keys of an external issuer
+    pub jwks_file: Option<PathBuf>,
+    pub issuer: String,
+    /// Checked against `aud` when set
+    pub audience: Option<String>,
+    pub tenant_claim: String,
+    /// Lets tokens without the tenant claim in as the default tenant instead of rejecting them
+    pub default_tenant_fallback: bool,
+    pub token_ttl_seconds: u32,
+    /// Client-credentials clients as JSON, see `OAuthClient`
+    pub clients: Option<String>,
+}
+
+/// A client that can get tokens from `/oauth/token`, tokens carry its tenant and at most its
+/// scopes
+#[derive(Debug, Clone, Deserialize)]
+pub struct OAuthClient {
+    pub secret: String,
+    #[serde(default)]
+    pub tenant: Tenant,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+#[derive(Debug, Clone)]
+struct RsaKey {
+    kid: Option<String>,
+    n: Vec<u8>,
+    e: Vec<u8>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Jwks {
+    keys: Vec<Jwk>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Jwk {
+    kty: String,
+    kid: Option<String>,
+    #[serde(rename = "use")]
+    usage: Option<String>,
+    n: Option<String>,
+    e: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JwtHeader {
+    alg: String,
+    kid: Option<String>,
+}
+
+/// A token issued to a client, shaped like an RFC 6749 token response by the route
+#[derive(Debug, Clone)]
+pub struct IssuedToken {
+    pub access_token: String,
+    pub expires_in: u32,
+    pub scopes: Vec<ApiKeyScope>,
+}
+
+/// Checks JWT bearer tokens and issues them for the client-credentials grant. Only HS256 and
+/// RS256 are accepted, anything else including `none` is rejected
+#[derive(Debug, Clone)]
+pub struct JwtAuth {
+    secret: Option<Vec<u8>>,
+    rsa_keys: Vec<RsaKey>,
+    issuer: String,
+    audience: Option<String>,
+    tenant_claim: String,
+    default_tenant_fallback: bool,
+    token_ttl_seconds: u32,
+    clients: HashMap<String, OAuthClient>,
+}
+
+impl JwtAuth {
+    pub fn new(settings: JwtSettings) -> Result<Self, String> {
+        let rsa_keys = match &settings.jwks_file {
+            Some(path) => load_jwks(path)?,
+            None => Vec::new(),
+        };
+
+        if settings.secret.is_none() && rsa_keys.is_empty() {
+            return Err("JWT authentication needs JWT_SECRET or JWT_JWKS_FILE".to_string());
+        }
+
+        let clients = match &settings.clients {
+            Some(json) => {
+                serde_json::from_str(json).map_err(|e| format!("Invalid OAuth clients: {e}"))?
+            }
+            None => HashMap::new(),
+        };
+
+        Ok(Self {
+            secret: settings.secret.map(String::into_bytes),
+            rsa_keys,
+            issuer: settings.issuer,
+            audience: settings.audience,
+            tenant_claim: settings.tenant_claim,
+            default_tenant_fallback: settings.default_tenant_fallback,
+            token_ttl_seconds: settings.token_ttl_seconds,
+            clients,
+        })
+    }
+
+    /// Tokens have three dot separated parts, API keys have none
+    pub fn looks_like_jwt(token: &str) -> bool {
+        token.split('.').count() == 3
+    }
+
+    /// The tenant comes from the tenant claim, tokens without it are rejected unless the default
+    /// tenant fallback is turned on. Scopes come from the space separated `scope` claim or the
+    /// `scp` list, unknown ones are ignored. `sub` is required, rate limits and the audit trail
+    /// are kept per subject
+    #[tracing::instrument(skip(self, token))]
+    pub fn authenticate(&self, token: &str) -> Result<Principal, AuthenticateError> {
+        let claims = self
+            .verify(token)
+            .map_err(AuthenticateError::InvalidToken)?;
+
+        let tenant = match claims.get(&self.tenant_claim) {
+            Some(Value::String(tenant)) => {
+                Tenant::new(tenant).map_err(AuthenticateError::InvalidToken)?
+            }
+            Some(_) => {
+                return Err(AuthenticateError::InvalidToken(format!(
+                    "Claim {} must be a string",
+                    self.tenant_claim
+                )))
+            }
+            None if self.default_tenant_fallback => Tenant::default(),
+            None => {
+                return Err(AuthenticateError::InvalidToken(format!(
+                    "Token has no {} claim",
+                    self.tenant_claim
+                )))
+            }
+        };
+
+        let scope_names: Vec<&str> = match (claims.get("scope"), claims.get("scp")) {
+            (Some(Value::String(scope)), _) => scope.split_whitespace().collect(),
+            (_, Some(Value::Array(scp))) => scp.iter().filter_map(Value::as_str).collect(),
+            (_, Some(Value::String(scp))) => scp.split_whitespace().collect(),
+            _ => Vec::new(),
+        };
+
+        let subject = claims
+            .get("sub")
+            .and_then(Value::as_str)
+            .filter(|sub| !sub.is_empty())
+            .ok_or_else(|| AuthenticateError::InvalidToken("Token has no sub claim".to_string()))?;
+
+        Ok(Principal {
+            tenant,
+            scopes: parse_scopes(&scope_names),
+            subject: format!("client:{subject}"),
+        })
+    }
+
+    /// Verifies the signature and the registered claims, returning every claim
+    fn verify(&self, token: &str) -> Result<Map<String, Value>, String> {
+        let mut parts = token.split('.');
+        let (Some(header), Some(payload), Some(signature), None) =
+            (parts.next(), parts.next(), parts.next(), parts.next())
+        else {
+            return Err("Token is not a JWT".to_string());
+        };
+
+        let header: JwtHeader = decode_json(header)?;
+        let signature = URL_SAFE_NO_PAD
+            .decode(signature)
+            .map_err(|_| "Signature is not base64url".to_string())?;
+        let message = &token[..header_and_payload_len(token)];
+
+        match header.alg.as_str() {
+            "HS256" => {
+                let secret = self.secret.as_ref().ok_or("HS256 tokens need JWT_SECRET")?;
+                let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+                    .map_err(|_| "Invalid JWT_SECRET".to_string())?;
+                mac.update(message.as_bytes());
+                mac.verify_slice(&signature)
+                    .map_err(|_| "Invalid signature".to_string())?;
+            }
+            "RS256" => {
+                let key = self.rsa_key(header.kid.as_deref())?;
+                RsaPublicKeyComponents {
+                    n: &key.n,
+                    e: &key.e,
+                }
+                .verify(&RSA_PKCS1_2048_8192_SHA256, message.as_bytes(), &signature)
+                .map_err(|_| "Invalid signature".to_string())?;
+            }
+            alg => return Err(format!("Unsupported algorithm {alg}")),
+        }
+
+        let claims: Map<String, Value> = decode_json(payload)?;
+        let now = Utc::now().timestamp();
+
+        match claims.get("exp").and_then(Value::as_i64) {
+            Some(exp) if exp + LEEWAY_SECONDS <= now => return Err("Token has expired".to_string()),
+            Some(_) => {}
+            None => return Err("Token has no exp claim".to_string()),
+        }
+
+        if let Some(nbf) = claims.get("nbf").and_then(Value::as_i64) {
+            if nbf - LEEWAY_SECONDS > now {
+                return Err("Token is not valid yet".to_string());
+            }
+        }
+
+        if claims.get("iss").and_then(Value::as_str) != Some(self.issuer.as_str()) {
+            return Err(format!("Issuer must be {}", self.issuer));
+        }
+
+        if let Some(audience) = &self.audience {
+            let matches = match claims.get("aud") {
+                Some(Value::String(aud)) => aud == audience,
+                Some(Value::Array(auds)) => auds.iter().any(|aud| aud.as_str() == Some(audience)),
+                _ => false,
+            };
+
+            if !matches {
+                return Err(format!("Audience must include {audience}"));
+            }
+        }
+
+        Ok(claims)
+    }
+
+    /// A key without `kid` can only be picked when the JWKS holds a single key
+    fn rsa_key(&self, kid: Option<&str>) -> Result<&RsaKey, String> {
+        match kid {
+            Some(kid) => self
+                .rsa_keys
+                .iter()
+                .find(|key| key.kid.as_deref() == Some(kid))
+                .ok_or_else(|| format!("Unknown key {kid}")),
+            None if self.rsa_keys.len() == 1 => Ok(&self.rsa_keys[0]),
+            None => Err("Token has no kid".to_string()),
+        }
+    }
+
+    /// Client-credentials grant. Without `scope` the token gets every scope of the client
+    #[tracing::instrument(skip(self, client_secret))]
+    pub fn issue_token(
+        &self,
+        client_id: &str,
+        client_secret: &str,
+        scope: Option<&str>,
+    ) -> Result<IssuedToken, IssueTokenError> {
+        let secret = self
+            .secret
+            .as_ref()
+            .ok_or(IssueTokenError::SigningUnavailable)?;
+
+        let client = self
+            .clients
+            .get(client_id)
+            .filter(|client| client.secret == client_secret)
+            .ok_or(IssueTokenError::InvalidClient)?;
+
+        let scopes = match scope {
+            Some(scope) => {
+                let requested: Vec<&str> = scope.split_whitespace().collect();
+                let scopes = parse_scopes(&requested);
+
+                if scopes.len() != requested.len()
+                    || scopes.iter().any(|scope| !client.scopes.contains(scope))
+                {
+                    return Err(IssueTokenError::InvalidScope(scope.to_string()));
+                }
+
+                scopes
+            }
+            None => {
+                let mut scopes = client.scopes.clone();
+                scopes.sort();
+                scopes.dedup();
+                scopes
+            }
+        };
+
+        let now = Utc::now().timestamp();
+        let mut claims = json!({
+            "iss": self.issuer,
+            "sub": client_id,
+            "iat": now,
+            "exp": now + i64::from(self.token_ttl_seconds),
+            "scope": join_scopes(&scopes),
+        });
+        claims[&self.tenant_claim] = json!(client.tenant);
+        if let Some(audience) = &self.audience {
+            claims["aud"] = json!(audience);
+        }
+
+        let header = URL_SAFE_NO_PAD.encode(json!({"alg": "HS256", "typ": "JWT"}).to_string());
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let message = format!("{header}.{payload}");
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(secret)
+            .map_err(|_| IssueTokenError::SigningUnavailable)?;
+        mac.update(message.as_bytes());
+        let signature = URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes());
+
+        Ok(IssuedToken {
+            access_token: format!("{message}.{signature}"),
+            expires_in: self.token_ttl_seconds,
+            scopes,
+        })
+    }
+}
+
+pub fn join_scopes(scopes: &[ApiKeyScope]) -> String {
+    scopes
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Sorted and without duplicates, names that aren't a scope are dropped
+fn parse_scopes(names: &[&str]) -> Vec<ApiKeyScope> {
+    let mut scopes: Vec<ApiKeyScope> = names
+        .iter()
+        .filter_map(|name| serde_json::from_value(Value::String(name.to_string())).ok())
+        .collect();
+    scopes.sort();
+    scopes.dedup();
+    scopes
+}
+
+fn header_and_payload_len(token: &str) -> usize {
+    token.rfind('.').unwrap_or(token.len())
+}
+
+fn decode_json<T: serde::de::DeserializeOwned>(part: &str) -> Result<T, String> {
+    let bytes = URL_SAFE_NO_PAD
+        .decode(part)
+        .map_err(|_| "Token is not base64url".to_string())?;
+
+    serde_json::from_slice(&bytes).map_err(|e| format!("Token is not valid JSON: {e}"))
+}
+
+/// Only RSA signing keys are kept, other keys in the set are skipped
+fn load_jwks(path: &PathBuf) -> Result<Vec<RsaKey>, String> {
+    info!("Loading JWKS from {}", path.display());
+
+    let contents = fs::read_to_string(path).map_err(|e| format!("Unable to read JWKS: {e}"))?;
+    let jwks: Jwks =
+        serde_json::from_str(&contents).map_err(|e| format!("JWKS is not valid: {e}"))?;
+
+    let decode = |value: Option<String>, name: &str| {
+        value
+            .and_then(|value| URL_SAFE_NO_PAD.decode(value).ok())
+            .ok_or_else(|| format!("RSA key is missing a base64url {name}"))
+    };
+
+    jwks.keys
+        .into_iter()
+        .filter(|key| key.kty == "RSA" && key.usage.as_deref().unwrap_or("sig") == "sig")
+        .map(|key| {
+            Ok(RsaKey {
+                kid: key.kid,
+                n: decode(key.n, "n")?,
+                e: decode(key.e, "e")?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SECRET: &str = "test-secret";
+
+    fn auth(default_tenant_fallback: bool) -> JwtAuth {
+        JwtAuth::new(JwtSettings {
+            secret: Some(SECRET.to_string()),
+            jwks_file: None,
+            issuer: "torii-mock".to_string(),
+            audience: None,
+            tenant_claim: "tenant".to_string(),
+            default_tenant_fallback,
+            token_ttl_seconds: 3600,
+            clients: Some(
+                r#"{"ci": {"secret": "ci-secret", "tenant": "team-a", "scopes": ["read"]}}"#
+                    .to_string(),
+            ),
+        })
+        .unwrap()
+    }
+
+    fn token(alg: &str, claims: Value) -> String {
+        let header = URL_SAFE_NO_PAD.encode(json!({"alg": alg, "typ": "JWT"}).to_string());
+        let payload = URL_SAFE_NO_PAD.encode(claims.to_string());
+        let message = format!("{header}.{payload}");
+
+        let mut mac = Hmac::<Sha256>::new_from_slice(SECRET.as_bytes()).unwrap();
+        mac.update(message.as_bytes());
+
+        format!(
+            "{message}.{}",
+            URL_SAFE_NO_PAD.encode(mac.finalize().into_bytes())
+        )
+    }
+
+    fn claims() -> Value {
+        json!({
+            "iss": "torii-mock",
+            "sub": "ci",
+            "exp": Utc::now().timestamp() + 600,
+            "tenant": "team-a",
+            "scope": "read write unknown",
+        })
+    }
+
+    fn rejection(auth: &JwtAuth, token: &str) -> String {
+        match auth.authenticate(token) {
+            Err(AuthenticateError::InvalidToken(reason)) => reason,
+            other => panic!("Expected an invalid token, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn accepts_a_valid_hs256_token() {
+        let principal = auth(false).authenticate(&token("HS256", claims())).unwrap();
+
+        assert_eq!(principal.tenant.as_str(), "team-a");
+        assert_eq!(principal.subject, "client:ci");
+        assert_eq!(
+            principal.scopes,
+            vec![ApiKeyScope::Read, ApiKeyScope::Write]
+        );
+    }
+
+    #[test]
+    fn rejects_other_algorithms_and_bad_signatures() {
+        let auth = auth(false);
+        let valid = token("HS256", claims());
+        let (message, _) = valid.rsplit_once('.').unwrap();
+
+        assert_eq!(
+            rejection(&auth, &format!("{message}.")),
+            "Invalid signature"
+        );
+        assert_eq!(
+            rejection(&auth, &token("none", claims())),
+            "Unsupported algorithm none"
+        );
+        assert_eq!(
+            rejection(&auth, &token("HS512", claims())),
+            "Unsupported algorithm HS512"
+        );
+        assert_eq!(
+            rejection(&auth, &token("RS256", claims())),
+            "Token has no kid"
+        );
+    }
+
+    #[test]
+    fn checks_expiry_with_leeway() {
+        let auth = auth(false);
+        let now = Utc::now().timestamp();
+        let mut within_leeway = claims();
+        within_leeway["exp"] = json!(now - LEEWAY_SECONDS / 2);
+        let mut expired = claims();
+        expired["exp"] = json!(now - LEEWAY_SECONDS - 1);
+        let mut missing = claims();
+        missing.as_object_mut().unwrap().remove("exp");
+
+        assert!(auth.authenticate(&token("HS256", within_leeway)).is_ok());
+        assert_eq!(
+            rejection(&auth, &token("HS256", expired)),
+            "Token has expired"
+        );
+        assert_eq!(
+            rejection(&auth, &token("HS256", missing)),
+            "Token has no exp claim"
+        );
+    }
+
+    #[test]
+    fn rejects_another_issuer() {
+        let mut claims = claims();
+        claims["iss"] = json!("someone-else");
+
+        assert_eq!(
+            rejection(&auth(false), &token("HS256", claims)),
+            "Issuer must be torii-mock"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_the_default_tenant_only_when_enabled() {
+        let mut claims = claims();
+        claims.as_object_mut().unwrap().remove("tenant");
+        let token = token("HS256", claims);
+
+        assert_eq!(rejection(&auth(false), &token), "Token has no tenant claim");
+        assert!(auth(true).authenticate(&token).unwrap().tenant.is_default());
+    }
+
+    #[test]
+    fn issued_tokens_authenticate_as_the_client() {
+        let auth = auth(false);
+        let issued = auth.issue_token("ci", "ci-secret", None).unwrap();
+        let principal = auth.authenticate(&issued.access_token).unwrap();
+
+        assert_eq!(principal.tenant.as_str(), "team-a");
+        assert_eq!(principal.scopes, vec![ApiKeyScope::Read]);
+        assert!(matches!(
+            auth.issue_token("ci", "wrong", None),
+            Err(IssueTokenError::InvalidClient)
+        ));
+    }
+}
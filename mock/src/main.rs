@@ -1,6 +1,6 @@
 use axum::{
     middleware,
-    routing::{get, post},
+    routing::{get, post, put},
     Router,
 };
 use backend::setup;
@@ -15,11 +15,29 @@ use utoipa::{
 use utoipa_swagger_ui::SwaggerUi;
 
 mod api_error;
+mod api_keys_service;
+mod app_patch;
 mod apps_service;
 mod auth;
 mod backend;
+mod catalog;
+mod contracts_service;
+mod custom_fields;
+mod etag;
+mod export;
+mod faults;
+mod field_selection;
+mod import;
+mod jwt;
+mod rate_limit;
 mod repository;
+mod request_context;
 mod routes;
+mod search;
+mod sorting;
+mod tenant;
+mod users_service;
+mod webhooks;
 
 #[derive(OpenApi)]
 #[openapi(
@@ -30,8 +48,48 @@ mod routes;
         routes::get_app,
         routes::list_apps,
         routes::list_known_apps,
+        routes::get_known_app,
         routes::search_apps,
         routes::update_app,
+        routes::patch_app,
+        routes::bulk_apps,
+        routes::import_apps,
+        routes::export_apps,
+        routes::create_user,
+        routes::delete_user,
+        routes::get_user,
+        routes::list_users,
+        routes::update_user,
+        routes::list_app_users,
+        routes::list_user_apps,
+        routes::put_app_user,
+        routes::delete_app_user,
+        routes::create_contract,
+        routes::delete_contract,
+        routes::get_contract,
+        routes::list_contracts,
+        routes::update_contract,
+        routes::create_app_field,
+        routes::delete_app_field,
+        routes::get_app_field,
+        routes::list_app_fields,
+        routes::update_app_field,
+        routes::list_audit_events,
+        routes::create_webhook,
+        routes::delete_webhook,
+        routes::get_webhook,
+        routes::list_webhooks,
+        routes::update_webhook,
+        routes::list_webhook_deliveries,
+        routes::create_api_key,
+        routes::delete_api_key,
+        routes::get_api_key,
+        routes::list_api_keys,
+        routes::rotate_api_key,
+        routes::get_faults,
+        routes::update_faults,
+        routes::delete_faults,
+        routes::issue_token,
     ),
     modifiers(&SecurityAddon),
     security(
@@ -42,18 +100,115 @@ mod routes;
             "routes::get_app",
             "routes::list_apps",
             "routes::list_known_apps",
+            "routes::get_known_app",
             "routes::search_apps",
             "routes::update_app",
+            "routes::patch_app",
+            "routes::bulk_apps",
+            "routes::import_apps",
+            "routes::export_apps",
+            "routes::create_user",
+            "routes::delete_user",
+            "routes::get_user",
+            "routes::list_users",
+            "routes::update_user",
+            "routes::list_app_users",
+            "routes::list_user_apps",
+            "routes::put_app_user",
+            "routes::delete_app_user",
+            "routes::create_contract",
+            "routes::delete_contract",
+            "routes::get_contract",
+            "routes::list_contracts",
+            "routes::update_contract",
+            "routes::create_app_field",
+            "routes::delete_app_field",
+            "routes::get_app_field",
+            "routes::list_app_fields",
+            "routes::update_app_field",
+            "routes::list_audit_events",
+            "routes::create_webhook",
+            "routes::delete_webhook",
+            "routes::get_webhook",
+            "routes::list_webhooks",
+            "routes::update_webhook",
+            "routes::list_webhook_deliveries",
+            "routes::create_api_key",
+            "routes::delete_api_key",
+            "routes::get_api_key",
+            "routes::list_api_keys",
+            "routes::rotate_api_key",
+            "routes::get_faults",
+            "routes::update_faults",
+            "routes::delete_faults",
         ])
     ),
     components(schemas(
         routes::AddAppHttpRequestBody,
         routes::CreateAppHttpRequestBody,
+        routes::ListAppsHttpResponse,
         routes::UpdateAppHttpRequestBody,
+        routes::BulkAppsHttpRequestBody,
+        routes::BulkAppOperation,
+        routes::BulkAppsHttpResponse,
+        routes::BulkAppResult,
+        routes::ImportAppsForm,
+        routes::ImportAppsHttpResponse,
+        import::ImportRowResult,
+        import::ImportRowStatus,
+        export::ExportFormat,
+        faults::FaultConfig,
+        faults::FaultRule,
+        routes::CreateUserHttpRequestBody,
+        routes::ListUsersHttpResponse,
+        routes::UpdateUserHttpRequestBody,
+        routes::ListAppUsersHttpResponse,
+        routes::ListUserAppsHttpResponse,
+        routes::PutAppUserHttpRequestBody,
+        routes::CreateContractHttpRequestBody,
+        routes::ListContractsHttpResponse,
+        routes::UpdateContractHttpRequestBody,
+        routes::CreateAppFieldHttpRequestBody,
+        routes::ListAppFieldsHttpResponse,
+        routes::UpdateAppFieldHttpRequestBody,
+        routes::ListAuditEventsHttpResponse,
+        routes::CreateWebhookHttpRequestBody,
+        routes::ListWebhooksHttpResponse,
+        routes::UpdateWebhookHttpRequestBody,
+        routes::ListWebhookDeliveriesHttpResponse,
+        routes::CreateApiKeyHttpRequestBody,
+        routes::ListApiKeysHttpResponse,
+        routes::RotateApiKeyHttpRequestBody,
+        routes::IssueTokenForm,
+        routes::IssueTokenHttpResponse,
         repository::models::App,
         repository::models::AppCategory,
         repository::models::AppState,       
-        apps_service::KnownApp 
+        repository::models::User,
+        repository::models::UserLifecycleStatus,
+        repository::models::AppUser,
+        repository::models::LicenseStatus,
+        repository::models::Contract,
+        repository::models::AppField,
+        repository::models::AppFieldType,
+        repository::models::AuditEvent,
+        repository::models::AuditAction,
+        repository::models::AuditChange,
+        repository::models::Webhook,
+        repository::models::RedactedWebhook,
+        repository::models::WebhookEventType,
+        repository::models::WebhookDelivery,
+        repository::models::DeliveryStatus,
+        repository::models::DeliveryAttempt,
+        repository::models::ApiKey,
+        repository::models::ApiKeyScope,
+        repository::models::CreatedApiKey,
+        catalog::KnownApp,
+        apps_service::ScoredApp,
+        apps_service::AppUserDetails,
+        apps_service::UserAppDetails,
+        sorting::AppSortField,
+        sorting::SortOrder 
     ))
 )]
 struct ApiDoc;
@@ -93,17 +248,101 @@ async fn main() -> Result<(), Error> {
             "/v1.0/apps/:id",
             get(routes::get_app)
                 .put(routes::update_app)
+                .patch(routes::patch_app)
                 .delete(routes::delete_app),
         )
+        .route("/v1.0/apps/:id/users", get(routes::list_app_users))
+        .route(
+            "/v1.0/apps/:id/users/:user_id",
+            put(routes::put_app_user).delete(routes::delete_app_user),
+        )
         .route("/v1.0/apps/custom", post(routes::create_app))
+        .route(
+            "/v1.0/apps/fields",
+            get(routes::list_app_fields).post(routes::create_app_field),
+        )
+        .route(
+            "/v1.0/apps/fields/:key",
+            get(routes::get_app_field)
+                .put(routes::update_app_field)
+                .delete(routes::delete_app_field),
+        )
+        .route("/v1.0/apps/bulk", post(routes::bulk_apps))
+        .route("/v1.0/apps/import", post(routes::import_apps))
+        .route("/v1.0/apps/export", get(routes::export_apps))
         .route("/v1.0/apps/search", get(routes::search_apps))
         .route("/v1.0/apps/known", get(routes::list_known_apps))
+        .route("/v1.0/apps/known/:id", get(routes::get_known_app))
+        .route(
+            "/v1.0/users",
+            get(routes::list_users).post(routes::create_user),
+        )
+        .route(
+            "/v1.0/users/:id",
+            get(routes::get_user)
+                .put(routes::update_user)
+                .delete(routes::delete_user),
+        )
+        .route("/v1.0/users/:id/apps", get(routes::list_user_apps))
+        .route(
+            "/v1.0/contracts",
+            get(routes::list_contracts).post(routes::create_contract),
+        )
+        .route(
+            "/v1.0/contracts/:id",
+            get(routes::get_contract)
+                .put(routes::update_contract)
+                .delete(routes::delete_contract),
+        )
+        .route("/v1.0/audit", get(routes::list_audit_events))
+        .route(
+            "/v1.0/webhooks",
+            get(routes::list_webhooks).post(routes::create_webhook),
+        )
+        .route(
+            "/v1.0/webhooks/:id",
+            get(routes::get_webhook)
+                .put(routes::update_webhook)
+                .delete(routes::delete_webhook),
+        )
+        .route(
+            "/v1.0/webhooks/:id/deliveries",
+            get(routes::list_webhook_deliveries),
+        )
+        .route(
+            "/v1.0/admin/api-keys",
+            get(routes::list_api_keys).post(routes::create_api_key),
+        )
+        .route(
+            "/v1.0/admin/api-keys/:id",
+            get(routes::get_api_key).delete(routes::delete_api_key),
+        )
+        .route(
+            "/v1.0/admin/api-keys/:id/rotate",
+            post(routes::rotate_api_key),
+        )
+        .route(
+            "/v1.0/admin/faults",
+            get(routes::get_faults)
+                .put(routes::update_faults)
+                .delete(routes::delete_faults),
+        )
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            rate_limit::rate_limit,
+        ))
         .layer(middleware::from_fn_with_state(
             app_state.clone(),
             auth::auth,
+        ))
+        .layer(middleware::from_fn_with_state(
+            app_state.clone(),
+            faults::inject_faults,
         ));
 
+    // Clients exchange their credentials here before they have a token
     let app = router
+        .route("/oauth/token", post(routes::issue_token))
         .merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", ApiDoc::openapi()))
         .with_state(app_state)
         .layer(tracing_layer);
@@ -0,0 +1,255 @@
+use std::{num::NonZeroUsize, sync::Mutex, time::Instant};
+
+use axum::{
+    extract::{Request, State},
+    http::{HeaderMap, HeaderName, HeaderValue},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use lru::LruCache;
+use tracing::warn;
+
+use crate::{
+    api_error::ApiError, api_keys_service::Principal, apps_service::AppsServiceTrait,
+    backend::Backend, contracts_service::ContractsServiceTrait, users_service::UsersServiceTrait,
+};
+
+const LIMIT_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-limit");
+const REMAINING_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-remaining");
+const RESET_HEADER: HeaderName = HeaderName::from_static("x-ratelimit-reset");
+
+/// The least recently used bucket is dropped once there are this many. A dropped bucket starts
+/// over full, which only lets a caller that went quiet for a while back in early
+const MAX_BUCKETS: NonZeroUsize = match NonZeroUsize::new(10_000) {
+    Some(max) => max,
+    None => unreachable!(),
+};
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+}
+
+/// What the limiter decided for one request
+#[derive(Debug, Clone, Copy)]
+pub struct RateLimitDecision {
+    pub allowed: bool,
+    pub limit: u32,
+    pub remaining: u32,
+    /// Seconds until the bucket is full again
+    pub reset_seconds: u64,
+    /// Seconds until the next request is allowed, zero when this one was
+    pub retry_after_seconds: u64,
+}
+
+/// Token bucket per caller. Every API key or OAuth client can make `burst` requests at once and
+/// gets `per_minute` requests a minute after that
+#[derive(Debug)]
+pub struct RateLimiter {
+    burst: u32,
+    per_second: f64,
+    buckets: Mutex<LruCache<String, Bucket>>,
+}
+
+impl RateLimiter {
+    pub fn new(per_minute: u32, burst: u32) -> Self {
+        Self {
+            burst,
+            per_second: f64::from(per_minute) / 60.0,
+            buckets: Mutex::new(LruCache::new(MAX_BUCKETS)),
+        }
+    }
+
+    /// Takes a token from the bucket of `key` when there is one
+    pub fn check(&self, key: &str) -> RateLimitDecision {
+        self.check_at(key, Instant::now())
+    }
+
+    fn check_at(&self, key: &str, now: Instant) -> RateLimitDecision {
+        let capacity = f64::from(self.burst);
+        let mut buckets = self.buckets.lock().unwrap();
+
+        let bucket = buckets.get_or_insert_mut(key.to_string(), || Bucket {
+            tokens: capacity,
+            updated: now,
+        });
+
+        bucket.tokens =
+            (bucket.tokens + elapsed(bucket.updated, now) * self.per_second).min(capacity);
+        bucket.updated = now;
+
+        let allowed = bucket.tokens >= 1.0;
+        if allowed {
+            bucket.tokens -= 1.0;
+        }
+
+        RateLimitDecision {
+            allowed,
+            limit: self.burst,
+            remaining: bucket.tokens.floor() as u32,
+            reset_seconds: self.seconds_until(capacity - bucket.tokens),
+            retry_after_seconds: if allowed {
+                0
+            } else {
+                self.seconds_until(1.0 - bucket.tokens).max(1)
+            },
+        }
+    }
+
+    fn seconds_until(&self, missing_tokens: f64) -> u64 {
+        if missing_tokens <= 0.0 {
+            0
+        } else if self.per_second <= 0.0 {
+            u64::MAX
+        } else {
+            (missing_tokens / self.per_second).ceil() as u64
+        }
+    }
+}
+
+fn elapsed(since: Instant, now: Instant) -> f64 {
+    now.saturating_duration_since(since).as_secs_f64()
+}
+
+impl RateLimitDecision {
+    pub fn insert_headers(&self, headers: &mut HeaderMap) {
+        headers.insert(LIMIT_HEADER, HeaderValue::from(self.limit));
+        headers.insert(REMAINING_HEADER, HeaderValue::from(self.remaining));
+        headers.insert(RESET_HEADER, HeaderValue::from(self.reset_seconds));
+    }
+
+    /// The 429 sent when the request wasn't allowed
+    pub fn rejection(&self) -> Option<Response> {
+        if self.allowed {
+            return None;
+        }
+
+        warn!(
+            "Rate limit exceeded, retry after {} seconds",
+            self.retry_after_seconds
+        );
+
+        let mut response = ApiError::TooManyRequests(self.retry_after_seconds).into_response();
+        self.insert_headers(response.headers_mut());
+
+        Some(response)
+    }
+}
+
+/// Runs after `auth::auth` so every API key and OAuth client gets a bucket of its own,
+/// whichever secret or token it sends. `/oauth/token` is limited per client by its handler
+pub async fn rate_limit<AS, US, CS>(
+    State(state): State<Backend<AS, US, CS>>,
+    req: Request,
+    next: Next,
+) -> Response
+where
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+{
+    let (Some(limiter), Some(principal)) =
+        (&state.rate_limiter, req.extensions().get::<Principal>())
+    else {
+        return next.run(req).await;
+    };
+
+    let decision = limiter.check(&format!("{}/{}", principal.tenant, principal.subject));
+    if let Some(rejection) = decision.rejection() {
+        return rejection;
+    }
+
+    let mut response = next.run(req).await;
+    decision.insert_headers(response.headers_mut());
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use axum::http::{header, StatusCode};
+
+    use super::*;
+
+    #[test]
+    fn allows_the_burst_then_rejects_until_a_token_is_refilled() {
+        let limiter = RateLimiter::new(60, 2);
+        let start = Instant::now();
+
+        let first = limiter.check_at("key", start);
+        let second = limiter.check_at("key", start);
+        let third = limiter.check_at("key", start);
+
+        assert!(first.allowed && second.allowed);
+        assert_eq!((first.remaining, second.remaining), (1, 0));
+        assert!(!third.allowed);
+        assert_eq!(third.retry_after_seconds, 1);
+        assert_eq!(third.reset_seconds, 2);
+
+        assert!(
+            !limiter
+                .check_at("key", start + Duration::from_millis(500))
+                .allowed
+        );
+        assert!(
+            limiter
+                .check_at("key", start + Duration::from_secs(1))
+                .allowed
+        );
+    }
+
+    #[test]
+    fn refills_no_further_than_the_burst() {
+        let limiter = RateLimiter::new(60, 3);
+        let start = Instant::now();
+
+        limiter.check_at("key", start);
+        let decision = limiter.check_at("key", start + Duration::from_secs(3600));
+
+        assert_eq!(decision.remaining, 2);
+        assert_eq!(decision.reset_seconds, 1);
+    }
+
+    #[test]
+    fn keeps_a_bucket_per_key() {
+        let limiter = RateLimiter::new(1, 1);
+        let start = Instant::now();
+
+        assert!(limiter.check_at("a", start).allowed);
+        assert!(!limiter.check_at("a", start).allowed);
+        assert!(limiter.check_at("b", start).allowed);
+    }
+
+    #[test]
+    fn retry_after_counts_down_at_the_per_minute_rate() {
+        let limiter = RateLimiter::new(6, 1);
+        let start = Instant::now();
+
+        limiter.check_at("key", start);
+        let decision = limiter.check_at("key", start + Duration::from_secs(4));
+
+        assert!(!decision.allowed);
+        assert_eq!(decision.retry_after_seconds, 6);
+    }
+
+    #[test]
+    fn rejection_carries_retry_after_and_rate_limit_headers() {
+        let limiter = RateLimiter::new(60, 1);
+        let start = Instant::now();
+
+        let allowed = limiter.check_at("key", start);
+        assert!(allowed.rejection().is_none());
+
+        let response = limiter.check_at("key", start).rejection().unwrap();
+        let headers = response.headers();
+
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(headers[header::RETRY_AFTER], "1");
+        assert_eq!(headers[LIMIT_HEADER], "1");
+        assert_eq!(headers[REMAINING_HEADER], "0");
+        assert_eq!(headers[RESET_HEADER], "1");
+    }
+}
@@ -0,0 +1,249 @@
+use super::{
+    CreateApiKeyError, DeleteApiKeyError, GetApiKeyError, ListApiKeysError, StoredApiKey,
+    UpdateApiKeyError,
+};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::operation::update_item::UpdateItemError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_item, from_items, to_attribute_value, to_item};
+use tracing::instrument;
+
+use crate::tenant::Tenant;
+
+/// Keys are looked up by ID before the tenant of a request is known, so unlike every other
+/// repository this one isn't scoped to a tenant and listing takes the tenant instead
+#[async_trait]
+pub trait ApiKeysRepository: std::fmt::Debug + Send + Sync + Clone {
+    async fn get_api_key(&self, id: &str) -> Result<StoredApiKey, GetApiKeyError>;
+    async fn create_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, CreateApiKeyError>;
+    async fn update_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, UpdateApiKeyError>;
+    async fn delete_api_key(&self, id: &str) -> Result<(), DeleteApiKeyError>;
+    async fn list_api_keys(&self, tenant: &Tenant) -> Result<Vec<StoredApiKey>, ListApiKeysError>;
+    /// Only touches `lastUsedAt`, so it can't undo a concurrent rotation or revocation
+    async fn record_use(&self, id: &str, at: DateTime<Utc>) -> Result<(), UpdateApiKeyError>;
+}
+
+/// Keys live in the same table as apps, keyed as `api_key#<id>` whatever their tenant
+#[derive(Debug, Clone)]
+pub struct DynamoApiKeysRepository {
+    pub dynamo_client: aws_sdk_dynamodb::Client,
+    pub table_name: String,
+}
+
+impl DynamoApiKeysRepository {
+    #[instrument]
+    pub fn new(dynamo_client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        Self {
+            dynamo_client,
+            table_name,
+        }
+    }
+}
+
+fn api_key_pk(id: &str) -> String {
+    format!("api_key#{id}")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ApiKeyDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    key: StoredApiKey,
+}
+
+impl ApiKeyDynamoItem {
+    #[instrument(skip(key))]
+    pub fn new(key: &StoredApiKey) -> Self {
+        Self {
+            pk: api_key_pk(&key.key.id),
+            entity_type: "api_key".to_string(),
+            key: key.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl ApiKeysRepository for DynamoApiKeysRepository {
+    #[instrument(skip(self))]
+    async fn get_api_key(&self, id: &str) -> Result<StoredApiKey, GetApiKeyError> {
+        let result = self
+            .dynamo_client
+            .get_item()
+            .table_name(&self.table_name)
+            .key("pk", AttributeValue::S(api_key_pk(id)))
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                GetApiKeyError::UnexpectedError
+            })?;
+
+        if let Some(item) = result.item {
+            let key: StoredApiKey = from_item(item)?;
+
+            Ok(key)
+        } else {
+            Err(GetApiKeyError::ResourceNotFound(id.to_string()))
+        }
+    }
+
+    #[instrument(skip(self, key))]
+    async fn create_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, CreateApiKeyError> {
+        let item = to_item(ApiKeyDynamoItem::new(&key))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    PutItemError::ConditionalCheckFailedException(_) => {
+                        CreateApiKeyError::ResourceAlreadyExists(key.key.id.clone())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        CreateApiKeyError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(key)
+    }
+
+    #[instrument(skip(self, key))]
+    async fn update_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, UpdateApiKeyError> {
+        let item = to_item(ApiKeyDynamoItem::new(&key))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    PutItemError::ConditionalCheckFailedException(_) => {
+                        UpdateApiKeyError::ResourceNotFound(key.key.id.clone())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        UpdateApiKeyError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(key)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_api_key(&self, id: &str) -> Result<(), DeleteApiKeyError> {
+        self.dynamo_client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key("pk", AttributeValue::S(api_key_pk(id)))
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    DeleteItemError::ConditionalCheckFailedException(_) => {
+                        DeleteApiKeyError::ResourceNotFound(id.to_string())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        DeleteApiKeyError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_api_keys(&self, tenant: &Tenant) -> Result<Vec<StoredApiKey>, ListApiKeysError> {
+        let values = HashMap::from([
+            (
+                ":entity_type".to_string(),
+                AttributeValue::S("api_key".to_string()),
+            ),
+            (":tenant".to_string(), AttributeValue::S(tenant.to_string())),
+        ]);
+
+        let mut keys = Vec::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression("entity_type = :entity_type AND #tenant = :tenant")
+                .expression_attribute_names("#tenant", "tenant")
+                .set_expression_attribute_values(Some(values.clone()))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListApiKeysError::UnexpectedError
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<StoredApiKey> = from_items(items)?;
+                keys.extend(page);
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(keys),
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn record_use(&self, id: &str, at: DateTime<Utc>) -> Result<(), UpdateApiKeyError> {
+        self.dynamo_client
+            .update_item()
+            .table_name(&self.table_name)
+            .key("pk", AttributeValue::S(api_key_pk(id)))
+            .update_expression("SET lastUsedAt = :at")
+            .condition_expression("attribute_exists(pk)")
+            .expression_attribute_values(":at", to_attribute_value(at)?)
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    UpdateItemError::ConditionalCheckFailedException(_) => {
+                        UpdateApiKeyError::ResourceNotFound(id.to_string())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        UpdateApiKeyError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,222 @@
+use super::tenancy::{tenant_condition, tenant_pk, tenant_value, TENANT_PLACEHOLDER};
+use super::{
+    AppField, CreateAppFieldError, DeleteAppFieldError, GetAppFieldError, ListAppFieldsError,
+    UpdateAppFieldError,
+};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::{Deserialize, Serialize};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_item, from_items, to_item};
+use tracing::instrument;
+
+use crate::tenant::{Tenant, TenantScoped};
+
+#[async_trait]
+pub trait AppFieldsRepository: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    async fn get_app_field(&self, key: &str) -> Result<AppField, GetAppFieldError>;
+    async fn create_app_field(&self, field: AppField) -> Result<AppField, CreateAppFieldError>;
+    async fn delete_app_field(&self, key: &str) -> Result<(), DeleteAppFieldError>;
+    async fn list_app_fields(&self) -> Result<Vec<AppField>, ListAppFieldsError>;
+    async fn update_app_field(&self, field: AppField) -> Result<AppField, UpdateAppFieldError>;
+}
+
+/// Field definitions live in the same table as apps, keyed as `app_field#<key>`. Keys of tenants
+/// other than the default one are prefixed with the tenant
+#[derive(Debug, Clone)]
+pub struct DynamoAppFieldsRepository {
+    pub dynamo_client: aws_sdk_dynamodb::Client,
+    pub table_name: String,
+    pub tenant: Tenant,
+}
+
+impl DynamoAppFieldsRepository {
+    #[instrument]
+    pub fn new(dynamo_client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        Self {
+            dynamo_client,
+            table_name,
+            tenant: Tenant::default(),
+        }
+    }
+}
+
+fn app_field_pk(key: &str) -> String {
+    format!("app_field#{key}")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppFieldDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    field: AppField,
+}
+
+impl AppFieldDynamoItem {
+    #[instrument]
+    pub fn new(tenant: &Tenant, field: &AppField) -> Self {
+        Self {
+            pk: tenant_pk(tenant, &app_field_pk(&field.key)),
+            entity_type: "app_field".to_string(),
+            field: field.clone(),
+        }
+    }
+}
+
+impl TenantScoped for DynamoAppFieldsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl AppFieldsRepository for DynamoAppFieldsRepository {
+    #[instrument(skip(self))]
+    async fn get_app_field(&self, key: &str) -> Result<AppField, GetAppFieldError> {
+        let result = self
+            .dynamo_client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &app_field_pk(key))),
+            )
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                GetAppFieldError::UnexpectedError
+            })?;
+
+        if let Some(item) = result.item {
+            let field: AppField = from_item(item)?;
+
+            Ok(field)
+        } else {
+            Err(GetAppFieldError::ResourceNotFound(key.to_string()))
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn create_app_field(&self, field: AppField) -> Result<AppField, CreateAppFieldError> {
+        let item = to_item(AppFieldDynamoItem::new(&self.tenant, &field))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    PutItemError::ConditionalCheckFailedException(_) => {
+                        CreateAppFieldError::ResourceAlreadyExists(field.key.clone())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        CreateAppFieldError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(field)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app_field(&self, key: &str) -> Result<(), DeleteAppFieldError> {
+        self.dynamo_client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &app_field_pk(key))),
+            )
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    DeleteItemError::ConditionalCheckFailedException(_) => {
+                        DeleteAppFieldError::ResourceNotFound(key.to_string())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        DeleteAppFieldError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_app_fields(&self) -> Result<Vec<AppField>, ListAppFieldsError> {
+        let mut fields = Vec::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression(format!(
+                    "entity_type = :entity_type AND {}",
+                    tenant_condition(&self.tenant)
+                ))
+                .expression_attribute_values(
+                    ":entity_type",
+                    AttributeValue::S("app_field".to_string()),
+                )
+                .expression_attribute_values(TENANT_PLACEHOLDER, tenant_value(&self.tenant))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListAppFieldsError::UnexpectedError
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<AppField> = from_items(items)?;
+                fields.extend(page);
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(fields),
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn update_app_field(&self, field: AppField) -> Result<AppField, UpdateAppFieldError> {
+        let item = to_item(AppFieldDynamoItem::new(&self.tenant, &field))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                UpdateAppFieldError::UnexpectedError
+            })?;
+
+        Ok(field)
+    }
+}
@@ -0,0 +1,225 @@
+use super::tenancy::{tenant_condition, tenant_pk, tenant_value, TENANT_PLACEHOLDER};
+use super::{AppUser, DeleteAppUserError, LicenseStatus, ListAppUsersError, PutAppUserError};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::{Deserialize, Serialize};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_items, to_attribute_value, to_item};
+use tracing::instrument;
+
+use crate::tenant::{Tenant, TenantScoped};
+
+#[async_trait]
+pub trait AppUsersRepository: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    /// Creates the link or replaces it if the user is already linked to the app
+    async fn put_app_user(&self, app_user: AppUser) -> Result<AppUser, PutAppUserError>;
+    async fn delete_app_user(&self, app_id: u16, user_id: u32) -> Result<(), DeleteAppUserError>;
+    async fn list_app_users(&self, app_id: u16) -> Result<Vec<AppUser>, ListAppUsersError>;
+    async fn list_user_apps(&self, user_id: u32) -> Result<Vec<AppUser>, ListAppUsersError>;
+    /// Number of users with an active license, keyed by app ID. Apps without any are left out
+    async fn count_active_users(&self) -> Result<HashMap<u16, u32>, ListAppUsersError>;
+}
+
+/// Links are keyed as `app_user#<app id>#<user id>`, `app_id_index` and `user_id_index` only
+/// contain links since no other entity has an `appId` or `userId` attribute. Keys of tenants
+/// other than the default one are prefixed with the tenant
+#[derive(Debug, Clone)]
+pub struct DynamoAppUsersRepository {
+    pub dynamo_client: aws_sdk_dynamodb::Client,
+    pub table_name: String,
+    pub tenant: Tenant,
+}
+
+impl DynamoAppUsersRepository {
+    #[instrument]
+    pub fn new(dynamo_client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        Self {
+            dynamo_client,
+            table_name,
+            tenant: Tenant::default(),
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn query_index(
+        &self,
+        index_name: &str,
+        attribute: &str,
+        value: AttributeValue,
+    ) -> Result<Vec<AppUser>, ListAppUsersError> {
+        let mut app_users = Vec::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .query()
+                .table_name(&self.table_name)
+                .index_name(index_name)
+                .key_condition_expression("#key = :key_val")
+                .filter_expression(tenant_condition(&self.tenant))
+                .expression_attribute_names("#key", attribute)
+                .expression_attribute_values(":key_val", value.clone())
+                .expression_attribute_values(TENANT_PLACEHOLDER, tenant_value(&self.tenant))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListAppUsersError::UnexpectedError
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<AppUser> = from_items(items)?;
+                app_users.extend(page);
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(app_users),
+            }
+        }
+    }
+}
+
+fn app_user_pk(app_id: u16, user_id: u32) -> String {
+    format!("app_user#{app_id}#{user_id}")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AppUserDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    app_user: AppUser,
+}
+
+impl AppUserDynamoItem {
+    #[instrument]
+    pub fn new(tenant: &Tenant, app_user: &AppUser) -> Self {
+        Self {
+            pk: tenant_pk(tenant, &app_user_pk(app_user.app_id, app_user.user_id)),
+            entity_type: "app_user".to_string(),
+            app_user: app_user.clone(),
+        }
+    }
+}
+
+impl TenantScoped for DynamoAppUsersRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl AppUsersRepository for DynamoAppUsersRepository {
+    #[instrument(skip(self))]
+    async fn put_app_user(&self, app_user: AppUser) -> Result<AppUser, PutAppUserError> {
+        let item = to_item(AppUserDynamoItem::new(&self.tenant, &app_user))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                PutAppUserError::UnexpectedError
+            })?;
+
+        Ok(app_user)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app_user(&self, app_id: u16, user_id: u32) -> Result<(), DeleteAppUserError> {
+        self.dynamo_client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &app_user_pk(app_id, user_id))),
+            )
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    DeleteItemError::ConditionalCheckFailedException(_) => {
+                        DeleteAppUserError::ResourceNotFound { app_id, user_id }
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        DeleteAppUserError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_app_users(&self, app_id: u16) -> Result<Vec<AppUser>, ListAppUsersError> {
+        self.query_index("app_id_index", "appId", to_attribute_value(app_id)?)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_user_apps(&self, user_id: u32) -> Result<Vec<AppUser>, ListAppUsersError> {
+        self.query_index("user_id_index", "userId", to_attribute_value(user_id)?)
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn count_active_users(&self) -> Result<HashMap<u16, u32>, ListAppUsersError> {
+        let mut counts: HashMap<u16, u32> = HashMap::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression(format!(
+                    "entity_type = :entity_type AND #status = :status AND {}",
+                    tenant_condition(&self.tenant)
+                ))
+                .expression_attribute_names("#status", "licenseStatus")
+                .expression_attribute_values(
+                    ":entity_type",
+                    AttributeValue::S("app_user".to_string()),
+                )
+                .expression_attribute_values(":status", to_attribute_value(LicenseStatus::Active)?)
+                .expression_attribute_values(TENANT_PLACEHOLDER, tenant_value(&self.tenant))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListAppUsersError::UnexpectedError
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<AppUser> = from_items(items)?;
+
+                for app_user in page {
+                    *counts.entry(app_user.app_id).or_default() += 1;
+                }
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(counts),
+            }
+        }
+    }
+}
@@ -1,32 +1,292 @@
+use crate::{
+    custom_fields::value_matches,
+    tenant::{Tenant, TenantScoped},
+};
+
+use super::tenancy::{tenant_condition, tenant_pk, tenant_value, TENANT_PLACEHOLDER};
 use super::{
-    AddAppError, App, CreateAppError, DeleteAppError, GetAppError, ListAppsError, UpdateAppError,
+    AddAppError, App, AppCategory, AppState, CreateAppError, DeleteAppError, GetAppError,
+    ListAppsError, UpdateAppError, WriteAppsError,
 };
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use aws_sdk_dynamodb::error::DisplayErrorContext;
 use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
 use aws_sdk_dynamodb::operation::get_item::GetItemError;
 use aws_sdk_dynamodb::operation::put_item::PutItemError;
-use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use aws_sdk_dynamodb::operation::transact_write_items::TransactWriteItemsError;
+use aws_sdk_dynamodb::types::{
+    AttributeValue, Delete, Put, ReturnValue, ReturnValuesOnConditionCheckFailure,
+    TransactWriteItem,
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use chrono::{DateTime, Duration, DurationRound, Utc};
 use serde::{Deserialize, Serialize};
-use serde_dynamo::aws_sdk_dynamodb_1::{from_item, from_items, to_item};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_item, from_items, to_attribute_value, to_item};
 use tracing::{debug, info, instrument};
 
 #[async_trait]
-pub trait AppsRepository: std::fmt::Debug + Send + Sync + Clone {
+pub trait AppsRepository: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
     async fn get_app(&self, id: u16) -> Result<App, GetAppError>;
     async fn get_app_by_name(&self, name: &str) -> Result<App, GetAppError>;
     async fn add_app(&self, app: App) -> Result<App, AddAppError>;
     async fn create_app(&self, app: App) -> Result<App, CreateAppError>;
     async fn delete_app(&self, id: u16) -> Result<(), DeleteAppError>;
-    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError>;
+    async fn list_apps_page(
+        &self,
+        size: Option<u16>,
+        cursor: Option<String>,
+        filter: &AppsFilter,
+    ) -> Result<AppsPage, ListAppsError>;
+    /// Only succeeds while the stored app is still at `app.version`, the app is saved and
+    /// returned with the next version
     async fn update_app(&self, app: App) -> Result<App, UpdateAppError>;
+    /// Applies every write or none of them. Puts and updates return the stored app, deletes
+    /// return `None`
+    async fn write_apps(&self, writes: Vec<AppWrite>) -> Result<Vec<Option<App>>, WriteAppsError>;
     async fn get_id(&self) -> Result<u16, ()>;
+
+    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError> {
+        self.list_filtered_apps(&AppsFilter::default()).await
+    }
+
+    /// Follows `next_cursor` until every page has been read
+    async fn list_filtered_apps(&self, filter: &AppsFilter) -> Result<Vec<App>, ListAppsError> {
+        let mut apps = Vec::new();
+        let mut cursor = None;
+
+        loop {
+            let page = self.list_apps_page(None, cursor, filter).await?;
+            apps.extend(page.apps);
+
+            match page.next_cursor {
+                Some(next_cursor) => cursor = Some(next_cursor),
+                None => return Ok(apps),
+            }
+        }
+    }
+}
+
+/// A single write of `AppsRepository::write_apps`, each has the same condition as the
+/// single app method it stands for
+#[derive(Debug, Clone)]
+pub enum AppWrite {
+    /// Like `create_app`, fails when the ID is taken
+    Put(App),
+    /// Like `update_app`, fails unless the stored app is still at `app.version`
+    Update(App),
+    /// Like `delete_app`, fails when the app does not exist
+    Delete(u16),
+}
+
+impl AppWrite {
+    pub fn id(&self) -> u16 {
+        match self {
+            Self::Put(app) | Self::Update(app) => app.id,
+            Self::Delete(id) => *id,
+        }
+    }
+}
+
+/// A single page of apps, `next_cursor` is set when there may be more apps to read
+#[derive(Debug, Clone, Default)]
+pub struct AppsPage {
+    pub apps: Vec<App>,
+    pub next_cursor: Option<String>,
+}
+
+/// Opaque cursor pointing after the app with `id`, storage keys never reach clients
+pub fn encode_cursor(id: u16) -> String {
+    URL_SAFE_NO_PAD.encode(id.to_string())
+}
+
+/// ID of the app a cursor made by `encode_cursor` points after
+pub fn decode_cursor(cursor: &str) -> Result<u16, ListAppsError> {
+    URL_SAFE_NO_PAD
+        .decode(cursor)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .and_then(|id| id.parse().ok())
+        .ok_or_else(|| ListAppsError::InvalidCursor(cursor.to_string()))
+}
+
+/// Criteria an app must meet to be listed, every criteria that is set has to match
+#[derive(Debug, Clone, Default)]
+pub struct AppsFilter {
+    pub state: Option<AppState>,
+    pub category: Option<AppCategory>,
+    pub is_custom: Option<bool>,
+    pub is_hidden: Option<bool>,
+    /// Every tag has to be present on the app
+    pub tags: Vec<String>,
+    pub primary_owner: Option<String>,
+    pub creation_time_from: Option<DateTime<Utc>>,
+    pub creation_time_to: Option<DateTime<Utc>>,
+    pub last_updated_at_from: Option<DateTime<Utc>>,
+    pub last_updated_at_to: Option<DateTime<Utc>>,
+    /// Custom field keys and the value each has to hold
+    pub custom_fields: Vec<(String, String)>,
+}
+
+impl AppsFilter {
+    pub fn matches(&self, app: &App) -> bool {
+        fn within(
+            time: &DateTime<Utc>,
+            from: &Option<DateTime<Utc>>,
+            to: &Option<DateTime<Utc>>,
+        ) -> bool {
+            from.is_none_or(|from| *time >= from) && to.is_none_or(|to| *time <= to)
+        }
+
+        self.state.as_ref().is_none_or(|state| app.state == *state)
+            && self
+                .category
+                .as_ref()
+                .is_none_or(|category| app.category == *category)
+            && self
+                .is_custom
+                .is_none_or(|is_custom| app.is_custom == is_custom)
+            && self
+                .is_hidden
+                .is_none_or(|is_hidden| app.is_hidden == is_hidden)
+            && self
+                .primary_owner
+                .as_ref()
+                .is_none_or(|owner| app.primary_owner == *owner)
+            && self.tags.iter().all(|tag| app.has_tag(tag))
+            && self.custom_fields.iter().all(|(key, expected)| {
+                app.fields
+                    .get(key)
+                    .is_some_and(|value| value_matches(value, expected))
+            })
+            && within(
+                &app.creation_time,
+                &self.creation_time_from,
+                &self.creation_time_to,
+            )
+            && within(
+                &app.last_updated_at,
+                &self.last_updated_at_from,
+                &self.last_updated_at_to,
+            )
+    }
+}
+
+/// Scan filter built from an `AppsFilter`. Tags match regardless of case, which `contains` can't
+/// do, so they are left out and dates are only narrowed down roughly. Results still need to be
+/// checked with `AppsFilter::matches`
+#[derive(Debug, Default)]
+struct DynamoFilterExpression {
+    conditions: Vec<String>,
+    names: HashMap<String, String>,
+    values: HashMap<String, AttributeValue>,
+}
+
+impl DynamoFilterExpression {
+    fn new(filter: &AppsFilter, tenant: &Tenant) -> Result<Self, ListAppsError> {
+        let mut expression = Self::default();
+
+        // Counters, other entities such as users and other tenants share the table
+        expression.add("entity_type", "=", AttributeValue::S("app".to_string()));
+        expression
+            .conditions
+            .push(tenant_condition(tenant).to_string());
+        expression
+            .values
+            .insert(TENANT_PLACEHOLDER.to_string(), tenant_value(tenant));
+
+        if let Some(state) = &filter.state {
+            expression.add("state", "=", to_attribute_value(state)?);
+        }
+
+        if let Some(category) = &filter.category {
+            expression.add("category", "=", to_attribute_value(category)?);
+        }
+
+        if let Some(is_custom) = filter.is_custom {
+            expression.add("isCustom", "=", AttributeValue::Bool(is_custom));
+        }
+
+        if let Some(is_hidden) = filter.is_hidden {
+            expression.add("isHidden", "=", AttributeValue::Bool(is_hidden));
+        }
+
+        if let Some(primary_owner) = &filter.primary_owner {
+            expression.add(
+                "primaryOwner",
+                "=",
+                AttributeValue::S(primary_owner.clone()),
+            );
+        }
+
+        expression.add_range(
+            "creationTime",
+            &filter.creation_time_from,
+            &filter.creation_time_to,
+        );
+        expression.add_range(
+            "lastUpdatedAt",
+            &filter.last_updated_at_from,
+            &filter.last_updated_at_to,
+        );
+
+        Ok(expression)
+    }
+
+    fn add(&mut self, attribute: &str, operator: &str, value: AttributeValue) {
+        let name = format!("#{attribute}");
+        let placeholder = format!(":v{}", self.values.len());
+
+        self.conditions
+            .push(format!("{name} {operator} {placeholder}"));
+        self.names.insert(name, attribute.to_string());
+        self.values.insert(placeholder, value);
+    }
+
+    /// Timestamps are stored as RFC 3339 strings with a variable number of fractional digits,
+    /// which only sort correctly down to the second. Bounds are widened to whole seconds and
+    /// the exact comparison is left to `AppsFilter::matches`
+    fn add_range(
+        &mut self,
+        attribute: &str,
+        from: &Option<DateTime<Utc>>,
+        to: &Option<DateTime<Utc>>,
+    ) {
+        let whole_second = |time: DateTime<Utc>| {
+            time.duration_trunc(Duration::seconds(1))
+                .unwrap_or(time)
+                .format("%Y-%m-%dT%H:%M:%S")
+                .to_string()
+        };
+
+        if let Some(from) = from {
+            self.add(attribute, ">=", AttributeValue::S(whole_second(*from)));
+        }
+
+        if let Some(to) = to {
+            self.add(
+                attribute,
+                "<",
+                AttributeValue::S(whole_second(*to + Duration::seconds(1))),
+            );
+        }
+    }
+
+    fn expression(&self) -> String {
+        self.conditions.join(" AND ")
+    }
 }
 
+/// First ID handed out to custom apps, known apps of the catalog stay below it
+const COUNTER_START: u16 = 1100;
+
+/// Apps are keyed by ID, prefixed with the tenant for every tenant but the default one
 #[derive(Debug, Clone)]
 pub struct DynamoAppsRepository {
     pub dynamo_client: aws_sdk_dynamodb::Client,
     pub table_name: String,
+    pub tenant: Tenant,
 }
 
 impl DynamoAppsRepository {
@@ -35,6 +295,7 @@ impl DynamoAppsRepository {
         Self {
             dynamo_client,
             table_name,
+            tenant: Tenant::default(),
         }
     }
 
@@ -54,11 +315,9 @@ impl DynamoAppsRepository {
         if result.item.is_none() {
             info!("Atomic counter was not found, creating...");
 
-            let start_count = 1100;
-
             let new_counter = to_item(DynamoCounter {
                 pk: "atomic_counter".to_string(),
-                count: start_count,
+                count: COUNTER_START,
             })
             .expect("Creation of atomic counter should not fail");
 
@@ -84,9 +343,9 @@ pub struct AppDynamoItem {
 
 impl AppDynamoItem {
     #[instrument]
-    pub fn new(app: &App) -> Self {
+    pub fn new(tenant: &Tenant, app: &App) -> Self {
         Self {
-            pk: app.id.to_string(),
+            pk: tenant_pk(tenant, &app.id.to_string()),
             entity_type: "app".to_string(),
             app: app.clone(),
         }
@@ -99,6 +358,15 @@ pub struct DynamoCounter {
     count: u16,
 }
 
+impl TenantScoped for DynamoAppsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+}
+
 #[async_trait]
 impl AppsRepository for DynamoAppsRepository {
     #[instrument(skip(self))]
@@ -107,7 +375,10 @@ impl AppsRepository for DynamoAppsRepository {
             .dynamo_client
             .get_item()
             .table_name(&self.table_name)
-            .key("pk", AttributeValue::S(id.to_string()))
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &id.to_string())),
+            )
             .send()
             .await
             .map_err(|e| {
@@ -139,8 +410,10 @@ impl AppsRepository for DynamoAppsRepository {
             .table_name(&self.table_name)
             .index_name("name_index")
             .key_condition_expression("#name = :name_val")
+            .filter_expression(tenant_condition(&self.tenant))
             .expression_attribute_names("#name", "name")
             .expression_attribute_values(":name_val", AttributeValue::S(name.to_string()))
+            .expression_attribute_values(TENANT_PLACEHOLDER, tenant_value(&self.tenant))
             .send()
             .await
             .unwrap();
@@ -160,7 +433,7 @@ impl AppsRepository for DynamoAppsRepository {
     #[instrument(skip(self))]
     /// add_app is intended for adding "pre-existing" applications defined by the service
     async fn add_app(&self, app: App) -> Result<App, AddAppError> {
-        let item = to_item(AppDynamoItem::new(&app))?;
+        let item = to_item(AppDynamoItem::new(&self.tenant, &app))?;
 
         info!("{:?}", item);
 
@@ -193,7 +466,7 @@ impl AppsRepository for DynamoAppsRepository {
 
     #[instrument(skip(self))]
     async fn create_app(&self, app: App) -> Result<App, CreateAppError> {
-        let item = to_item(AppDynamoItem::new(&app))?;
+        let item = to_item(AppDynamoItem::new(&self.tenant, &app))?;
 
         let _result = self
             .dynamo_client
@@ -228,7 +501,10 @@ impl AppsRepository for DynamoAppsRepository {
             .dynamo_client
             .delete_item()
             .table_name(&self.table_name)
-            .key("pk", AttributeValue::S(id.to_string()))
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &id.to_string())),
+            )
             .condition_expression("attribute_exists(pk)")
             .send()
             .await
@@ -249,45 +525,110 @@ impl AppsRepository for DynamoAppsRepository {
         Ok(())
     }
 
+    /// The cursor holds the ID of the last app on the previous page, scanning resumes after that
+    /// app's key in this tenant. DynamoDB applies `Limit` before the filter expression and
+    /// counts every item type, so instead the scan keeps going until `size` apps are found or
+    /// the table runs out
     #[instrument(skip(self))]
-    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError> {
-        let result = self
-            .dynamo_client
-            .scan()
-            .table_name(&self.table_name)
-            .filter_expression("pk <> :pk")
-            .expression_attribute_values(":pk", AttributeValue::S("atomic_counter".to_string()))
-            .send()
-            .await
-            .map_err(|e| {
-                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
-                ListAppsError::UnexpectedError
-            })?;
+    async fn list_apps_page(
+        &self,
+        size: Option<u16>,
+        cursor: Option<String>,
+        filter: &AppsFilter,
+    ) -> Result<AppsPage, ListAppsError> {
+        let mut exclusive_start_key = match cursor {
+            Some(cursor) => {
+                let id = decode_cursor(&cursor)?;
+
+                Some(HashMap::from([(
+                    "pk".to_string(),
+                    AttributeValue::S(tenant_pk(&self.tenant, &id.to_string())),
+                )]))
+            }
+            None => None,
+        };
+        let size = size.map(usize::from);
+        let mut apps = Vec::new();
+        let mut more = false;
+
+        loop {
+            let filter_expression = DynamoFilterExpression::new(filter, &self.tenant)?;
+
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression(filter_expression.expression())
+                .set_expression_attribute_names(Some(filter_expression.names))
+                .set_expression_attribute_values(Some(filter_expression.values))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListAppsError::UnexpectedError
+                })?;
 
-        if let Some(items) = result.items {
-            let apps: Vec<App> = from_items(items)?;
+            let scanned: Vec<App> = if let Some(items) = result.items {
+                from_items(items)?
+            } else {
+                Vec::new()
+            };
 
-            Ok(apps)
-        } else {
-            Ok(Vec::new())
+            for app in scanned.into_iter().filter(|app| filter.matches(app)) {
+                if size.is_some_and(|size| apps.len() == size) {
+                    more = true;
+                    break;
+                }
+
+                apps.push(app);
+            }
+
+            exclusive_start_key = result.last_evaluated_key;
+
+            if more || exclusive_start_key.is_none() {
+                break;
+            }
         }
+
+        let next_cursor = more
+            .then(|| apps.last().map(|app| encode_cursor(app.id)))
+            .flatten();
+
+        Ok(AppsPage { apps, next_cursor })
     }
 
+    /// The stored item comes back when the condition fails, without one the app is gone rather
+    /// than at another version
     #[instrument(skip(self))]
-    async fn update_app(&self, app: App) -> Result<App, UpdateAppError> {
-        let item = to_item(AppDynamoItem::new(&app))?;
+    async fn update_app(&self, mut app: App) -> Result<App, UpdateAppError> {
+        let expected_version = app.version;
+        app.version += 1;
+
+        let item = to_item(AppDynamoItem::new(&self.tenant, &app))?;
 
         let _result = self
             .dynamo_client
             .put_item()
             .table_name(&self.table_name)
             .set_item(Some(item))
+            .condition_expression(version_condition(expected_version))
+            .expression_attribute_names("#version", "version")
+            .expression_attribute_values(":version", to_attribute_value(expected_version)?)
+            .return_values_on_condition_check_failure(ReturnValuesOnConditionCheckFailure::AllOld)
             .send()
             .await
             .map_err(|e| {
                 let err = e.into_service_error();
 
                 match err {
+                    PutItemError::ConditionalCheckFailedException(failed) => {
+                        if failed.item().is_some() {
+                            UpdateAppError::VersionConflict(app.id)
+                        } else {
+                            UpdateAppError::ResourceNotFound(app.id)
+                        }
+                    }
                     _ => {
                         tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
                         UpdateAppError::UnexpectedError
@@ -298,19 +639,122 @@ impl AppsRepository for DynamoAppsRepository {
         Ok(app)
     }
 
+    /// Uses a single `TransactWriteItems` call, which DynamoDB limits to 100 writes that each
+    /// touch a different app
+    #[instrument(skip(self))]
+    async fn write_apps(&self, writes: Vec<AppWrite>) -> Result<Vec<Option<App>>, WriteAppsError> {
+        let mut items = Vec::with_capacity(writes.len());
+        let mut written = Vec::with_capacity(writes.len());
+
+        for write in writes {
+            let item = match write {
+                AppWrite::Put(app) => {
+                    let put = Put::builder()
+                        .table_name(&self.table_name)
+                        .set_item(Some(to_item(AppDynamoItem::new(&self.tenant, &app))?))
+                        .condition_expression("attribute_not_exists(pk)")
+                        .build()
+                        .map_err(|e| {
+                            tracing::error!("DynamoDB SDK Error: {}", e);
+                            WriteAppsError::UnexpectedError
+                        })?;
+
+                    written.push(Some(app));
+                    TransactWriteItem::builder().put(put).build()
+                }
+                AppWrite::Update(mut app) => {
+                    let expected_version = app.version;
+                    app.version += 1;
+
+                    let put = Put::builder()
+                        .table_name(&self.table_name)
+                        .set_item(Some(to_item(AppDynamoItem::new(&self.tenant, &app))?))
+                        .condition_expression(version_condition(expected_version))
+                        .expression_attribute_names("#version", "version")
+                        .expression_attribute_values(
+                            ":version",
+                            to_attribute_value(expected_version)?,
+                        )
+                        .build()
+                        .map_err(|e| {
+                            tracing::error!("DynamoDB SDK Error: {}", e);
+                            WriteAppsError::UnexpectedError
+                        })?;
+
+                    written.push(Some(app));
+                    TransactWriteItem::builder().put(put).build()
+                }
+                AppWrite::Delete(id) => {
+                    let delete = Delete::builder()
+                        .table_name(&self.table_name)
+                        .key(
+                            "pk",
+                            AttributeValue::S(tenant_pk(&self.tenant, &id.to_string())),
+                        )
+                        .condition_expression("attribute_exists(pk)")
+                        .build()
+                        .map_err(|e| {
+                            tracing::error!("DynamoDB SDK Error: {}", e);
+                            WriteAppsError::UnexpectedError
+                        })?;
+
+                    written.push(None);
+                    TransactWriteItem::builder().delete(delete).build()
+                }
+            };
+
+            items.push(item);
+        }
+
+        let _result = self
+            .dynamo_client
+            .transact_write_items()
+            .set_transact_items(Some(items))
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                // Reasons are listed in the same order as the writes, with "None" for the ones
+                // that would have succeeded
+                let failed = match &err {
+                    TransactWriteItemsError::TransactionCanceledException(e) => e
+                        .cancellation_reasons()
+                        .iter()
+                        .position(|reason| reason.code() == Some("ConditionalCheckFailed")),
+                    _ => None,
+                };
+
+                match failed {
+                    Some(index) => WriteAppsError::ConditionFailed(index),
+                    None => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        WriteAppsError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(written)
+    }
+
     /// Atomic counter will be set to a number that should be above the staticly configured
     /// apps in the apps_service.rs. This is a manually specified number, and is not intended
     /// to be able to handle any situations beyond that. If you are shenaniganizing, this can
-    /// blow up, and you deserve it
+    /// blow up, and you deserve it. Tenants other than the default one get their counter on
+    /// first use
     #[instrument(skip(self))]
     async fn get_id(&self) -> Result<u16, ()> {
         let result = self
             .dynamo_client
             .update_item()
             .table_name(&self.table_name)
-            .key("pk", AttributeValue::S("atomic_counter".to_string()))
-            .update_expression("SET #cnt = #cnt + :inc")
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, "atomic_counter")),
+            )
+            .update_expression("SET #cnt = if_not_exists(#cnt, :start) + :inc")
             .expression_attribute_names("#cnt", "count")
+            .expression_attribute_values(":start", AttributeValue::N(COUNTER_START.to_string()))
             .expression_attribute_values(":inc", AttributeValue::N("1".to_string()))
             .return_values(ReturnValue::AllNew)
             .send()
@@ -326,7 +770,67 @@ impl AppsRepository for DynamoAppsRepository {
     }
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct AddAppRequest {
-    pub id_app: u16,
+/// Items written before versioning have no version attribute and count as version 0
+fn version_condition(expected_version: u64) -> &'static str {
+    if expected_version == 0 {
+        "attribute_exists(pk) AND (attribute_not_exists(#version) OR #version = :version)"
+    } else {
+        "attribute_exists(pk) AND #version = :version"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::InMemoryAppsRepository;
+
+    /// Updates at a stale version conflict, and once the app is deleted they find nothing
+    async fn update_conflicts_then_finds_nothing(repo: impl AppsRepository, id: u16) {
+        let _ = repo.delete_app(id).await;
+
+        let app = repo
+            .create_app(App {
+                id,
+                name: format!("App {id}"),
+                version: 1,
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        let updated = repo.update_app(app.clone()).await.unwrap();
+
+        assert_eq!(updated.version, 2);
+        assert!(matches!(
+            repo.update_app(app.clone()).await,
+            Err(UpdateAppError::VersionConflict(conflict)) if conflict == id
+        ));
+
+        repo.delete_app(id).await.unwrap();
+
+        assert!(matches!(
+            repo.update_app(updated).await,
+            Err(UpdateAppError::ResourceNotFound(missing)) if missing == id
+        ));
+    }
+
+    #[tokio::test]
+    async fn in_memory_update_of_a_deleted_app_is_not_found() {
+        update_conflicts_then_finds_nothing(InMemoryAppsRepository::new(), 1).await;
+    }
+
+    #[tokio::test]
+    #[ignore = "needs DynamoDB Local from dynamo/docker-compose.yml"]
+    async fn dynamo_update_of_a_deleted_app_is_not_found() {
+        let config = aws_config::defaults(aws_config::BehaviorVersion::latest())
+            .region("us-east-1")
+            .endpoint_url("http://127.0.0.1:8001")
+            .load()
+            .await;
+        let repo = DynamoAppsRepository::new(
+            aws_sdk_dynamodb::Client::new(&config),
+            "torii-table".to_string(),
+        );
+
+        update_conflicts_then_finds_nothing(repo, 65000).await;
+    }
 }
@@ -0,0 +1,174 @@
+use super::tenancy::{tenant_condition, tenant_pk, tenant_value, TENANT_PLACEHOLDER};
+use super::{AppendAuditEventError, AuditAction, AuditEvent, ListAuditEventsError};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::types::AttributeValue;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_items, to_attribute_value, to_item};
+use tracing::instrument;
+
+use crate::tenant::{Tenant, TenantScoped};
+
+#[async_trait]
+pub trait AuditRepository: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    async fn append_event(&self, event: AuditEvent) -> Result<(), AppendAuditEventError>;
+    /// Events in no particular order
+    async fn list_events(
+        &self,
+        filter: &AuditFilter,
+    ) -> Result<Vec<AuditEvent>, ListAuditEventsError>;
+}
+
+/// Criteria an event must meet to be listed, every criteria that is set has to match
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub app_id: Option<u16>,
+    pub action: Option<AuditAction>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        self.app_id.is_none_or(|app_id| event.app_id == app_id)
+            && self.action.is_none_or(|action| event.action == action)
+            && self.from.is_none_or(|from| event.timestamp >= from)
+            && self.to.is_none_or(|to| event.timestamp <= to)
+    }
+}
+
+/// Events are append only and keyed as `audit#<id>`, prefixed with the tenant for every tenant
+/// but the default one
+#[derive(Debug, Clone)]
+pub struct DynamoAuditRepository {
+    pub dynamo_client: aws_sdk_dynamodb::Client,
+    pub table_name: String,
+    pub tenant: Tenant,
+}
+
+impl DynamoAuditRepository {
+    #[instrument]
+    pub fn new(dynamo_client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        Self {
+            dynamo_client,
+            table_name,
+            tenant: Tenant::default(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct AuditEventDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    event: AuditEvent,
+}
+
+impl AuditEventDynamoItem {
+    #[instrument]
+    pub fn new(tenant: &Tenant, event: &AuditEvent) -> Self {
+        Self {
+            pk: tenant_pk(tenant, &format!("audit#{}", event.id)),
+            entity_type: "audit_event".to_string(),
+            event: event.clone(),
+        }
+    }
+}
+
+impl TenantScoped for DynamoAuditRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for DynamoAuditRepository {
+    #[instrument(skip(self))]
+    async fn append_event(&self, event: AuditEvent) -> Result<(), AppendAuditEventError> {
+        let item = to_item(AuditEventDynamoItem::new(&self.tenant, &event))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                AppendAuditEventError::UnexpectedError
+            })?;
+
+        Ok(())
+    }
+
+    /// App ID and action are narrowed down in the scan, timestamps are compared afterwards
+    #[instrument(skip(self))]
+    async fn list_events(
+        &self,
+        filter: &AuditFilter,
+    ) -> Result<Vec<AuditEvent>, ListAuditEventsError> {
+        let mut conditions = vec![
+            "entity_type = :entity_type".to_string(),
+            tenant_condition(&self.tenant).to_string(),
+        ];
+        let mut names = HashMap::new();
+        let mut values = HashMap::from([
+            (
+                ":entity_type".to_string(),
+                AttributeValue::S("audit_event".to_string()),
+            ),
+            (TENANT_PLACEHOLDER.to_string(), tenant_value(&self.tenant)),
+        ]);
+
+        if let Some(app_id) = filter.app_id {
+            conditions.push("#app_id = :app_id".to_string());
+            names.insert("#app_id".to_string(), "appId".to_string());
+            values.insert(":app_id".to_string(), to_attribute_value(app_id)?);
+        }
+
+        if let Some(action) = filter.action {
+            conditions.push("#action = :action".to_string());
+            names.insert("#action".to_string(), "action".to_string());
+            values.insert(":action".to_string(), to_attribute_value(action)?);
+        }
+
+        let mut events = Vec::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression(conditions.join(" AND "))
+                .set_expression_attribute_names((!names.is_empty()).then(|| names.clone()))
+                .set_expression_attribute_values(Some(values.clone()))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListAuditEventsError::UnexpectedError
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<AuditEvent> = from_items(items)?;
+                events.extend(page.into_iter().filter(|event| filter.matches(event)));
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(events),
+            }
+        }
+    }
+}
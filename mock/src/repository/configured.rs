@@ -0,0 +1,573 @@
+use std::collections::HashMap;
+
+use super::{
+    AddAppError, ApiKeysRepository, App, AppField, AppFieldsRepository, AppUser,
+    AppUsersRepository, AppWrite, AppendAuditEventError, AppsFilter, AppsPage, AppsRepository,
+    AuditEvent, AuditFilter, AuditRepository, Contract, ContractsRepository, CreateApiKeyError,
+    CreateAppError, CreateAppFieldError, CreateContractError, CreateUserError, CreateWebhookError,
+    DeleteApiKeyError, DeleteAppError, DeleteAppFieldError, DeleteAppUserError,
+    DeleteContractError, DeleteUserError, DeleteWebhookError, DynamoApiKeysRepository,
+    DynamoAppFieldsRepository, DynamoAppUsersRepository, DynamoAppsRepository,
+    DynamoAuditRepository, DynamoContractsRepository, DynamoUsersRepository,
+    DynamoWebhooksRepository, FileApiKeysRepository, FileAppFieldsRepository,
+    FileAppUsersRepository, FileAppsRepository, FileAuditRepository, FileContractsRepository,
+    FileUsersRepository, FileWebhooksRepository, GetApiKeyError, GetAppError, GetAppFieldError,
+    GetContractError, GetUserError, GetWebhookError, InMemoryApiKeysRepository,
+    InMemoryAppFieldsRepository, InMemoryAppUsersRepository, InMemoryAppsRepository,
+    InMemoryAuditRepository, InMemoryContractsRepository, InMemoryUsersRepository,
+    InMemoryWebhooksRepository, ListApiKeysError, ListAppFieldsError, ListAppUsersError,
+    ListAppsError, ListAuditEventsError, ListContractsError, ListUsersError,
+    ListWebhookDeliveriesError, ListWebhooksError, PutAppUserError, PutWebhookDeliveryError,
+    StoredApiKey, UpdateApiKeyError, UpdateAppError, UpdateAppFieldError, UpdateContractError,
+    UpdateUserError, UpdateWebhookError, User, UsersRepository, Webhook, WebhookDelivery,
+    WebhooksRepository, WriteAppsError,
+};
+use crate::tenant::{Tenant, TenantScoped};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+
+/// The repository implementation selected at startup with `REPOSITORY_BACKEND`
+#[derive(Debug, Clone)]
+pub enum ConfiguredAppsRepository {
+    Dynamo(DynamoAppsRepository),
+    InMemory(InMemoryAppsRepository),
+    File(FileAppsRepository),
+}
+
+impl TenantScoped for ConfiguredAppsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        match self {
+            Self::Dynamo(repo) => Self::Dynamo(repo.for_tenant(tenant)),
+            Self::InMemory(repo) => Self::InMemory(repo.for_tenant(tenant)),
+            Self::File(repo) => Self::File(repo.for_tenant(tenant)),
+        }
+    }
+}
+
+#[async_trait]
+impl AppsRepository for ConfiguredAppsRepository {
+    async fn get_app(&self, id: u16) -> Result<App, GetAppError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_app(id).await,
+            Self::InMemory(repo) => repo.get_app(id).await,
+            Self::File(repo) => repo.get_app(id).await,
+        }
+    }
+
+    async fn get_app_by_name(&self, name: &str) -> Result<App, GetAppError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_app_by_name(name).await,
+            Self::InMemory(repo) => repo.get_app_by_name(name).await,
+            Self::File(repo) => repo.get_app_by_name(name).await,
+        }
+    }
+
+    async fn add_app(&self, app: App) -> Result<App, AddAppError> {
+        match self {
+            Self::Dynamo(repo) => repo.add_app(app).await,
+            Self::InMemory(repo) => repo.add_app(app).await,
+            Self::File(repo) => repo.add_app(app).await,
+        }
+    }
+
+    async fn create_app(&self, app: App) -> Result<App, CreateAppError> {
+        match self {
+            Self::Dynamo(repo) => repo.create_app(app).await,
+            Self::InMemory(repo) => repo.create_app(app).await,
+            Self::File(repo) => repo.create_app(app).await,
+        }
+    }
+
+    async fn delete_app(&self, id: u16) -> Result<(), DeleteAppError> {
+        match self {
+            Self::Dynamo(repo) => repo.delete_app(id).await,
+            Self::InMemory(repo) => repo.delete_app(id).await,
+            Self::File(repo) => repo.delete_app(id).await,
+        }
+    }
+
+    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_apps().await,
+            Self::InMemory(repo) => repo.list_apps().await,
+            Self::File(repo) => repo.list_apps().await,
+        }
+    }
+
+    async fn list_apps_page(
+        &self,
+        size: Option<u16>,
+        cursor: Option<String>,
+        filter: &AppsFilter,
+    ) -> Result<AppsPage, ListAppsError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_apps_page(size, cursor, filter).await,
+            Self::InMemory(repo) => repo.list_apps_page(size, cursor, filter).await,
+            Self::File(repo) => repo.list_apps_page(size, cursor, filter).await,
+        }
+    }
+
+    async fn update_app(&self, app: App) -> Result<App, UpdateAppError> {
+        match self {
+            Self::Dynamo(repo) => repo.update_app(app).await,
+            Self::InMemory(repo) => repo.update_app(app).await,
+            Self::File(repo) => repo.update_app(app).await,
+        }
+    }
+
+    async fn write_apps(&self, writes: Vec<AppWrite>) -> Result<Vec<Option<App>>, WriteAppsError> {
+        match self {
+            Self::Dynamo(repo) => repo.write_apps(writes).await,
+            Self::InMemory(repo) => repo.write_apps(writes).await,
+            Self::File(repo) => repo.write_apps(writes).await,
+        }
+    }
+
+    async fn get_id(&self) -> Result<u16, ()> {
+        match self {
+            Self::Dynamo(repo) => repo.get_id().await,
+            Self::InMemory(repo) => repo.get_id().await,
+            Self::File(repo) => repo.get_id().await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfiguredUsersRepository {
+    Dynamo(DynamoUsersRepository),
+    InMemory(InMemoryUsersRepository),
+    File(FileUsersRepository),
+}
+
+impl TenantScoped for ConfiguredUsersRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        match self {
+            Self::Dynamo(repo) => Self::Dynamo(repo.for_tenant(tenant)),
+            Self::InMemory(repo) => Self::InMemory(repo.for_tenant(tenant)),
+            Self::File(repo) => Self::File(repo.for_tenant(tenant)),
+        }
+    }
+}
+
+#[async_trait]
+impl UsersRepository for ConfiguredUsersRepository {
+    async fn get_user(&self, id: u32) -> Result<User, GetUserError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_user(id).await,
+            Self::InMemory(repo) => repo.get_user(id).await,
+            Self::File(repo) => repo.get_user(id).await,
+        }
+    }
+
+    async fn get_user_by_email(&self, email: &str) -> Result<User, GetUserError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_user_by_email(email).await,
+            Self::InMemory(repo) => repo.get_user_by_email(email).await,
+            Self::File(repo) => repo.get_user_by_email(email).await,
+        }
+    }
+
+    async fn create_user(&self, user: User) -> Result<User, CreateUserError> {
+        match self {
+            Self::Dynamo(repo) => repo.create_user(user).await,
+            Self::InMemory(repo) => repo.create_user(user).await,
+            Self::File(repo) => repo.create_user(user).await,
+        }
+    }
+
+    async fn delete_user(&self, id: u32) -> Result<(), DeleteUserError> {
+        match self {
+            Self::Dynamo(repo) => repo.delete_user(id).await,
+            Self::InMemory(repo) => repo.delete_user(id).await,
+            Self::File(repo) => repo.delete_user(id).await,
+        }
+    }
+
+    async fn list_users(&self) -> Result<Vec<User>, ListUsersError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_users().await,
+            Self::InMemory(repo) => repo.list_users().await,
+            Self::File(repo) => repo.list_users().await,
+        }
+    }
+
+    async fn update_user(&self, user: User) -> Result<User, UpdateUserError> {
+        match self {
+            Self::Dynamo(repo) => repo.update_user(user).await,
+            Self::InMemory(repo) => repo.update_user(user).await,
+            Self::File(repo) => repo.update_user(user).await,
+        }
+    }
+
+    async fn get_user_id(&self) -> Result<u32, ()> {
+        match self {
+            Self::Dynamo(repo) => repo.get_user_id().await,
+            Self::InMemory(repo) => repo.get_user_id().await,
+            Self::File(repo) => repo.get_user_id().await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfiguredAppUsersRepository {
+    Dynamo(DynamoAppUsersRepository),
+    InMemory(InMemoryAppUsersRepository),
+    File(FileAppUsersRepository),
+}
+
+impl TenantScoped for ConfiguredAppUsersRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        match self {
+            Self::Dynamo(repo) => Self::Dynamo(repo.for_tenant(tenant)),
+            Self::InMemory(repo) => Self::InMemory(repo.for_tenant(tenant)),
+            Self::File(repo) => Self::File(repo.for_tenant(tenant)),
+        }
+    }
+}
+
+#[async_trait]
+impl AppUsersRepository for ConfiguredAppUsersRepository {
+    async fn put_app_user(&self, app_user: AppUser) -> Result<AppUser, PutAppUserError> {
+        match self {
+            Self::Dynamo(repo) => repo.put_app_user(app_user).await,
+            Self::InMemory(repo) => repo.put_app_user(app_user).await,
+            Self::File(repo) => repo.put_app_user(app_user).await,
+        }
+    }
+
+    async fn delete_app_user(&self, app_id: u16, user_id: u32) -> Result<(), DeleteAppUserError> {
+        match self {
+            Self::Dynamo(repo) => repo.delete_app_user(app_id, user_id).await,
+            Self::InMemory(repo) => repo.delete_app_user(app_id, user_id).await,
+            Self::File(repo) => repo.delete_app_user(app_id, user_id).await,
+        }
+    }
+
+    async fn list_app_users(&self, app_id: u16) -> Result<Vec<AppUser>, ListAppUsersError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_app_users(app_id).await,
+            Self::InMemory(repo) => repo.list_app_users(app_id).await,
+            Self::File(repo) => repo.list_app_users(app_id).await,
+        }
+    }
+
+    async fn list_user_apps(&self, user_id: u32) -> Result<Vec<AppUser>, ListAppUsersError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_user_apps(user_id).await,
+            Self::InMemory(repo) => repo.list_user_apps(user_id).await,
+            Self::File(repo) => repo.list_user_apps(user_id).await,
+        }
+    }
+
+    async fn count_active_users(&self) -> Result<HashMap<u16, u32>, ListAppUsersError> {
+        match self {
+            Self::Dynamo(repo) => repo.count_active_users().await,
+            Self::InMemory(repo) => repo.count_active_users().await,
+            Self::File(repo) => repo.count_active_users().await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfiguredContractsRepository {
+    Dynamo(DynamoContractsRepository),
+    InMemory(InMemoryContractsRepository),
+    File(FileContractsRepository),
+}
+
+impl TenantScoped for ConfiguredContractsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        match self {
+            Self::Dynamo(repo) => Self::Dynamo(repo.for_tenant(tenant)),
+            Self::InMemory(repo) => Self::InMemory(repo.for_tenant(tenant)),
+            Self::File(repo) => Self::File(repo.for_tenant(tenant)),
+        }
+    }
+}
+
+#[async_trait]
+impl ContractsRepository for ConfiguredContractsRepository {
+    async fn get_contract(&self, id: u32) -> Result<Contract, GetContractError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_contract(id).await,
+            Self::InMemory(repo) => repo.get_contract(id).await,
+            Self::File(repo) => repo.get_contract(id).await,
+        }
+    }
+
+    async fn create_contract(&self, contract: Contract) -> Result<Contract, CreateContractError> {
+        match self {
+            Self::Dynamo(repo) => repo.create_contract(contract).await,
+            Self::InMemory(repo) => repo.create_contract(contract).await,
+            Self::File(repo) => repo.create_contract(contract).await,
+        }
+    }
+
+    async fn delete_contract(&self, id: u32) -> Result<(), DeleteContractError> {
+        match self {
+            Self::Dynamo(repo) => repo.delete_contract(id).await,
+            Self::InMemory(repo) => repo.delete_contract(id).await,
+            Self::File(repo) => repo.delete_contract(id).await,
+        }
+    }
+
+    async fn list_contracts(&self) -> Result<Vec<Contract>, ListContractsError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_contracts().await,
+            Self::InMemory(repo) => repo.list_contracts().await,
+            Self::File(repo) => repo.list_contracts().await,
+        }
+    }
+
+    async fn update_contract(&self, contract: Contract) -> Result<Contract, UpdateContractError> {
+        match self {
+            Self::Dynamo(repo) => repo.update_contract(contract).await,
+            Self::InMemory(repo) => repo.update_contract(contract).await,
+            Self::File(repo) => repo.update_contract(contract).await,
+        }
+    }
+
+    async fn get_contract_id(&self) -> Result<u32, ()> {
+        match self {
+            Self::Dynamo(repo) => repo.get_contract_id().await,
+            Self::InMemory(repo) => repo.get_contract_id().await,
+            Self::File(repo) => repo.get_contract_id().await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfiguredAppFieldsRepository {
+    Dynamo(DynamoAppFieldsRepository),
+    InMemory(InMemoryAppFieldsRepository),
+    File(FileAppFieldsRepository),
+}
+
+impl TenantScoped for ConfiguredAppFieldsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        match self {
+            Self::Dynamo(repo) => Self::Dynamo(repo.for_tenant(tenant)),
+            Self::InMemory(repo) => Self::InMemory(repo.for_tenant(tenant)),
+            Self::File(repo) => Self::File(repo.for_tenant(tenant)),
+        }
+    }
+}
+
+#[async_trait]
+impl AppFieldsRepository for ConfiguredAppFieldsRepository {
+    async fn get_app_field(&self, key: &str) -> Result<AppField, GetAppFieldError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_app_field(key).await,
+            Self::InMemory(repo) => repo.get_app_field(key).await,
+            Self::File(repo) => repo.get_app_field(key).await,
+        }
+    }
+
+    async fn create_app_field(&self, field: AppField) -> Result<AppField, CreateAppFieldError> {
+        match self {
+            Self::Dynamo(repo) => repo.create_app_field(field).await,
+            Self::InMemory(repo) => repo.create_app_field(field).await,
+            Self::File(repo) => repo.create_app_field(field).await,
+        }
+    }
+
+    async fn delete_app_field(&self, key: &str) -> Result<(), DeleteAppFieldError> {
+        match self {
+            Self::Dynamo(repo) => repo.delete_app_field(key).await,
+            Self::InMemory(repo) => repo.delete_app_field(key).await,
+            Self::File(repo) => repo.delete_app_field(key).await,
+        }
+    }
+
+    async fn list_app_fields(&self) -> Result<Vec<AppField>, ListAppFieldsError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_app_fields().await,
+            Self::InMemory(repo) => repo.list_app_fields().await,
+            Self::File(repo) => repo.list_app_fields().await,
+        }
+    }
+
+    async fn update_app_field(&self, field: AppField) -> Result<AppField, UpdateAppFieldError> {
+        match self {
+            Self::Dynamo(repo) => repo.update_app_field(field).await,
+            Self::InMemory(repo) => repo.update_app_field(field).await,
+            Self::File(repo) => repo.update_app_field(field).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfiguredAuditRepository {
+    Dynamo(DynamoAuditRepository),
+    InMemory(InMemoryAuditRepository),
+    File(FileAuditRepository),
+}
+
+impl TenantScoped for ConfiguredAuditRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        match self {
+            Self::Dynamo(repo) => Self::Dynamo(repo.for_tenant(tenant)),
+            Self::InMemory(repo) => Self::InMemory(repo.for_tenant(tenant)),
+            Self::File(repo) => Self::File(repo.for_tenant(tenant)),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for ConfiguredAuditRepository {
+    async fn append_event(&self, event: AuditEvent) -> Result<(), AppendAuditEventError> {
+        match self {
+            Self::Dynamo(repo) => repo.append_event(event).await,
+            Self::InMemory(repo) => repo.append_event(event).await,
+            Self::File(repo) => repo.append_event(event).await,
+        }
+    }
+
+    async fn list_events(
+        &self,
+        filter: &AuditFilter,
+    ) -> Result<Vec<AuditEvent>, ListAuditEventsError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_events(filter).await,
+            Self::InMemory(repo) => repo.list_events(filter).await,
+            Self::File(repo) => repo.list_events(filter).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfiguredWebhooksRepository {
+    Dynamo(DynamoWebhooksRepository),
+    InMemory(InMemoryWebhooksRepository),
+    File(FileWebhooksRepository),
+}
+
+impl TenantScoped for ConfiguredWebhooksRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        match self {
+            Self::Dynamo(repo) => Self::Dynamo(repo.for_tenant(tenant)),
+            Self::InMemory(repo) => Self::InMemory(repo.for_tenant(tenant)),
+            Self::File(repo) => Self::File(repo.for_tenant(tenant)),
+        }
+    }
+}
+
+#[async_trait]
+impl WebhooksRepository for ConfiguredWebhooksRepository {
+    async fn get_webhook(&self, id: &str) -> Result<Webhook, GetWebhookError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_webhook(id).await,
+            Self::InMemory(repo) => repo.get_webhook(id).await,
+            Self::File(repo) => repo.get_webhook(id).await,
+        }
+    }
+
+    async fn create_webhook(&self, webhook: Webhook) -> Result<Webhook, CreateWebhookError> {
+        match self {
+            Self::Dynamo(repo) => repo.create_webhook(webhook).await,
+            Self::InMemory(repo) => repo.create_webhook(webhook).await,
+            Self::File(repo) => repo.create_webhook(webhook).await,
+        }
+    }
+
+    async fn delete_webhook(&self, id: &str) -> Result<(), DeleteWebhookError> {
+        match self {
+            Self::Dynamo(repo) => repo.delete_webhook(id).await,
+            Self::InMemory(repo) => repo.delete_webhook(id).await,
+            Self::File(repo) => repo.delete_webhook(id).await,
+        }
+    }
+
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>, ListWebhooksError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_webhooks().await,
+            Self::InMemory(repo) => repo.list_webhooks().await,
+            Self::File(repo) => repo.list_webhooks().await,
+        }
+    }
+
+    async fn update_webhook(&self, webhook: Webhook) -> Result<Webhook, UpdateWebhookError> {
+        match self {
+            Self::Dynamo(repo) => repo.update_webhook(webhook).await,
+            Self::InMemory(repo) => repo.update_webhook(webhook).await,
+            Self::File(repo) => repo.update_webhook(webhook).await,
+        }
+    }
+
+    async fn put_delivery(&self, delivery: WebhookDelivery) -> Result<(), PutWebhookDeliveryError> {
+        match self {
+            Self::Dynamo(repo) => repo.put_delivery(delivery).await,
+            Self::InMemory(repo) => repo.put_delivery(delivery).await,
+            Self::File(repo) => repo.put_delivery(delivery).await,
+        }
+    }
+
+    async fn list_deliveries(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, ListWebhookDeliveriesError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_deliveries(webhook_id).await,
+            Self::InMemory(repo) => repo.list_deliveries(webhook_id).await,
+            Self::File(repo) => repo.list_deliveries(webhook_id).await,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum ConfiguredApiKeysRepository {
+    Dynamo(DynamoApiKeysRepository),
+    InMemory(InMemoryApiKeysRepository),
+    File(FileApiKeysRepository),
+}
+
+#[async_trait]
+impl ApiKeysRepository for ConfiguredApiKeysRepository {
+    async fn get_api_key(&self, id: &str) -> Result<StoredApiKey, GetApiKeyError> {
+        match self {
+            Self::Dynamo(repo) => repo.get_api_key(id).await,
+            Self::InMemory(repo) => repo.get_api_key(id).await,
+            Self::File(repo) => repo.get_api_key(id).await,
+        }
+    }
+
+    async fn create_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, CreateApiKeyError> {
+        match self {
+            Self::Dynamo(repo) => repo.create_api_key(key).await,
+            Self::InMemory(repo) => repo.create_api_key(key).await,
+            Self::File(repo) => repo.create_api_key(key).await,
+        }
+    }
+
+    async fn update_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, UpdateApiKeyError> {
+        match self {
+            Self::Dynamo(repo) => repo.update_api_key(key).await,
+            Self::InMemory(repo) => repo.update_api_key(key).await,
+            Self::File(repo) => repo.update_api_key(key).await,
+        }
+    }
+
+    async fn delete_api_key(&self, id: &str) -> Result<(), DeleteApiKeyError> {
+        match self {
+            Self::Dynamo(repo) => repo.delete_api_key(id).await,
+            Self::InMemory(repo) => repo.delete_api_key(id).await,
+            Self::File(repo) => repo.delete_api_key(id).await,
+        }
+    }
+
+    async fn list_api_keys(&self, tenant: &Tenant) -> Result<Vec<StoredApiKey>, ListApiKeysError> {
+        match self {
+            Self::Dynamo(repo) => repo.list_api_keys(tenant).await,
+            Self::InMemory(repo) => repo.list_api_keys(tenant).await,
+            Self::File(repo) => repo.list_api_keys(tenant).await,
+        }
+    }
+
+    async fn record_use(&self, id: &str, at: DateTime<Utc>) -> Result<(), UpdateApiKeyError> {
+        match self {
+            Self::Dynamo(repo) => repo.record_use(id, at).await,
+            Self::InMemory(repo) => repo.record_use(id, at).await,
+            Self::File(repo) => repo.record_use(id, at).await,
+        }
+    }
+}
@@ -0,0 +1,287 @@
+use super::tenancy::{tenant_condition, tenant_pk, tenant_value, TENANT_PLACEHOLDER};
+use super::{
+    Contract, CreateContractError, DeleteContractError, GetContractError, ListContractsError,
+    UpdateContractError,
+};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_item, from_items, to_item};
+use tracing::{info, instrument};
+
+use crate::tenant::{Tenant, TenantScoped};
+
+#[async_trait]
+pub trait ContractsRepository: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    async fn get_contract(&self, id: u32) -> Result<Contract, GetContractError>;
+    async fn create_contract(&self, contract: Contract) -> Result<Contract, CreateContractError>;
+    async fn delete_contract(&self, id: u32) -> Result<(), DeleteContractError>;
+    async fn list_contracts(&self) -> Result<Vec<Contract>, ListContractsError>;
+    async fn update_contract(&self, contract: Contract) -> Result<Contract, UpdateContractError>;
+    async fn get_contract_id(&self) -> Result<u32, ()>;
+}
+
+/// Contracts live in the same table as apps, keyed as `contract#<id>` with their own counter.
+/// Keys of tenants other than the default one are prefixed with the tenant
+#[derive(Debug, Clone)]
+pub struct DynamoContractsRepository {
+    pub dynamo_client: aws_sdk_dynamodb::Client,
+    pub table_name: String,
+    pub tenant: Tenant,
+}
+
+impl DynamoContractsRepository {
+    #[instrument]
+    pub fn new(dynamo_client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        Self {
+            dynamo_client,
+            table_name,
+            tenant: Tenant::default(),
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn create_contract_counter(&self) {
+        let new_counter = to_item(DynamoContractCounter {
+            pk: "contract_counter".to_string(),
+            count: 0,
+        })
+        .expect("Creation of contract counter should not fail");
+
+        let result = self
+            .dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(new_counter))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await;
+
+        match result.map_err(|e| e.into_service_error()) {
+            Ok(_) => info!("Contract counter was not found, created"),
+            Err(PutItemError::ConditionalCheckFailedException(_)) => {}
+            Err(err) => panic!(
+                "Unable to create contract counter: {}",
+                DisplayErrorContext(&err)
+            ),
+        }
+    }
+}
+
+fn contract_pk(id: u32) -> String {
+    format!("contract#{id}")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ContractDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    contract: Contract,
+}
+
+impl ContractDynamoItem {
+    #[instrument]
+    pub fn new(tenant: &Tenant, contract: &Contract) -> Self {
+        Self {
+            pk: tenant_pk(tenant, &contract_pk(contract.id)),
+            entity_type: "contract".to_string(),
+            contract: contract.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DynamoContractCounter {
+    pk: String,
+    count: u32,
+}
+
+impl TenantScoped for DynamoContractsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl ContractsRepository for DynamoContractsRepository {
+    #[instrument(skip(self))]
+    async fn get_contract(&self, id: u32) -> Result<Contract, GetContractError> {
+        let result = self
+            .dynamo_client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &contract_pk(id))),
+            )
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                GetContractError::UnexpectedError
+            })?;
+
+        if let Some(item) = result.item {
+            let contract: Contract = from_item(item)?;
+
+            Ok(contract)
+        } else {
+            Err(GetContractError::ResourceNotFound(id))
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn create_contract(&self, contract: Contract) -> Result<Contract, CreateContractError> {
+        let item = to_item(ContractDynamoItem::new(&self.tenant, &contract))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    PutItemError::ConditionalCheckFailedException(_) => {
+                        CreateContractError::ResourceAlreadyExists(contract.id)
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        CreateContractError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(contract)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_contract(&self, id: u32) -> Result<(), DeleteContractError> {
+        self.dynamo_client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &contract_pk(id))),
+            )
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    DeleteItemError::ConditionalCheckFailedException(_) => {
+                        DeleteContractError::ResourceNotFound(id)
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        DeleteContractError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_contracts(&self) -> Result<Vec<Contract>, ListContractsError> {
+        let mut contracts = Vec::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression(format!(
+                    "entity_type = :entity_type AND {}",
+                    tenant_condition(&self.tenant)
+                ))
+                .expression_attribute_values(
+                    ":entity_type",
+                    AttributeValue::S("contract".to_string()),
+                )
+                .expression_attribute_values(TENANT_PLACEHOLDER, tenant_value(&self.tenant))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListContractsError::UnexpectedError
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<Contract> = from_items(items)?;
+                contracts.extend(page);
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(contracts),
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn update_contract(&self, contract: Contract) -> Result<Contract, UpdateContractError> {
+        let item = to_item(ContractDynamoItem::new(&self.tenant, &contract))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                UpdateContractError::UnexpectedError
+            })?;
+
+        Ok(contract)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_contract_id(&self) -> Result<u32, ()> {
+        let result = self
+            .dynamo_client
+            .update_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, "contract_counter")),
+            )
+            .update_expression("SET #cnt = if_not_exists(#cnt, :start) + :inc")
+            .expression_attribute_names("#cnt", "count")
+            .expression_attribute_values(":start", AttributeValue::N("0".to_string()))
+            .expression_attribute_values(":inc", AttributeValue::N("1".to_string()))
+            .return_values(ReturnValue::AllNew)
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+            })?;
+
+        if let Some(item) = result.attributes {
+            let counter: DynamoContractCounter = from_item(item).map_err(|_| ())?;
+            Ok(counter.count)
+        } else {
+            Err(())
+        }
+    }
+}
@@ -0,0 +1,742 @@
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::future::Future;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use super::{
+    AddAppError, ApiKeysRepository, App, AppField, AppFieldsRepository, AppUser,
+    AppUsersRepository, AppWrite, AppendAuditEventError, AppsFilter, AppsPage, AppsRepository,
+    AppsSnapshot, AuditEvent, AuditFilter, AuditRepository, Contract, ContractsRepository,
+    CreateApiKeyError, CreateAppError, CreateAppFieldError, CreateContractError, CreateUserError,
+    CreateWebhookError, DeleteApiKeyError, DeleteAppError, DeleteAppFieldError, DeleteAppUserError,
+    DeleteContractError, DeleteUserError, DeleteWebhookError, GetApiKeyError, GetAppError,
+    GetAppFieldError, GetContractError, GetUserError, GetWebhookError, InMemoryApiKeysRepository,
+    InMemoryAppFieldsRepository, InMemoryAppUsersRepository, InMemoryAppsRepository,
+    InMemoryAuditRepository, InMemoryContractsRepository, InMemoryUsersRepository,
+    InMemoryWebhooksRepository, ListApiKeysError, ListAppFieldsError, ListAppUsersError,
+    ListAppsError, ListAuditEventsError, ListContractsError, ListUsersError,
+    ListWebhookDeliveriesError, ListWebhooksError, PutAppUserError, PutWebhookDeliveryError,
+    Snapshot, StoredApiKey, TenantContractsSnapshot, TenantUsersSnapshot, TenantWebhooksSnapshot,
+    UpdateApiKeyError, UpdateAppError, UpdateAppFieldError, UpdateContractError, UpdateUserError,
+    UpdateWebhookError, User, UsersRepository, Webhook, WebhookDelivery, WebhooksRepository,
+    WriteAppsError,
+};
+use crate::tenant::{Tenant, TenantScoped};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use tokio::sync::Mutex;
+use tracing::{info, instrument};
+
+/// Everything the file backend keeps. Apps sit at the top level, where they were when they were
+/// the only thing persisted, so older state files still load
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StateSnapshot {
+    #[serde(flatten)]
+    pub apps: AppsSnapshot,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub users: BTreeMap<Tenant, TenantUsersSnapshot>,
+    #[serde(
+        rename = "appUsers",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub app_users: BTreeMap<Tenant, Vec<AppUser>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub contracts: BTreeMap<Tenant, TenantContractsSnapshot>,
+    #[serde(
+        rename = "appFields",
+        default,
+        skip_serializing_if = "BTreeMap::is_empty"
+    )]
+    pub app_fields: BTreeMap<Tenant, Vec<AppField>>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub webhooks: BTreeMap<Tenant, TenantWebhooksSnapshot>,
+    #[serde(rename = "apiKeys", default, skip_serializing_if = "Vec::is_empty")]
+    pub api_keys: Vec<StoredApiKey>,
+}
+
+/// Keeps every repository in memory and writes all of them to one JSON file after every
+/// mutation, so data survives restarts and state files can be checked in as fixtures. Audit
+/// events, webhook deliveries and when API keys were last used only live in memory, they change
+/// on every request and would churn the file
+#[derive(Debug)]
+pub struct FileState {
+    path: PathBuf,
+    /// Contents of the state file as last written. Holding it serializes mutations so the file
+    /// always reflects the latest state
+    persisted: Mutex<Vec<u8>>,
+    apps: InMemoryAppsRepository,
+    users: InMemoryUsersRepository,
+    app_users: InMemoryAppUsersRepository,
+    contracts: InMemoryContractsRepository,
+    app_fields: InMemoryAppFieldsRepository,
+    audit: InMemoryAuditRepository,
+    webhooks: InMemoryWebhooksRepository,
+    api_keys: InMemoryApiKeysRepository,
+}
+
+impl FileState {
+    /// Loads the state file at `path`, creating it if it does not exist yet
+    #[instrument]
+    pub fn load(path: PathBuf) -> Arc<Self> {
+        let mut state = Self {
+            path,
+            persisted: Mutex::new(Vec::new()),
+            apps: InMemoryAppsRepository::new(),
+            users: InMemoryUsersRepository::new(),
+            app_users: InMemoryAppUsersRepository::new(),
+            contracts: InMemoryContractsRepository::new(),
+            app_fields: InMemoryAppFieldsRepository::new(),
+            audit: InMemoryAuditRepository::new(),
+            webhooks: InMemoryWebhooksRepository::new(),
+            api_keys: InMemoryApiKeysRepository::new(),
+        };
+
+        if state.path.exists() {
+            info!("Loading state from {}", state.path.display());
+
+            let contents = fs::read_to_string(&state.path).expect("Unable to read state file");
+            let snapshot: StateSnapshot =
+                serde_json::from_str(&contents).expect("State file is not valid");
+
+            state
+                .restore(snapshot)
+                .expect("Unable to restore state file");
+        } else {
+            info!(
+                "State file was not found, creating {}",
+                state.path.display()
+            );
+        }
+
+        let contents = state.serialize().expect("Unable to serialize state");
+        write_atomically(&state.path, &contents).expect("Unable to write state file");
+        *state.persisted.get_mut() = contents;
+
+        Arc::new(state)
+    }
+
+    pub fn apps(self: &Arc<Self>) -> FileAppsRepository {
+        FileRepository::new(self.apps.clone(), self)
+    }
+
+    pub fn users(self: &Arc<Self>) -> FileUsersRepository {
+        FileRepository::new(self.users.clone(), self)
+    }
+
+    pub fn app_users(self: &Arc<Self>) -> FileAppUsersRepository {
+        FileRepository::new(self.app_users.clone(), self)
+    }
+
+    pub fn contracts(self: &Arc<Self>) -> FileContractsRepository {
+        FileRepository::new(self.contracts.clone(), self)
+    }
+
+    pub fn app_fields(self: &Arc<Self>) -> FileAppFieldsRepository {
+        FileRepository::new(self.app_fields.clone(), self)
+    }
+
+    pub fn audit(self: &Arc<Self>) -> FileAuditRepository {
+        FileRepository::new(self.audit.clone(), self)
+    }
+
+    pub fn webhooks(self: &Arc<Self>) -> FileWebhooksRepository {
+        FileRepository::new(self.webhooks.clone(), self)
+    }
+
+    pub fn api_keys(self: &Arc<Self>) -> FileApiKeysRepository {
+        FileRepository::new(self.api_keys.clone(), self)
+    }
+
+    fn snapshot(&self) -> Result<StateSnapshot, ()> {
+        Ok(StateSnapshot {
+            apps: self.apps.snapshot()?,
+            users: self.users.snapshot()?,
+            app_users: self.app_users.snapshot()?,
+            contracts: self.contracts.snapshot()?,
+            app_fields: self.app_fields.snapshot()?,
+            webhooks: self.webhooks.snapshot()?,
+            api_keys: self.api_keys.snapshot()?,
+        })
+    }
+
+    fn restore(&self, snapshot: StateSnapshot) -> Result<(), ()> {
+        self.apps.restore(snapshot.apps)?;
+        self.users.restore(snapshot.users)?;
+        self.app_users.restore(snapshot.app_users)?;
+        self.contracts.restore(snapshot.contracts)?;
+        self.app_fields.restore(snapshot.app_fields)?;
+        self.webhooks.restore(snapshot.webhooks)?;
+        self.api_keys.restore(snapshot.api_keys)
+    }
+
+    fn serialize(&self) -> Result<Vec<u8>, ()> {
+        serde_json::to_vec_pretty(&self.snapshot()?).map_err(|e| {
+            tracing::error!("Unable to serialize state: {}", e);
+        })
+    }
+
+    /// Runs `mutation` against `repo` and writes the state file off the async runtime. When the
+    /// file can't be written `repo` is put back from the contents last written, so memory never
+    /// holds a change the file doesn't
+    async fn write<R, T, E>(
+        &self,
+        repo: &R,
+        unexpected: impl Fn() -> E,
+        mutation: impl Future<Output = Result<T, E>>,
+    ) -> Result<T, E>
+    where
+        R: Persisted,
+    {
+        let mut persisted = self.persisted.lock().await;
+
+        let value = mutation.await?;
+
+        let written = match self.serialize() {
+            Ok(contents) => {
+                let path = self.path.clone();
+
+                tokio::task::spawn_blocking(move || {
+                    write_atomically(&path, &contents).map(|_| contents)
+                })
+                .await
+                .map_err(|e| tracing::error!("State file writer panicked: {}", e))
+                .and_then(|result| {
+                    result.map_err(|e| tracing::error!("Unable to write state file: {}", e))
+                })
+            }
+            Err(()) => Err(()),
+        };
+
+        match written {
+            Ok(contents) => {
+                *persisted = contents;
+                Ok(value)
+            }
+            Err(()) => {
+                let rolled_back = serde_json::from_slice::<StateSnapshot>(&persisted)
+                    .map_err(|_| ())
+                    .and_then(|snapshot| repo.restore(R::from_state(snapshot)));
+
+                if rolled_back.is_err() {
+                    tracing::error!("Unable to roll back a change the state file didn't take");
+                }
+
+                Err(unexpected())
+            }
+        }
+    }
+}
+
+/// Picks the part of the state file a repository restores from
+trait Persisted: Snapshot {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot;
+}
+
+impl Persisted for InMemoryAppsRepository {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot {
+        state.apps
+    }
+}
+
+impl Persisted for InMemoryUsersRepository {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot {
+        state.users
+    }
+}
+
+impl Persisted for InMemoryAppUsersRepository {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot {
+        state.app_users
+    }
+}
+
+impl Persisted for InMemoryContractsRepository {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot {
+        state.contracts
+    }
+}
+
+impl Persisted for InMemoryAppFieldsRepository {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot {
+        state.app_fields
+    }
+}
+
+impl Persisted for InMemoryWebhooksRepository {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot {
+        state.webhooks
+    }
+}
+
+impl Persisted for InMemoryApiKeysRepository {
+    fn from_state(state: StateSnapshot) -> Self::Snapshot {
+        state.api_keys
+    }
+}
+
+/// Writes to a temporary file next to the target and renames it over the original, so a crash
+/// mid-write never leaves a truncated state file behind
+fn write_atomically(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let tmp_path = PathBuf::from(tmp_path);
+
+    let mut file = fs::File::create(&tmp_path)?;
+    file.write_all(contents)?;
+    file.sync_all()?;
+
+    fs::rename(&tmp_path, path)
+}
+
+/// An in-memory repository whose mutations are written to the state file. Reads go straight
+/// to memory, and so do the writes `FileState` leaves out of the file
+#[derive(Debug, Clone)]
+pub struct FileRepository<R> {
+    inner: R,
+    state: Arc<FileState>,
+}
+
+pub type FileAppsRepository = FileRepository<InMemoryAppsRepository>;
+pub type FileUsersRepository = FileRepository<InMemoryUsersRepository>;
+pub type FileAppUsersRepository = FileRepository<InMemoryAppUsersRepository>;
+pub type FileContractsRepository = FileRepository<InMemoryContractsRepository>;
+pub type FileAppFieldsRepository = FileRepository<InMemoryAppFieldsRepository>;
+pub type FileAuditRepository = FileRepository<InMemoryAuditRepository>;
+pub type FileWebhooksRepository = FileRepository<InMemoryWebhooksRepository>;
+pub type FileApiKeysRepository = FileRepository<InMemoryApiKeysRepository>;
+
+impl<R> FileRepository<R> {
+    fn new(inner: R, state: &Arc<FileState>) -> Self {
+        Self {
+            inner,
+            state: state.clone(),
+        }
+    }
+}
+
+/// Every tenant is kept in the same state file, so all of them share the write lock
+impl<R: TenantScoped> TenantScoped for FileRepository<R> {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            inner: self.inner.for_tenant(tenant),
+            state: self.state.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl AppsRepository for FileAppsRepository {
+    #[instrument(skip(self))]
+    async fn get_app(&self, id: u16) -> Result<App, GetAppError> {
+        self.inner.get_app(id).await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_app_by_name(&self, name: &str) -> Result<App, GetAppError> {
+        self.inner.get_app_by_name(name).await
+    }
+
+    #[instrument(skip(self))]
+    async fn add_app(&self, app: App) -> Result<App, AddAppError> {
+        self.state
+            .write(
+                &self.inner,
+                || AddAppError::UnexpectedError,
+                self.inner.add_app(app),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn create_app(&self, app: App) -> Result<App, CreateAppError> {
+        self.state
+            .write(
+                &self.inner,
+                || CreateAppError::UnexpectedError,
+                self.inner.create_app(app),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app(&self, id: u16) -> Result<(), DeleteAppError> {
+        self.state
+            .write(
+                &self.inner,
+                || DeleteAppError::UnexpectedError,
+                self.inner.delete_app(id),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError> {
+        self.inner.list_apps().await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_apps_page(
+        &self,
+        size: Option<u16>,
+        cursor: Option<String>,
+        filter: &AppsFilter,
+    ) -> Result<AppsPage, ListAppsError> {
+        self.inner.list_apps_page(size, cursor, filter).await
+    }
+
+    #[instrument(skip(self))]
+    async fn update_app(&self, app: App) -> Result<App, UpdateAppError> {
+        self.state
+            .write(
+                &self.inner,
+                || UpdateAppError::UnexpectedError,
+                self.inner.update_app(app),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn write_apps(&self, writes: Vec<AppWrite>) -> Result<Vec<Option<App>>, WriteAppsError> {
+        self.state
+            .write(
+                &self.inner,
+                || WriteAppsError::UnexpectedError,
+                self.inner.write_apps(writes),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_id(&self) -> Result<u16, ()> {
+        self.state
+            .write(&self.inner, || (), self.inner.get_id())
+            .await
+    }
+}
+
+#[async_trait]
+impl UsersRepository for FileUsersRepository {
+    #[instrument(skip(self))]
+    async fn get_user(&self, id: u32) -> Result<User, GetUserError> {
+        self.inner.get_user(id).await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user_by_email(&self, email: &str) -> Result<User, GetUserError> {
+        self.inner.get_user_by_email(email).await
+    }
+
+    #[instrument(skip(self))]
+    async fn create_user(&self, user: User) -> Result<User, CreateUserError> {
+        self.state
+            .write(
+                &self.inner,
+                || CreateUserError::UnexpectedError,
+                self.inner.create_user(user),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_user(&self, id: u32) -> Result<(), DeleteUserError> {
+        self.state
+            .write(
+                &self.inner,
+                || DeleteUserError::UnexpectedError,
+                self.inner.delete_user(id),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_users(&self) -> Result<Vec<User>, ListUsersError> {
+        self.inner.list_users().await
+    }
+
+    #[instrument(skip(self))]
+    async fn update_user(&self, user: User) -> Result<User, UpdateUserError> {
+        self.state
+            .write(
+                &self.inner,
+                || UpdateUserError::UnexpectedError,
+                self.inner.update_user(user),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user_id(&self) -> Result<u32, ()> {
+        self.state
+            .write(&self.inner, || (), self.inner.get_user_id())
+            .await
+    }
+}
+
+#[async_trait]
+impl AppUsersRepository for FileAppUsersRepository {
+    #[instrument(skip(self))]
+    async fn put_app_user(&self, app_user: AppUser) -> Result<AppUser, PutAppUserError> {
+        self.state
+            .write(
+                &self.inner,
+                || PutAppUserError::UnexpectedError,
+                self.inner.put_app_user(app_user),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app_user(&self, app_id: u16, user_id: u32) -> Result<(), DeleteAppUserError> {
+        self.state
+            .write(
+                &self.inner,
+                || DeleteAppUserError::UnexpectedError,
+                self.inner.delete_app_user(app_id, user_id),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_app_users(&self, app_id: u16) -> Result<Vec<AppUser>, ListAppUsersError> {
+        self.inner.list_app_users(app_id).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_user_apps(&self, user_id: u32) -> Result<Vec<AppUser>, ListAppUsersError> {
+        self.inner.list_user_apps(user_id).await
+    }
+
+    #[instrument(skip(self))]
+    async fn count_active_users(&self) -> Result<HashMap<u16, u32>, ListAppUsersError> {
+        self.inner.count_active_users().await
+    }
+}
+
+#[async_trait]
+impl ContractsRepository for FileContractsRepository {
+    #[instrument(skip(self))]
+    async fn get_contract(&self, id: u32) -> Result<Contract, GetContractError> {
+        self.inner.get_contract(id).await
+    }
+
+    #[instrument(skip(self))]
+    async fn create_contract(&self, contract: Contract) -> Result<Contract, CreateContractError> {
+        self.state
+            .write(
+                &self.inner,
+                || CreateContractError::UnexpectedError,
+                self.inner.create_contract(contract),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_contract(&self, id: u32) -> Result<(), DeleteContractError> {
+        self.state
+            .write(
+                &self.inner,
+                || DeleteContractError::UnexpectedError,
+                self.inner.delete_contract(id),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_contracts(&self) -> Result<Vec<Contract>, ListContractsError> {
+        self.inner.list_contracts().await
+    }
+
+    #[instrument(skip(self))]
+    async fn update_contract(&self, contract: Contract) -> Result<Contract, UpdateContractError> {
+        self.state
+            .write(
+                &self.inner,
+                || UpdateContractError::UnexpectedError,
+                self.inner.update_contract(contract),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn get_contract_id(&self) -> Result<u32, ()> {
+        self.state
+            .write(&self.inner, || (), self.inner.get_contract_id())
+            .await
+    }
+}
+
+#[async_trait]
+impl AppFieldsRepository for FileAppFieldsRepository {
+    #[instrument(skip(self))]
+    async fn get_app_field(&self, key: &str) -> Result<AppField, GetAppFieldError> {
+        self.inner.get_app_field(key).await
+    }
+
+    #[instrument(skip(self))]
+    async fn create_app_field(&self, field: AppField) -> Result<AppField, CreateAppFieldError> {
+        self.state
+            .write(
+                &self.inner,
+                || CreateAppFieldError::UnexpectedError,
+                self.inner.create_app_field(field),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app_field(&self, key: &str) -> Result<(), DeleteAppFieldError> {
+        self.state
+            .write(
+                &self.inner,
+                || DeleteAppFieldError::UnexpectedError,
+                self.inner.delete_app_field(key),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_app_fields(&self) -> Result<Vec<AppField>, ListAppFieldsError> {
+        self.inner.list_app_fields().await
+    }
+
+    #[instrument(skip(self))]
+    async fn update_app_field(&self, field: AppField) -> Result<AppField, UpdateAppFieldError> {
+        self.state
+            .write(
+                &self.inner,
+                || UpdateAppFieldError::UnexpectedError,
+                self.inner.update_app_field(field),
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl AuditRepository for FileAuditRepository {
+    #[instrument(skip(self))]
+    async fn append_event(&self, event: AuditEvent) -> Result<(), AppendAuditEventError> {
+        self.inner.append_event(event).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_events(
+        &self,
+        filter: &AuditFilter,
+    ) -> Result<Vec<AuditEvent>, ListAuditEventsError> {
+        self.inner.list_events(filter).await
+    }
+}
+
+#[async_trait]
+impl WebhooksRepository for FileWebhooksRepository {
+    #[instrument(skip(self))]
+    async fn get_webhook(&self, id: &str) -> Result<Webhook, GetWebhookError> {
+        self.inner.get_webhook(id).await
+    }
+
+    #[instrument(skip(self))]
+    async fn create_webhook(&self, webhook: Webhook) -> Result<Webhook, CreateWebhookError> {
+        self.state
+            .write(
+                &self.inner,
+                || CreateWebhookError::UnexpectedError,
+                self.inner.create_webhook(webhook),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_webhook(&self, id: &str) -> Result<(), DeleteWebhookError> {
+        self.state
+            .write(
+                &self.inner,
+                || DeleteWebhookError::UnexpectedError,
+                self.inner.delete_webhook(id),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>, ListWebhooksError> {
+        self.inner.list_webhooks().await
+    }
+
+    #[instrument(skip(self))]
+    async fn update_webhook(&self, webhook: Webhook) -> Result<Webhook, UpdateWebhookError> {
+        self.state
+            .write(
+                &self.inner,
+                || UpdateWebhookError::UnexpectedError,
+                self.inner.update_webhook(webhook),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn put_delivery(&self, delivery: WebhookDelivery) -> Result<(), PutWebhookDeliveryError> {
+        self.inner.put_delivery(delivery).await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_deliveries(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, ListWebhookDeliveriesError> {
+        self.inner.list_deliveries(webhook_id).await
+    }
+}
+
+#[async_trait]
+impl ApiKeysRepository for FileApiKeysRepository {
+    #[instrument(skip(self))]
+    async fn get_api_key(&self, id: &str) -> Result<StoredApiKey, GetApiKeyError> {
+        self.inner.get_api_key(id).await
+    }
+
+    #[instrument(skip(self, key))]
+    async fn create_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, CreateApiKeyError> {
+        self.state
+            .write(
+                &self.inner,
+                || CreateApiKeyError::UnexpectedError,
+                self.inner.create_api_key(key),
+            )
+            .await
+    }
+
+    #[instrument(skip(self, key))]
+    async fn update_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, UpdateApiKeyError> {
+        self.state
+            .write(
+                &self.inner,
+                || UpdateApiKeyError::UnexpectedError,
+                self.inner.update_api_key(key),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_api_key(&self, id: &str) -> Result<(), DeleteApiKeyError> {
+        self.state
+            .write(
+                &self.inner,
+                || DeleteApiKeyError::UnexpectedError,
+                self.inner.delete_api_key(id),
+            )
+            .await
+    }
+
+    #[instrument(skip(self))]
+    async fn list_api_keys(&self, tenant: &Tenant) -> Result<Vec<StoredApiKey>, ListApiKeysError> {
+        self.inner.list_api_keys(tenant).await
+    }
+
+    #[instrument(skip(self))]
+    async fn record_use(&self, id: &str, at: DateTime<Utc>) -> Result<(), UpdateApiKeyError> {
+        self.inner.record_use(id, at).await
+    }
+}
@@ -0,0 +1,1484 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::ops::Bound;
+use std::sync::{Arc, Mutex, PoisonError, RwLock};
+
+use super::{
+    decode_cursor, encode_cursor, AddAppError, ApiKeysRepository, App, AppField,
+    AppFieldsRepository, AppUser, AppUsersRepository, AppWrite, AppendAuditEventError, AppsFilter,
+    AppsPage, AppsRepository, AuditEvent, AuditFilter, AuditRepository, Contract,
+    ContractsRepository, CreateApiKeyError, CreateAppError, CreateAppFieldError,
+    CreateContractError, CreateUserError, CreateWebhookError, DeleteApiKeyError, DeleteAppError,
+    DeleteAppFieldError, DeleteAppUserError, DeleteContractError, DeleteUserError,
+    DeleteWebhookError, GetApiKeyError, GetAppError, GetAppFieldError, GetContractError,
+    GetUserError, GetWebhookError, LicenseStatus, ListApiKeysError, ListAppFieldsError,
+    ListAppUsersError, ListAppsError, ListAuditEventsError, ListContractsError, ListUsersError,
+    ListWebhookDeliveriesError, ListWebhooksError, PutAppUserError, PutWebhookDeliveryError,
+    StoredApiKey, UpdateApiKeyError, UpdateAppError, UpdateAppFieldError, UpdateContractError,
+    UpdateUserError, UpdateWebhookError, User, UsersRepository, Webhook, WebhookDelivery,
+    WebhooksRepository, WriteAppsError,
+};
+use crate::tenant::{Tenant, TenantScoped};
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use tracing::instrument;
+
+/// Matches the starting value `DynamoAppsRepository::create_atomic_counter` uses so IDs line up
+/// between backends
+const COUNTER_START: u16 = 1100;
+
+/// Every tenant gets a store of its own, created the first time a repository is scoped to it
+#[derive(Debug)]
+struct TenantStores<S>(Arc<Mutex<HashMap<Tenant, Arc<RwLock<S>>>>>);
+
+impl<S> Clone for TenantStores<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S: Default> TenantStores<S> {
+    fn new() -> Self {
+        Self(Arc::new(Mutex::new(HashMap::new())))
+    }
+
+    /// The map is only ever inserted into, so a poisoned lock still holds consistent data
+    fn store(&self, tenant: &Tenant) -> Arc<RwLock<S>> {
+        let mut stores = self.0.lock().unwrap_or_else(PoisonError::into_inner);
+
+        stores.entry(tenant.clone()).or_default().clone()
+    }
+
+    /// Every tenant's store turned into `T`
+    fn snapshot<T>(&self, to_snapshot: impl Fn(&S) -> T) -> Result<BTreeMap<Tenant, T>, ()> {
+        let stores = self.0.lock().map_err(|_| ())?;
+
+        stores
+            .iter()
+            .map(|(tenant, store)| {
+                let store = store.read().map_err(|_| ())?;
+                Ok((tenant.clone(), to_snapshot(&store)))
+            })
+            .collect()
+    }
+
+    /// Replaces every tenant's store, the ones missing from `snapshot` are emptied. Stores are
+    /// overwritten in place because scoped repositories hold on to them
+    fn restore<T>(
+        &self,
+        mut snapshot: BTreeMap<Tenant, T>,
+        from_snapshot: impl Fn(T) -> S,
+    ) -> Result<(), ()> {
+        let existing: Vec<Tenant> = self.0.lock().map_err(|_| ())?.keys().cloned().collect();
+
+        for tenant in existing {
+            let store = match snapshot.remove(&tenant) {
+                Some(tenant_snapshot) => from_snapshot(tenant_snapshot),
+                None => S::default(),
+            };
+            *self.store(&tenant).write().map_err(|_| ())? = store;
+        }
+
+        for (tenant, tenant_snapshot) in snapshot {
+            *self.store(&tenant).write().map_err(|_| ())? = from_snapshot(tenant_snapshot);
+        }
+
+        Ok(())
+    }
+}
+
+/// Contents of a repository across every tenant that the file backend writes to the state file.
+/// Bookkeeping that changes on reads or on every request, like delivery logs and when a key was
+/// last used, is left out and kept as is on `restore`
+pub trait Snapshot {
+    type Snapshot: Serialize + DeserializeOwned;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, ()>;
+    fn restore(&self, snapshot: Self::Snapshot) -> Result<(), ()>;
+}
+
+#[derive(Debug)]
+struct InMemoryStore {
+    apps: BTreeMap<u16, App>,
+    /// Stand-in for the `name_index` GSI, names are not unique so each maps to a set of IDs
+    name_index: HashMap<String, BTreeSet<u16>>,
+    counter: u16,
+}
+
+impl Default for InMemoryStore {
+    fn default() -> Self {
+        Self {
+            apps: BTreeMap::new(),
+            name_index: HashMap::new(),
+            counter: COUNTER_START,
+        }
+    }
+}
+
+impl InMemoryStore {
+    fn index(&mut self, app: &App) {
+        self.name_index
+            .entry(app.name.clone())
+            .or_default()
+            .insert(app.id);
+    }
+
+    fn unindex(&mut self, app: &App) {
+        if let Some(ids) = self.name_index.get_mut(&app.name) {
+            ids.remove(&app.id);
+
+            if ids.is_empty() {
+                self.name_index.remove(&app.name);
+            }
+        }
+    }
+
+    fn insert(&mut self, app: App) {
+        if let Some(previous) = self.apps.remove(&app.id) {
+            self.unindex(&previous);
+        }
+
+        self.index(&app);
+        self.apps.insert(app.id, app);
+    }
+
+    fn restore(snapshot: TenantAppsSnapshot) -> Self {
+        let mut store = Self {
+            counter: snapshot.counter,
+            ..Default::default()
+        };
+
+        for app in snapshot.apps {
+            store.insert(app);
+        }
+
+        store
+    }
+
+    fn snapshot(&self) -> TenantAppsSnapshot {
+        TenantAppsSnapshot {
+            counter: self.counter,
+            apps: self.apps.values().cloned().collect(),
+        }
+    }
+}
+
+/// Full contents of an in-memory store, used to persist and restore it. The default tenant's
+/// apps sit at the top level so snapshots taken before tenants existed still load
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AppsSnapshot {
+    pub counter: u16,
+    pub apps: Vec<App>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub tenants: BTreeMap<Tenant, TenantAppsSnapshot>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantAppsSnapshot {
+    pub counter: u16,
+    pub apps: Vec<App>,
+}
+
+/// Keeps all apps in process memory, intended for local development and CI where DynamoDB
+/// is not available. Data does not survive a restart
+#[derive(Debug, Clone)]
+pub struct InMemoryAppsRepository {
+    store: Arc<RwLock<InMemoryStore>>,
+    tenants: TenantStores<InMemoryStore>,
+}
+
+impl InMemoryAppsRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        let tenants = TenantStores::new();
+
+        Self {
+            store: tenants.store(&Tenant::default()),
+            tenants,
+        }
+    }
+}
+
+impl Default for InMemoryAppsRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantScoped for InMemoryAppsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            store: self.tenants.store(tenant),
+            tenants: self.tenants.clone(),
+        }
+    }
+}
+
+/// Covers every tenant, whichever one the repository is scoped to
+impl Snapshot for InMemoryAppsRepository {
+    type Snapshot = AppsSnapshot;
+
+    fn snapshot(&self) -> Result<AppsSnapshot, ()> {
+        let mut tenants = self.tenants.snapshot(InMemoryStore::snapshot)?;
+
+        let default = tenants
+            .remove(&Tenant::default())
+            .unwrap_or_else(|| InMemoryStore::default().snapshot());
+
+        Ok(AppsSnapshot {
+            counter: default.counter,
+            apps: default.apps,
+            tenants,
+        })
+    }
+
+    fn restore(&self, snapshot: AppsSnapshot) -> Result<(), ()> {
+        let mut tenants = snapshot.tenants;
+        tenants.insert(
+            Tenant::default(),
+            TenantAppsSnapshot {
+                counter: snapshot.counter,
+                apps: snapshot.apps,
+            },
+        );
+
+        self.tenants.restore(tenants, InMemoryStore::restore)
+    }
+}
+
+#[async_trait]
+impl AppsRepository for InMemoryAppsRepository {
+    #[instrument(skip(self))]
+    async fn get_app(&self, id: u16) -> Result<App, GetAppError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetAppError::UnexpectedError)?;
+
+        store
+            .apps
+            .get(&id)
+            .cloned()
+            .ok_or(GetAppError::ResourceNotFound(id))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_app_by_name(&self, name: &str) -> Result<App, GetAppError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetAppError::UnexpectedError)?;
+
+        store
+            .name_index
+            .get(name)
+            .and_then(|ids| ids.first())
+            .and_then(|id| store.apps.get(id))
+            .cloned()
+            .ok_or(GetAppError::ResourceNotFound(0))
+    }
+
+    #[instrument(skip(self))]
+    /// add_app is intended for adding "pre-existing" applications defined by the service
+    async fn add_app(&self, app: App) -> Result<App, AddAppError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| AddAppError::UnexpectedError)?;
+
+        if store.apps.contains_key(&app.id) {
+            return Err(AddAppError::ResourceAlreadyExists { name: app.name });
+        }
+
+        store.insert(app.clone());
+
+        Ok(app)
+    }
+
+    #[instrument(skip(self))]
+    async fn create_app(&self, app: App) -> Result<App, CreateAppError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| CreateAppError::UnexpectedError)?;
+
+        if store.apps.contains_key(&app.id) {
+            return Err(CreateAppError::ResourceAlreadyExists { name: app.name });
+        }
+
+        store.insert(app.clone());
+
+        Ok(app)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app(&self, id: u16) -> Result<(), DeleteAppError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| DeleteAppError::UnexpectedError)?;
+
+        let app = store
+            .apps
+            .remove(&id)
+            .ok_or(DeleteAppError::ResourceNotFound(id))?;
+        store.unindex(&app);
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_apps(&self) -> Result<Vec<App>, ListAppsError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListAppsError::UnexpectedError)?;
+
+        Ok(store.apps.values().cloned().collect())
+    }
+
+    /// The cursor holds the ID of the last app on the previous page
+    #[instrument(skip(self))]
+    async fn list_apps_page(
+        &self,
+        size: Option<u16>,
+        cursor: Option<String>,
+        filter: &AppsFilter,
+    ) -> Result<AppsPage, ListAppsError> {
+        let start = match cursor {
+            Some(cursor) => Bound::Excluded(decode_cursor(&cursor)?),
+            None => Bound::Unbounded,
+        };
+
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListAppsError::UnexpectedError)?;
+
+        let mut remaining = store
+            .apps
+            .range((start, Bound::Unbounded))
+            .map(|(_, app)| app)
+            .filter(|app| filter.matches(app));
+        let apps: Vec<App> = match size {
+            Some(size) => remaining
+                .by_ref()
+                .take(usize::from(size))
+                .cloned()
+                .collect(),
+            None => remaining.by_ref().cloned().collect(),
+        };
+
+        let next_cursor = match (remaining.next(), apps.last()) {
+            (Some(_), Some(last)) => Some(encode_cursor(last.id)),
+            _ => None,
+        };
+
+        Ok(AppsPage { apps, next_cursor })
+    }
+
+    #[instrument(skip(self))]
+    async fn update_app(&self, mut app: App) -> Result<App, UpdateAppError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| UpdateAppError::UnexpectedError)?;
+
+        match store.apps.get(&app.id) {
+            Some(stored) if stored.version == app.version => {}
+            Some(_) => return Err(UpdateAppError::VersionConflict(app.id)),
+            None => return Err(UpdateAppError::ResourceNotFound(app.id)),
+        }
+
+        app.version += 1;
+        store.insert(app.clone());
+
+        Ok(app)
+    }
+
+    /// Every condition is checked under the write lock before anything is changed
+    #[instrument(skip(self))]
+    async fn write_apps(&self, writes: Vec<AppWrite>) -> Result<Vec<Option<App>>, WriteAppsError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| WriteAppsError::UnexpectedError)?;
+
+        let failed = writes.iter().position(|write| match write {
+            AppWrite::Put(app) => store.apps.contains_key(&app.id),
+            AppWrite::Update(app) => store
+                .apps
+                .get(&app.id)
+                .is_none_or(|stored| stored.version != app.version),
+            AppWrite::Delete(id) => !store.apps.contains_key(id),
+        });
+
+        if let Some(index) = failed {
+            return Err(WriteAppsError::ConditionFailed(index));
+        }
+
+        let written = writes
+            .into_iter()
+            .map(|write| match write {
+                AppWrite::Put(app) => {
+                    store.insert(app.clone());
+                    Some(app)
+                }
+                AppWrite::Update(mut app) => {
+                    app.version += 1;
+                    store.insert(app.clone());
+                    Some(app)
+                }
+                AppWrite::Delete(id) => {
+                    if let Some(app) = store.apps.remove(&id) {
+                        store.unindex(&app);
+                    }
+                    None
+                }
+            })
+            .collect();
+
+        Ok(written)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_id(&self) -> Result<u16, ()> {
+        let mut store = self.store.write().map_err(|_| ())?;
+
+        store.counter = store.counter.checked_add(1).ok_or(())?;
+
+        Ok(store.counter)
+    }
+}
+
+#[derive(Debug, Default)]
+struct InMemoryUsersStore {
+    users: BTreeMap<u32, User>,
+    /// Stand-in for the `email_index` GSI
+    email_index: HashMap<String, u32>,
+    counter: u32,
+}
+
+/// Contents of one tenant's users store, the email index is rebuilt on restore
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantUsersSnapshot {
+    pub counter: u32,
+    pub users: Vec<User>,
+}
+
+impl InMemoryUsersStore {
+    fn restore(snapshot: TenantUsersSnapshot) -> Self {
+        let mut store = Self {
+            counter: snapshot.counter,
+            ..Default::default()
+        };
+
+        for user in snapshot.users {
+            store.insert(user);
+        }
+
+        store
+    }
+
+    fn snapshot(&self) -> TenantUsersSnapshot {
+        TenantUsersSnapshot {
+            counter: self.counter,
+            users: self.users.values().cloned().collect(),
+        }
+    }
+
+    fn insert(&mut self, user: User) {
+        if let Some(previous) = self.users.remove(&user.id) {
+            self.email_index.remove(&previous.email);
+        }
+
+        self.email_index.insert(user.email.clone(), user.id);
+        self.users.insert(user.id, user);
+    }
+}
+
+/// In-process counterpart of `DynamoUsersRepository`
+#[derive(Debug, Clone)]
+pub struct InMemoryUsersRepository {
+    store: Arc<RwLock<InMemoryUsersStore>>,
+    tenants: TenantStores<InMemoryUsersStore>,
+}
+
+impl InMemoryUsersRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        let tenants = TenantStores::new();
+
+        Self {
+            store: tenants.store(&Tenant::default()),
+            tenants,
+        }
+    }
+}
+
+impl Default for InMemoryUsersRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantScoped for InMemoryUsersRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            store: self.tenants.store(tenant),
+            tenants: self.tenants.clone(),
+        }
+    }
+}
+
+impl Snapshot for InMemoryUsersRepository {
+    type Snapshot = BTreeMap<Tenant, TenantUsersSnapshot>;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, ()> {
+        self.tenants.snapshot(InMemoryUsersStore::snapshot)
+    }
+
+    fn restore(&self, snapshot: Self::Snapshot) -> Result<(), ()> {
+        self.tenants.restore(snapshot, InMemoryUsersStore::restore)
+    }
+}
+
+#[async_trait]
+impl UsersRepository for InMemoryUsersRepository {
+    #[instrument(skip(self))]
+    async fn get_user(&self, id: u32) -> Result<User, GetUserError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetUserError::UnexpectedError)?;
+
+        store
+            .users
+            .get(&id)
+            .cloned()
+            .ok_or(GetUserError::ResourceNotFound(id.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user_by_email(&self, email: &str) -> Result<User, GetUserError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetUserError::UnexpectedError)?;
+
+        store
+            .email_index
+            .get(email)
+            .and_then(|id| store.users.get(id))
+            .cloned()
+            .ok_or(GetUserError::ResourceNotFound(email.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn create_user(&self, user: User) -> Result<User, CreateUserError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| CreateUserError::UnexpectedError)?;
+
+        if store.users.contains_key(&user.id) {
+            return Err(CreateUserError::ResourceAlreadyExists { email: user.email });
+        }
+
+        store.insert(user.clone());
+
+        Ok(user)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_user(&self, id: u32) -> Result<(), DeleteUserError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| DeleteUserError::UnexpectedError)?;
+
+        let user = store
+            .users
+            .remove(&id)
+            .ok_or(DeleteUserError::ResourceNotFound(id))?;
+        store.email_index.remove(&user.email);
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_users(&self) -> Result<Vec<User>, ListUsersError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListUsersError::UnexpectedError)?;
+
+        Ok(store.users.values().cloned().collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn update_user(&self, user: User) -> Result<User, UpdateUserError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| UpdateUserError::UnexpectedError)?;
+
+        store.insert(user.clone());
+
+        Ok(user)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user_id(&self) -> Result<u32, ()> {
+        let mut store = self.store.write().map_err(|_| ())?;
+
+        store.counter = store.counter.checked_add(1).ok_or(())?;
+
+        Ok(store.counter)
+    }
+}
+
+/// In-process counterpart of `DynamoAppUsersRepository`, links are keyed by app ID then user ID
+#[derive(Debug, Clone)]
+pub struct InMemoryAppUsersRepository {
+    store: Arc<RwLock<BTreeMap<(u16, u32), AppUser>>>,
+    tenants: TenantStores<BTreeMap<(u16, u32), AppUser>>,
+}
+
+impl InMemoryAppUsersRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        let tenants = TenantStores::new();
+
+        Self {
+            store: tenants.store(&Tenant::default()),
+            tenants,
+        }
+    }
+}
+
+impl Default for InMemoryAppUsersRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantScoped for InMemoryAppUsersRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            store: self.tenants.store(tenant),
+            tenants: self.tenants.clone(),
+        }
+    }
+}
+
+impl Snapshot for InMemoryAppUsersRepository {
+    type Snapshot = BTreeMap<Tenant, Vec<AppUser>>;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, ()> {
+        self.tenants
+            .snapshot(|store| store.values().cloned().collect())
+    }
+
+    fn restore(&self, snapshot: Self::Snapshot) -> Result<(), ()> {
+        self.tenants.restore(snapshot, |app_users| {
+            app_users
+                .into_iter()
+                .map(|app_user| ((app_user.app_id, app_user.user_id), app_user))
+                .collect()
+        })
+    }
+}
+
+#[async_trait]
+impl AppUsersRepository for InMemoryAppUsersRepository {
+    #[instrument(skip(self))]
+    async fn put_app_user(&self, app_user: AppUser) -> Result<AppUser, PutAppUserError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| PutAppUserError::UnexpectedError)?;
+
+        store.insert((app_user.app_id, app_user.user_id), app_user.clone());
+
+        Ok(app_user)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app_user(&self, app_id: u16, user_id: u32) -> Result<(), DeleteAppUserError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| DeleteAppUserError::UnexpectedError)?;
+
+        store
+            .remove(&(app_id, user_id))
+            .map(|_| ())
+            .ok_or(DeleteAppUserError::ResourceNotFound { app_id, user_id })
+    }
+
+    #[instrument(skip(self))]
+    async fn list_app_users(&self, app_id: u16) -> Result<Vec<AppUser>, ListAppUsersError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListAppUsersError::UnexpectedError)?;
+
+        Ok(store
+            .range((app_id, u32::MIN)..=(app_id, u32::MAX))
+            .map(|(_, app_user)| app_user.clone())
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_user_apps(&self, user_id: u32) -> Result<Vec<AppUser>, ListAppUsersError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListAppUsersError::UnexpectedError)?;
+
+        Ok(store
+            .values()
+            .filter(|app_user| app_user.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn count_active_users(&self) -> Result<HashMap<u16, u32>, ListAppUsersError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListAppUsersError::UnexpectedError)?;
+
+        let mut counts: HashMap<u16, u32> = HashMap::new();
+
+        for app_user in store.values() {
+            if app_user.license_status == LicenseStatus::Active {
+                *counts.entry(app_user.app_id).or_default() += 1;
+            }
+        }
+
+        Ok(counts)
+    }
+}
+
+#[derive(Debug, Default)]
+struct InMemoryContractsStore {
+    contracts: BTreeMap<u32, Contract>,
+    counter: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantContractsSnapshot {
+    pub counter: u32,
+    pub contracts: Vec<Contract>,
+}
+
+/// In-process counterpart of `DynamoContractsRepository`
+#[derive(Debug, Clone)]
+pub struct InMemoryContractsRepository {
+    store: Arc<RwLock<InMemoryContractsStore>>,
+    tenants: TenantStores<InMemoryContractsStore>,
+}
+
+impl InMemoryContractsRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        let tenants = TenantStores::new();
+
+        Self {
+            store: tenants.store(&Tenant::default()),
+            tenants,
+        }
+    }
+}
+
+impl Default for InMemoryContractsRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantScoped for InMemoryContractsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            store: self.tenants.store(tenant),
+            tenants: self.tenants.clone(),
+        }
+    }
+}
+
+impl Snapshot for InMemoryContractsRepository {
+    type Snapshot = BTreeMap<Tenant, TenantContractsSnapshot>;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, ()> {
+        self.tenants.snapshot(|store| TenantContractsSnapshot {
+            counter: store.counter,
+            contracts: store.contracts.values().cloned().collect(),
+        })
+    }
+
+    fn restore(&self, snapshot: Self::Snapshot) -> Result<(), ()> {
+        self.tenants
+            .restore(snapshot, |snapshot| InMemoryContractsStore {
+                counter: snapshot.counter,
+                contracts: snapshot
+                    .contracts
+                    .into_iter()
+                    .map(|contract| (contract.id, contract))
+                    .collect(),
+            })
+    }
+}
+
+#[async_trait]
+impl ContractsRepository for InMemoryContractsRepository {
+    #[instrument(skip(self))]
+    async fn get_contract(&self, id: u32) -> Result<Contract, GetContractError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetContractError::UnexpectedError)?;
+
+        store
+            .contracts
+            .get(&id)
+            .cloned()
+            .ok_or(GetContractError::ResourceNotFound(id))
+    }
+
+    #[instrument(skip(self))]
+    async fn create_contract(&self, contract: Contract) -> Result<Contract, CreateContractError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| CreateContractError::UnexpectedError)?;
+
+        if store.contracts.contains_key(&contract.id) {
+            return Err(CreateContractError::ResourceAlreadyExists(contract.id));
+        }
+
+        store.contracts.insert(contract.id, contract.clone());
+
+        Ok(contract)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_contract(&self, id: u32) -> Result<(), DeleteContractError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| DeleteContractError::UnexpectedError)?;
+
+        store
+            .contracts
+            .remove(&id)
+            .map(|_| ())
+            .ok_or(DeleteContractError::ResourceNotFound(id))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_contracts(&self) -> Result<Vec<Contract>, ListContractsError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListContractsError::UnexpectedError)?;
+
+        Ok(store.contracts.values().cloned().collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn update_contract(&self, contract: Contract) -> Result<Contract, UpdateContractError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| UpdateContractError::UnexpectedError)?;
+
+        store.contracts.insert(contract.id, contract.clone());
+
+        Ok(contract)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_contract_id(&self) -> Result<u32, ()> {
+        let mut store = self.store.write().map_err(|_| ())?;
+
+        store.counter = store.counter.checked_add(1).ok_or(())?;
+
+        Ok(store.counter)
+    }
+}
+
+/// In-process counterpart of `DynamoAppFieldsRepository`
+#[derive(Debug, Clone)]
+pub struct InMemoryAppFieldsRepository {
+    store: Arc<RwLock<BTreeMap<String, AppField>>>,
+    tenants: TenantStores<BTreeMap<String, AppField>>,
+}
+
+impl InMemoryAppFieldsRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        let tenants = TenantStores::new();
+
+        Self {
+            store: tenants.store(&Tenant::default()),
+            tenants,
+        }
+    }
+}
+
+impl Default for InMemoryAppFieldsRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantScoped for InMemoryAppFieldsRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            store: self.tenants.store(tenant),
+            tenants: self.tenants.clone(),
+        }
+    }
+}
+
+impl Snapshot for InMemoryAppFieldsRepository {
+    type Snapshot = BTreeMap<Tenant, Vec<AppField>>;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, ()> {
+        self.tenants
+            .snapshot(|store| store.values().cloned().collect())
+    }
+
+    fn restore(&self, snapshot: Self::Snapshot) -> Result<(), ()> {
+        self.tenants.restore(snapshot, |fields| {
+            fields
+                .into_iter()
+                .map(|field| (field.key.clone(), field))
+                .collect()
+        })
+    }
+}
+
+#[async_trait]
+impl AppFieldsRepository for InMemoryAppFieldsRepository {
+    #[instrument(skip(self))]
+    async fn get_app_field(&self, key: &str) -> Result<AppField, GetAppFieldError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetAppFieldError::UnexpectedError)?;
+
+        store
+            .get(key)
+            .cloned()
+            .ok_or(GetAppFieldError::ResourceNotFound(key.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn create_app_field(&self, field: AppField) -> Result<AppField, CreateAppFieldError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| CreateAppFieldError::UnexpectedError)?;
+
+        if store.contains_key(&field.key) {
+            return Err(CreateAppFieldError::ResourceAlreadyExists(field.key));
+        }
+
+        store.insert(field.key.clone(), field.clone());
+
+        Ok(field)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_app_field(&self, key: &str) -> Result<(), DeleteAppFieldError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| DeleteAppFieldError::UnexpectedError)?;
+
+        store
+            .remove(key)
+            .map(|_| ())
+            .ok_or(DeleteAppFieldError::ResourceNotFound(key.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_app_fields(&self) -> Result<Vec<AppField>, ListAppFieldsError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListAppFieldsError::UnexpectedError)?;
+
+        Ok(store.values().cloned().collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn update_app_field(&self, field: AppField) -> Result<AppField, UpdateAppFieldError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| UpdateAppFieldError::UnexpectedError)?;
+
+        store.insert(field.key.clone(), field.clone());
+
+        Ok(field)
+    }
+}
+
+/// In-process counterpart of `DynamoAuditRepository`, events are kept in the order they happened
+#[derive(Debug, Clone)]
+pub struct InMemoryAuditRepository {
+    store: Arc<RwLock<Vec<AuditEvent>>>,
+    tenants: TenantStores<Vec<AuditEvent>>,
+}
+
+impl InMemoryAuditRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        let tenants = TenantStores::new();
+
+        Self {
+            store: tenants.store(&Tenant::default()),
+            tenants,
+        }
+    }
+}
+
+impl Default for InMemoryAuditRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantScoped for InMemoryAuditRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            store: self.tenants.store(tenant),
+            tenants: self.tenants.clone(),
+        }
+    }
+}
+
+#[async_trait]
+impl AuditRepository for InMemoryAuditRepository {
+    #[instrument(skip(self))]
+    async fn append_event(&self, event: AuditEvent) -> Result<(), AppendAuditEventError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| AppendAuditEventError::UnexpectedError)?;
+
+        store.push(event);
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_events(
+        &self,
+        filter: &AuditFilter,
+    ) -> Result<Vec<AuditEvent>, ListAuditEventsError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListAuditEventsError::UnexpectedError)?;
+
+        Ok(store
+            .iter()
+            .filter(|event| filter.matches(event))
+            .cloned()
+            .collect())
+    }
+}
+
+#[derive(Debug, Default)]
+struct InMemoryWebhooksStore {
+    webhooks: BTreeMap<String, Webhook>,
+    deliveries: BTreeMap<String, WebhookDelivery>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TenantWebhooksSnapshot {
+    pub webhooks: Vec<Webhook>,
+}
+
+/// In-process counterpart of `DynamoWebhooksRepository`
+#[derive(Debug, Clone)]
+pub struct InMemoryWebhooksRepository {
+    store: Arc<RwLock<InMemoryWebhooksStore>>,
+    tenants: TenantStores<InMemoryWebhooksStore>,
+}
+
+impl InMemoryWebhooksRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        let tenants = TenantStores::new();
+
+        Self {
+            store: tenants.store(&Tenant::default()),
+            tenants,
+        }
+    }
+}
+
+impl Default for InMemoryWebhooksRepository {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TenantScoped for InMemoryWebhooksRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            store: self.tenants.store(tenant),
+            tenants: self.tenants.clone(),
+        }
+    }
+}
+
+impl Snapshot for InMemoryWebhooksRepository {
+    type Snapshot = BTreeMap<Tenant, TenantWebhooksSnapshot>;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, ()> {
+        self.tenants.snapshot(|store| TenantWebhooksSnapshot {
+            webhooks: store.webhooks.values().cloned().collect(),
+        })
+    }
+
+    fn restore(&self, snapshot: Self::Snapshot) -> Result<(), ()> {
+        let mut deliveries = self.tenants.snapshot(|store| store.deliveries.clone())?;
+        let snapshot = snapshot
+            .into_iter()
+            .map(|(tenant, webhooks)| {
+                let deliveries = deliveries.remove(&tenant).unwrap_or_default();
+                (tenant, (webhooks, deliveries))
+            })
+            .collect();
+
+        self.tenants
+            .restore(snapshot, |(snapshot, deliveries)| InMemoryWebhooksStore {
+                webhooks: snapshot
+                    .webhooks
+                    .into_iter()
+                    .map(|webhook| (webhook.id.clone(), webhook))
+                    .collect(),
+                deliveries,
+            })
+    }
+}
+
+#[async_trait]
+impl WebhooksRepository for InMemoryWebhooksRepository {
+    #[instrument(skip(self))]
+    async fn get_webhook(&self, id: &str) -> Result<Webhook, GetWebhookError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetWebhookError::UnexpectedError)?;
+
+        store
+            .webhooks
+            .get(id)
+            .cloned()
+            .ok_or_else(|| GetWebhookError::ResourceNotFound(id.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn create_webhook(&self, webhook: Webhook) -> Result<Webhook, CreateWebhookError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| CreateWebhookError::UnexpectedError)?;
+
+        if store.webhooks.contains_key(&webhook.id) {
+            return Err(CreateWebhookError::ResourceAlreadyExists(webhook.id));
+        }
+
+        store.webhooks.insert(webhook.id.clone(), webhook.clone());
+
+        Ok(webhook)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_webhook(&self, id: &str) -> Result<(), DeleteWebhookError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| DeleteWebhookError::UnexpectedError)?;
+
+        store
+            .webhooks
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| DeleteWebhookError::ResourceNotFound(id.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>, ListWebhooksError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListWebhooksError::UnexpectedError)?;
+
+        Ok(store.webhooks.values().cloned().collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn update_webhook(&self, webhook: Webhook) -> Result<Webhook, UpdateWebhookError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| UpdateWebhookError::UnexpectedError)?;
+
+        match store.webhooks.get_mut(&webhook.id) {
+            Some(existing) => *existing = webhook.clone(),
+            None => return Err(UpdateWebhookError::ResourceNotFound(webhook.id)),
+        }
+
+        Ok(webhook)
+    }
+
+    #[instrument(skip(self))]
+    async fn put_delivery(&self, delivery: WebhookDelivery) -> Result<(), PutWebhookDeliveryError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| PutWebhookDeliveryError::UnexpectedError)?;
+
+        store.deliveries.insert(delivery.id.clone(), delivery);
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_deliveries(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, ListWebhookDeliveriesError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListWebhookDeliveriesError::UnexpectedError)?;
+
+        Ok(store
+            .deliveries
+            .values()
+            .filter(|delivery| delivery.webhook_id == webhook_id)
+            .cloned()
+            .collect())
+    }
+}
+
+/// In-process counterpart of `DynamoApiKeysRepository`, keys of every tenant share one store
+#[derive(Debug, Clone, Default)]
+pub struct InMemoryApiKeysRepository {
+    store: Arc<RwLock<BTreeMap<String, StoredApiKey>>>,
+}
+
+impl InMemoryApiKeysRepository {
+    #[instrument]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl Snapshot for InMemoryApiKeysRepository {
+    type Snapshot = Vec<StoredApiKey>;
+
+    fn snapshot(&self) -> Result<Self::Snapshot, ()> {
+        let store = self.store.read().map_err(|_| ())?;
+
+        Ok(store
+            .values()
+            .cloned()
+            .map(|mut key| {
+                key.key.last_used_at = None;
+                key
+            })
+            .collect())
+    }
+
+    fn restore(&self, snapshot: Self::Snapshot) -> Result<(), ()> {
+        let mut store = self.store.write().map_err(|_| ())?;
+        *store = snapshot
+            .into_iter()
+            .map(|mut key| {
+                key.key.last_used_at = store
+                    .get(&key.key.id)
+                    .and_then(|existing| existing.key.last_used_at);
+                (key.key.id.clone(), key)
+            })
+            .collect();
+
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl ApiKeysRepository for InMemoryApiKeysRepository {
+    #[instrument(skip(self))]
+    async fn get_api_key(&self, id: &str) -> Result<StoredApiKey, GetApiKeyError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| GetApiKeyError::UnexpectedError)?;
+
+        store
+            .get(id)
+            .cloned()
+            .ok_or_else(|| GetApiKeyError::ResourceNotFound(id.to_string()))
+    }
+
+    #[instrument(skip(self, key))]
+    async fn create_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, CreateApiKeyError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| CreateApiKeyError::UnexpectedError)?;
+
+        if store.contains_key(&key.key.id) {
+            return Err(CreateApiKeyError::ResourceAlreadyExists(key.key.id));
+        }
+
+        store.insert(key.key.id.clone(), key.clone());
+
+        Ok(key)
+    }
+
+    #[instrument(skip(self, key))]
+    async fn update_api_key(&self, key: StoredApiKey) -> Result<StoredApiKey, UpdateApiKeyError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| UpdateApiKeyError::UnexpectedError)?;
+
+        match store.get_mut(&key.key.id) {
+            Some(existing) => *existing = key.clone(),
+            None => return Err(UpdateApiKeyError::ResourceNotFound(key.key.id)),
+        }
+
+        Ok(key)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_api_key(&self, id: &str) -> Result<(), DeleteApiKeyError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| DeleteApiKeyError::UnexpectedError)?;
+
+        store
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| DeleteApiKeyError::ResourceNotFound(id.to_string()))
+    }
+
+    #[instrument(skip(self))]
+    async fn list_api_keys(&self, tenant: &Tenant) -> Result<Vec<StoredApiKey>, ListApiKeysError> {
+        let store = self
+            .store
+            .read()
+            .map_err(|_| ListApiKeysError::UnexpectedError)?;
+
+        Ok(store
+            .values()
+            .filter(|key| key.key.tenant == *tenant)
+            .cloned()
+            .collect())
+    }
+
+    #[instrument(skip(self))]
+    async fn record_use(&self, id: &str, at: DateTime<Utc>) -> Result<(), UpdateApiKeyError> {
+        let mut store = self
+            .store
+            .write()
+            .map_err(|_| UpdateApiKeyError::UnexpectedError)?;
+
+        let key = store
+            .get_mut(id)
+            .ok_or_else(|| UpdateApiKeyError::ResourceNotFound(id.to_string()))?;
+        key.key.last_used_at = Some(at);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+
+    use super::*;
+    use crate::repository::AppState;
+
+    async fn repository_with_apps(count: u16) -> InMemoryAppsRepository {
+        let repo = InMemoryAppsRepository::new();
+
+        for id in 1..=count {
+            repo.create_app(App {
+                id,
+                name: format!("App {id}"),
+                state: if id % 2 == 0 {
+                    AppState::Sanctioned
+                } else {
+                    AppState::Discovered
+                },
+                ..Default::default()
+            })
+            .await
+            .unwrap();
+        }
+
+        repo
+    }
+
+    #[test]
+    fn cursor_round_trips_and_is_opaque() {
+        let cursor = encode_cursor(1042);
+
+        assert_ne!(cursor, "1042");
+        assert_eq!(decode_cursor(&cursor).unwrap(), 1042);
+        assert!(matches!(
+            decode_cursor("not a cursor"),
+            Err(ListAppsError::InvalidCursor(_))
+        ));
+        assert!(decode_cursor(&URL_SAFE_NO_PAD.encode("tenant#acme#1")).is_err());
+    }
+
+    #[tokio::test]
+    async fn pages_through_matching_apps_until_there_are_no_more() {
+        let repo = repository_with_apps(7).await;
+        let filter = AppsFilter {
+            state: Some(AppState::Discovered),
+            ..Default::default()
+        };
+        let mut cursor = None;
+        let mut pages = Vec::new();
+
+        loop {
+            let page = repo.list_apps_page(Some(2), cursor, &filter).await.unwrap();
+            pages.push(page.apps.iter().map(|app| app.id).collect::<Vec<_>>());
+
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+
+        assert_eq!(pages, vec![vec![1, 3], vec![5, 7]]);
+    }
+
+    #[tokio::test]
+    async fn rejects_an_invalid_cursor() {
+        let repo = repository_with_apps(1).await;
+
+        let result = repo
+            .list_apps_page(Some(1), Some("1".to_string()), &AppsFilter::default())
+            .await;
+
+        assert!(matches!(result, Err(ListAppsError::InvalidCursor(_))));
+    }
+}
@@ -1,7 +1,28 @@
+pub mod api_keys;
+pub mod app_fields;
+pub mod app_users;
 pub mod apps;
+pub mod audit;
+pub mod configured;
+pub mod contracts;
+pub mod file;
+pub mod in_memory;
 pub mod models;
 pub mod repository_errors;
+pub mod tenancy;
+pub mod users;
+pub mod webhooks;
 
+pub use api_keys::*;
+pub use app_fields::*;
+pub use app_users::*;
 pub use apps::*;
+pub use audit::*;
+pub use configured::*;
+pub use contracts::*;
+pub use file::*;
+pub use in_memory::*;
 pub use models::*;
 pub use repository_errors::*;
+pub use users::*;
+pub use webhooks::*;
@@ -0,0 +1,73 @@
+use chrono::{prelude::*, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::tenant::Tenant;
+
+/// What a key may do, every scope includes the ones before it so an `admin` key can also read
+/// and write
+#[derive(
+    Debug,
+    Serialize,
+    Deserialize,
+    strum::Display,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum ApiKeyScope {
+    /// `GET` requests
+    Read,
+    /// Every other request outside of `/v1.0/admin`
+    Write,
+    /// Managing API keys
+    Admin,
+}
+
+/// An API key without its secret, which is only returned once when the key is created
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct ApiKey {
+    pub id: String,
+    pub name: String,
+    #[schema(value_type = String)]
+    pub tenant: Tenant,
+    pub scopes: Vec<ApiKeyScope>,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    /// The key is rejected from this moment on, it never expires when missing
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<DateTime<Utc>>,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl ApiKey {
+    pub fn is_expired(&self, now: DateTime<Utc>) -> bool {
+        self.expires_at.is_some_and(|expires_at| expires_at <= now)
+    }
+}
+
+/// An API key as stored, only a SHA-256 hash of the secret is kept
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct StoredApiKey {
+    #[serde(flatten)]
+    pub key: ApiKey,
+    #[serde(rename = "secretHash")]
+    pub secret_hash: String,
+}
+
+/// Returned when a key is created or rotated, the only time the secret can be read
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub key: ApiKey,
+    /// Sent as `Authorization: Bearer <secret>`
+    pub secret: String,
+}
@@ -1,16 +1,19 @@
+use std::collections::BTreeMap;
+
 use chrono::{prelude::*, Utc};
 use rand::Rng;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use utoipa::ToSchema;
 
-#[derive(Debug, Serialize, Deserialize, strum::Display, Clone, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, strum::Display, Clone, PartialEq, Eq, ToSchema)]
 pub enum AppState {
     Discovered,
     Sanctioned,
     Closed,
 }
 
-#[derive(Debug, Serialize, Deserialize, strum::Display, Clone, ToSchema)]
+#[derive(Debug, Serialize, Deserialize, strum::Display, Clone, PartialEq, Eq, ToSchema)]
 pub enum AppCategory {
     Operations,
     #[serde(rename = "Sales & Marketing")]
@@ -49,7 +52,8 @@ pub struct App {
     #[serde(rename = "imageUrl")]
     pub image_url: Option<String>,
     pub category: AppCategory,
-    pub users: Option<String>,
+    /// Number of users with an active license, computed from the app's users when read
+    pub users: Option<u32>,
     pub description: Option<String>,
     pub tags: Option<String>,
     #[serde(rename = "creationTime")]
@@ -65,6 +69,35 @@ pub struct App {
     #[serde(rename = "isCustom")]
     pub is_custom: bool,
     pub sources: Option<String>,
+    /// Values of custom fields keyed by `AppField.key`
+    #[serde(default)]
+    #[schema(value_type = Object)]
+    pub fields: BTreeMap<String, Value>,
+    /// Bumped on every update and returned as the `ETag`, apps stored before versioning read
+    /// as version 0
+    #[serde(default)]
+    pub version: u64,
+}
+
+impl App {
+    /// `tags` is stored as a single comma separated string
+    pub fn tag_list(&self) -> Vec<&str> {
+        self.tags
+            .as_deref()
+            .map(|tags| {
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tag_list()
+            .iter()
+            .any(|existing| existing.eq_ignore_ascii_case(tag))
+    }
 }
 
 impl Default for App {
@@ -92,6 +125,8 @@ impl Default for App {
             tags: None,
             url: format!("default-{random_id}.com"),
             users: None,
+            fields: BTreeMap::new(),
+            version: 1,
         }
     }
 }
@@ -0,0 +1,35 @@
+use chrono::{prelude::*, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, strum::Display, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+#[strum(serialize_all = "lowercase")]
+pub enum AppFieldType {
+    /// Any string
+    Text,
+    /// Any JSON number
+    Number,
+    /// `YYYY-MM-DD`
+    Date,
+    /// One of the field's `options`
+    Dropdown,
+    /// ID of an existing user
+    User,
+}
+
+/// Admin defined field that can be set on any app, values live in `App.fields` under `key`
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct AppField {
+    pub key: String,
+    pub name: String,
+    #[serde(rename = "fieldType")]
+    pub field_type: AppFieldType,
+    /// Allowed values of a dropdown field, empty for every other type
+    #[serde(default)]
+    pub options: Vec<String>,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    #[serde(rename = "lastUpdatedAt")]
+    pub last_updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,31 @@
+use chrono::{prelude::*, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug, Serialize, Deserialize, strum::Display, Clone, Default, PartialEq, Eq, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum LicenseStatus {
+    #[default]
+    Active,
+    Inactive,
+    Unassigned,
+}
+
+/// Links a user to an app they use, an app has many users and a user has many apps
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct AppUser {
+    #[serde(rename = "appId")]
+    pub app_id: u16,
+    #[serde(rename = "userId")]
+    pub user_id: u32,
+    #[serde(rename = "licenseStatus")]
+    pub license_status: LicenseStatus,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    #[serde(rename = "lastUpdatedAt")]
+    pub last_updated_at: DateTime<Utc>,
+}
@@ -0,0 +1,42 @@
+use std::collections::BTreeMap;
+
+use chrono::{prelude::*, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+#[derive(Debug, Serialize, Deserialize, strum::Display, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditAction {
+    Add,
+    Create,
+    Update,
+    Delete,
+}
+
+/// Value of a single app field before and after the change, `null` when it didn't exist
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, ToSchema)]
+pub struct AuditChange {
+    #[schema(value_type = Object)]
+    pub before: Value,
+    #[schema(value_type = Object)]
+    pub after: Value,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct AuditEvent {
+    pub id: String,
+    #[serde(rename = "appId")]
+    pub app_id: u16,
+    pub action: AuditAction,
+    /// The API key or token the change was made with, such as `api_key:<id>` or `client:<sub>`
+    pub actor: String,
+    /// Taken from the `X-Actor` header as the caller sent it
+    #[serde(rename = "onBehalfOf")]
+    pub on_behalf_of: Option<String>,
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    pub timestamp: DateTime<Utc>,
+    /// Only the app fields that changed, keyed by field name
+    pub changes: BTreeMap<String, AuditChange>,
+}
@@ -0,0 +1,26 @@
+use chrono::{prelude::*, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct Contract {
+    pub id: u32,
+    #[serde(rename = "appId")]
+    pub app_id: u16,
+    pub amount: f64,
+    /// ISO 4217 code, stored uppercase
+    pub currency: String,
+    #[serde(rename = "startDate")]
+    pub start_date: NaiveDate,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<NaiveDate>,
+    #[serde(rename = "renewalDate")]
+    pub renewal_date: Option<NaiveDate>,
+    #[serde(rename = "autoRenew")]
+    pub auto_renew: bool,
+    pub owner: String,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    #[serde(rename = "lastUpdatedAt")]
+    pub last_updated_at: DateTime<Utc>,
+}
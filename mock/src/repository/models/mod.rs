@@ -1,3 +1,17 @@
+pub mod api_key;
 pub mod app;
+pub mod app_field;
+pub mod app_user;
+pub mod audit_event;
+pub mod contract;
+pub mod user;
+pub mod webhook;
 
+pub use api_key::*;
 pub use app::*;
+pub use app_field::*;
+pub use app_user::*;
+pub use audit_event::*;
+pub use contract::*;
+pub use user::*;
+pub use webhook::*;
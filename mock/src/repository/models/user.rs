@@ -0,0 +1,35 @@
+use chrono::{prelude::*, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug, Serialize, Deserialize, strum::Display, Clone, Default, PartialEq, Eq, ToSchema,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum UserLifecycleStatus {
+    #[default]
+    Active,
+    Offboarding,
+    Offboarded,
+}
+
+// List of all values available here:
+// https://developers.toriihq.com/reference/getusers
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct User {
+    pub id: u32,
+    #[serde(rename = "firstName")]
+    pub first_name: String,
+    #[serde(rename = "lastName")]
+    pub last_name: String,
+    /// Stored lowercase so lookups by email are case insensitive
+    pub email: String,
+    #[serde(rename = "lifecycleStatus")]
+    pub lifecycle_status: UserLifecycleStatus,
+    #[serde(rename = "isExternal")]
+    pub is_external: bool,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    #[serde(rename = "lastUpdatedAt")]
+    pub last_updated_at: DateTime<Utc>,
+}
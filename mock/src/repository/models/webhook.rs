@@ -0,0 +1,106 @@
+use chrono::{prelude::*, Utc};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(
+    Debug, Serialize, Deserialize, strum::Display, Clone, Copy, PartialEq, Eq, Hash, ToSchema,
+)]
+pub enum WebhookEventType {
+    #[serde(rename = "app.created")]
+    #[strum(serialize = "app.created")]
+    Created,
+    #[serde(rename = "app.updated")]
+    #[strum(serialize = "app.updated")]
+    Updated,
+    #[serde(rename = "app.deleted")]
+    #[strum(serialize = "app.deleted")]
+    Deleted,
+    /// Sent alongside `app.updated` when the update moved the app to another state
+    #[serde(rename = "app.state_changed")]
+    #[strum(serialize = "app.state_changed")]
+    StateChanged,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct Webhook {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<WebhookEventType>,
+    /// Key for the HMAC-SHA256 signature sent in `X-Torii-Signature`, only returned when the
+    /// webhook is created
+    pub secret: String,
+    pub active: bool,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    #[serde(rename = "lastUpdatedAt")]
+    pub last_updated_at: DateTime<Utc>,
+}
+
+impl Webhook {
+    pub fn is_subscribed(&self, event_type: WebhookEventType) -> bool {
+        self.active && self.events.contains(&event_type)
+    }
+}
+
+/// A webhook as returned after it was created, without its secret
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct RedactedWebhook {
+    pub id: String,
+    pub url: String,
+    pub events: Vec<WebhookEventType>,
+    pub active: bool,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    #[serde(rename = "lastUpdatedAt")]
+    pub last_updated_at: DateTime<Utc>,
+}
+
+impl From<Webhook> for RedactedWebhook {
+    fn from(webhook: Webhook) -> Self {
+        Self {
+            id: webhook.id,
+            url: webhook.url,
+            events: webhook.events,
+            active: webhook.active,
+            creation_time: webhook.creation_time,
+            last_updated_at: webhook.last_updated_at,
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, strum::Display, Clone, Copy, PartialEq, Eq, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum DeliveryStatus {
+    Pending,
+    Succeeded,
+    Failed,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct DeliveryAttempt {
+    #[serde(rename = "attemptedAt")]
+    pub attempted_at: DateTime<Utc>,
+    /// Missing when no response was received
+    #[serde(rename = "statusCode")]
+    pub status_code: Option<u16>,
+    pub error: Option<String>,
+}
+
+/// One event sent to one webhook, updated after every attempt
+#[derive(Debug, Deserialize, Serialize, Clone, ToSchema)]
+pub struct WebhookDelivery {
+    pub id: String,
+    #[serde(rename = "webhookId")]
+    pub webhook_id: String,
+    #[serde(rename = "eventId")]
+    pub event_id: String,
+    #[serde(rename = "eventType")]
+    pub event_type: WebhookEventType,
+    pub url: String,
+    pub status: DeliveryStatus,
+    pub attempts: Vec<DeliveryAttempt>,
+    #[serde(rename = "creationTime")]
+    pub creation_time: DateTime<Utc>,
+    #[serde(rename = "lastUpdatedAt")]
+    pub last_updated_at: DateTime<Utc>,
+}
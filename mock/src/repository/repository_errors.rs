@@ -12,6 +12,9 @@ pub enum GetAppError {
 
 #[derive(Debug, thiserror::Error)]
 pub enum ListAppsError {
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+
     #[error(transparent)]
     ValidationError(#[from] serde_dynamo::Error),
 
@@ -19,6 +22,18 @@ pub enum ListAppsError {
     UnexpectedError,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum ListKnownAppsError {
+    #[error("Invalid cursor: {0}")]
+    InvalidCursor(String),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetKnownAppError {
+    #[error("Known app {0} not found")]
+    ResourceNotFound(u16),
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum DeleteAppError {
     #[error("Resource {0} not found")]
@@ -36,6 +51,15 @@ pub enum UpdateAppError {
     #[error("Resource {0} not found")]
     ResourceNotFound(u16),
 
+    #[error("App {0} was changed since it was read")]
+    VersionConflict(u16),
+
+    #[error("{0}")]
+    InvalidCustomField(String),
+
+    #[error("Invalid patch: {0}")]
+    InvalidPatch(String),
+
     #[error(transparent)]
     ValidationError(#[from] serde_dynamo::Error),
 
@@ -48,6 +72,9 @@ pub enum CreateAppError {
     #[error("Resource {name} already exists")]
     ResourceAlreadyExists { name: String },
 
+    #[error("{0}")]
+    InvalidCustomField(String),
+
     #[error(transparent)]
     ValidationError(#[from] serde_dynamo::Error),
 
@@ -69,3 +96,475 @@ pub enum AddAppError {
     #[error("Unexpected Error")]
     UnexpectedError,
 }
+
+#[derive(Debug, thiserror::Error)]
+pub enum WriteAppsError {
+    #[error("Write {0} did not meet its condition")]
+    ConditionFailed(usize),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+/// Outcome of a single operation of a bulk request that failed
+#[derive(Debug, thiserror::Error)]
+pub enum BulkAppOperationError {
+    #[error(transparent)]
+    Add(#[from] AddAppError),
+
+    #[error(transparent)]
+    Create(#[from] CreateAppError),
+
+    #[error(transparent)]
+    Update(#[from] UpdateAppError),
+
+    #[error(transparent)]
+    Delete(#[from] DeleteAppError),
+
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error("App {0} is changed by more than one operation of the transaction")]
+    DuplicateApp(u16),
+
+    #[error("Not applied because operation {0} failed")]
+    NotApplied(usize),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ImportAppsError {
+    #[error("{0}")]
+    InvalidMapping(String),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetUserError {
+    #[error("User {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListUsersError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateUserError {
+    #[error("User {email} already exists")]
+    ResourceAlreadyExists { email: String },
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateUserError {
+    #[error("User {0} not found")]
+    ResourceNotFound(u32),
+
+    #[error("User {email} already exists")]
+    ResourceAlreadyExists { email: String },
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteUserError {
+    #[error("User {0} not found")]
+    ResourceNotFound(u32),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListAppUsersError {
+    #[error("{0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PutAppUserError {
+    #[error("{0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteAppUserError {
+    #[error("User {user_id} is not a user of app {app_id}")]
+    ResourceNotFound { app_id: u16, user_id: u32 },
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetContractError {
+    #[error("Contract {0} not found")]
+    ResourceNotFound(u32),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListContractsError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateContractError {
+    #[error("Contract {0} already exists")]
+    ResourceAlreadyExists(u32),
+
+    #[error("App {0} does not exist")]
+    AppNotFound(u16),
+
+    #[error("{0}")]
+    InvalidContract(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateContractError {
+    #[error("Contract {0} not found")]
+    ResourceNotFound(u32),
+
+    #[error("App {0} does not exist")]
+    AppNotFound(u16),
+
+    #[error("{0}")]
+    InvalidContract(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteContractError {
+    #[error("Contract {0} not found")]
+    ResourceNotFound(u32),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetAppFieldError {
+    #[error("Field {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListAppFieldsError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateAppFieldError {
+    #[error("Field {0} already exists")]
+    ResourceAlreadyExists(String),
+
+    #[error("{0}")]
+    InvalidField(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateAppFieldError {
+    #[error("Field {0} not found")]
+    ResourceNotFound(String),
+
+    #[error("{0}")]
+    InvalidField(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteAppFieldError {
+    #[error("Field {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AppendAuditEventError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListAuditEventsError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetWebhookError {
+    #[error("Webhook {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListWebhooksError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateWebhookError {
+    #[error("Webhook {0} already exists")]
+    ResourceAlreadyExists(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateWebhookError {
+    #[error("Webhook {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteWebhookError {
+    #[error("Webhook {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum PutWebhookDeliveryError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListWebhookDeliveriesError {
+    #[error("Webhook {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum GetApiKeyError {
+    #[error("API key {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum ListApiKeysError {
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum CreateApiKeyError {
+    #[error("API key {0} already exists")]
+    ResourceAlreadyExists(String),
+
+    #[error("Expiry {0} is not in the future")]
+    InvalidExpiry(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum UpdateApiKeyError {
+    #[error("API key {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum DeleteApiKeyError {
+    #[error("API key {0} not found")]
+    ResourceNotFound(String),
+
+    #[error(transparent)]
+    ValidationError(#[from] serde_dynamo::Error),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum RotateApiKeyError {
+    #[error("API key {0} not found")]
+    ResourceNotFound(String),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum AuthenticateError {
+    #[error("Unknown API key")]
+    UnknownKey,
+
+    #[error("API key {0} has expired")]
+    Expired(String),
+
+    #[error("Invalid token: {0}")]
+    InvalidToken(String),
+
+    #[error("Unexpected Error")]
+    UnexpectedError,
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum IssueTokenError {
+    #[error("{0}")]
+    InvalidRequest(String),
+
+    #[error("Unknown client or wrong secret")]
+    InvalidClient,
+
+    #[error("Grant type {0} is not supported")]
+    UnsupportedGrantType(String),
+
+    #[error("Scope {0} is not allowed for this client")]
+    InvalidScope(String),
+
+    #[error("Tokens are only issued when AUTH_MODE accepts JWTs and JWT_SECRET is set")]
+    SigningUnavailable,
+}
@@ -0,0 +1,37 @@
+use aws_sdk_dynamodb::types::AttributeValue;
+
+use crate::tenant::Tenant;
+
+/// Every tenant but the default one has its items keyed as `tenant#<tenant>#<key>`
+const TENANT_PREFIX: &str = "tenant#";
+
+/// Placeholder `tenant_condition` binds, scans and queries have to set it to `tenant_value`
+pub const TENANT_PLACEHOLDER: &str = ":tenant_prefix";
+
+/// Partition key of `key` for `tenant`. The default tenant keeps unprefixed keys so tables
+/// written before tenants existed still read the same
+pub fn tenant_pk(tenant: &Tenant, key: &str) -> String {
+    if tenant.is_default() {
+        key.to_string()
+    } else {
+        format!("{TENANT_PREFIX}{tenant}#{key}")
+    }
+}
+
+/// Scan and index query condition that keeps only the items of `tenant`, indexes aren't keyed
+/// by tenant so their results are narrowed down on `pk` too
+pub fn tenant_condition(tenant: &Tenant) -> &'static str {
+    if tenant.is_default() {
+        "NOT begins_with(pk, :tenant_prefix)"
+    } else {
+        "begins_with(pk, :tenant_prefix)"
+    }
+}
+
+pub fn tenant_value(tenant: &Tenant) -> AttributeValue {
+    if tenant.is_default() {
+        AttributeValue::S(TENANT_PREFIX.to_string())
+    } else {
+        AttributeValue::S(tenant_pk(tenant, ""))
+    }
+}
@@ -0,0 +1,314 @@
+use super::tenancy::{tenant_condition, tenant_pk, tenant_value, TENANT_PLACEHOLDER};
+use super::{
+    CreateUserError, DeleteUserError, GetUserError, ListUsersError, UpdateUserError, User,
+};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::types::{AttributeValue, ReturnValue};
+use serde::{Deserialize, Serialize};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_item, from_items, to_item};
+use tracing::{info, instrument};
+
+use crate::tenant::{Tenant, TenantScoped};
+
+#[async_trait]
+pub trait UsersRepository: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    async fn get_user(&self, id: u32) -> Result<User, GetUserError>;
+    async fn get_user_by_email(&self, email: &str) -> Result<User, GetUserError>;
+    async fn create_user(&self, user: User) -> Result<User, CreateUserError>;
+    async fn delete_user(&self, id: u32) -> Result<(), DeleteUserError>;
+    async fn list_users(&self) -> Result<Vec<User>, ListUsersError>;
+    async fn update_user(&self, user: User) -> Result<User, UpdateUserError>;
+    async fn get_user_id(&self) -> Result<u32, ()>;
+}
+
+/// Users live in the same table as apps, keyed as `user#<id>` so they can't collide with app IDs.
+/// Keys of tenants other than the default one are prefixed with the tenant
+#[derive(Debug, Clone)]
+pub struct DynamoUsersRepository {
+    pub dynamo_client: aws_sdk_dynamodb::Client,
+    pub table_name: String,
+    pub tenant: Tenant,
+}
+
+impl DynamoUsersRepository {
+    #[instrument]
+    pub fn new(dynamo_client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        Self {
+            dynamo_client,
+            table_name,
+            tenant: Tenant::default(),
+        }
+    }
+
+    #[instrument(skip(self))]
+    pub async fn create_user_counter(&self) {
+        let new_counter = to_item(DynamoUserCounter {
+            pk: "user_counter".to_string(),
+            count: 0,
+        })
+        .expect("Creation of user counter should not fail");
+
+        let result = self
+            .dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(new_counter))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await;
+
+        match result.map_err(|e| e.into_service_error()) {
+            Ok(_) => info!("User counter was not found, created"),
+            Err(PutItemError::ConditionalCheckFailedException(_)) => {}
+            Err(err) => panic!(
+                "Unable to create user counter: {}",
+                DisplayErrorContext(&err)
+            ),
+        }
+    }
+}
+
+fn user_pk(id: u32) -> String {
+    format!("user#{id}")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct UserDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    user: User,
+}
+
+impl UserDynamoItem {
+    #[instrument]
+    pub fn new(tenant: &Tenant, user: &User) -> Self {
+        Self {
+            pk: tenant_pk(tenant, &user_pk(user.id)),
+            entity_type: "user".to_string(),
+            user: user.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DynamoUserCounter {
+    pk: String,
+    count: u32,
+}
+
+impl TenantScoped for DynamoUsersRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl UsersRepository for DynamoUsersRepository {
+    #[instrument(skip(self))]
+    async fn get_user(&self, id: u32) -> Result<User, GetUserError> {
+        let result = self
+            .dynamo_client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &user_pk(id))),
+            )
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                GetUserError::UnexpectedError
+            })?;
+
+        if let Some(item) = result.item {
+            let user: User = from_item(item)?;
+
+            Ok(user)
+        } else {
+            Err(GetUserError::ResourceNotFound(id.to_string()))
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user_by_email(&self, email: &str) -> Result<User, GetUserError> {
+        let result = self
+            .dynamo_client
+            .query()
+            .table_name(&self.table_name)
+            .index_name("email_index")
+            .key_condition_expression("#email = :email_val")
+            .expression_attribute_names("#email", "email")
+            .filter_expression(tenant_condition(&self.tenant))
+            .expression_attribute_values(":email_val", AttributeValue::S(email.to_string()))
+            .expression_attribute_values(TENANT_PLACEHOLDER, tenant_value(&self.tenant))
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                GetUserError::UnexpectedError
+            })?;
+
+        if let Some(item) = result.items.and_then(|items| items.into_iter().next()) {
+            let user: User = from_item(item)?;
+
+            Ok(user)
+        } else {
+            Err(GetUserError::ResourceNotFound(email.to_string()))
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn create_user(&self, user: User) -> Result<User, CreateUserError> {
+        let item = to_item(UserDynamoItem::new(&self.tenant, &user))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    PutItemError::ConditionalCheckFailedException(_) => {
+                        CreateUserError::ResourceAlreadyExists {
+                            email: user.email.clone(),
+                        }
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        CreateUserError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(user)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_user(&self, id: u32) -> Result<(), DeleteUserError> {
+        self.dynamo_client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &user_pk(id))),
+            )
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    DeleteItemError::ConditionalCheckFailedException(_) => {
+                        DeleteUserError::ResourceNotFound(id)
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        DeleteUserError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_users(&self) -> Result<Vec<User>, ListUsersError> {
+        let mut users = Vec::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression(format!(
+                    "entity_type = :entity_type AND {}",
+                    tenant_condition(&self.tenant)
+                ))
+                .expression_attribute_values(":entity_type", AttributeValue::S("user".to_string()))
+                .expression_attribute_values(TENANT_PLACEHOLDER, tenant_value(&self.tenant))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                    ListUsersError::UnexpectedError
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<User> = from_items(items)?;
+                users.extend(page);
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(users),
+            }
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn update_user(&self, user: User) -> Result<User, UpdateUserError> {
+        let item = to_item(UserDynamoItem::new(&self.tenant, &user))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                UpdateUserError::UnexpectedError
+            })?;
+
+        Ok(user)
+    }
+
+    #[instrument(skip(self))]
+    async fn get_user_id(&self) -> Result<u32, ()> {
+        let result = self
+            .dynamo_client
+            .update_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, "user_counter")),
+            )
+            .update_expression("SET #cnt = if_not_exists(#cnt, :start) + :inc")
+            .expression_attribute_names("#cnt", "count")
+            .expression_attribute_values(":start", AttributeValue::N("0".to_string()))
+            .expression_attribute_values(":inc", AttributeValue::N("1".to_string()))
+            .return_values(ReturnValue::AllNew)
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+            })?;
+
+        if let Some(item) = result.attributes {
+            let counter: DynamoUserCounter = from_item(item).map_err(|_| ())?;
+            Ok(counter.count)
+        } else {
+            Err(())
+        }
+    }
+}
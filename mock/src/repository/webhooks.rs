@@ -0,0 +1,313 @@
+use super::tenancy::{tenant_condition, tenant_pk, tenant_value, TENANT_PLACEHOLDER};
+use super::{
+    CreateWebhookError, DeleteWebhookError, GetWebhookError, ListWebhookDeliveriesError,
+    ListWebhooksError, PutWebhookDeliveryError, UpdateWebhookError, Webhook, WebhookDelivery,
+};
+use std::collections::HashMap;
+
+use async_trait::async_trait;
+use aws_sdk_dynamodb::error::DisplayErrorContext;
+use aws_sdk_dynamodb::operation::delete_item::DeleteItemError;
+use aws_sdk_dynamodb::operation::put_item::PutItemError;
+use aws_sdk_dynamodb::types::AttributeValue;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_dynamo::aws_sdk_dynamodb_1::{from_item, from_items, to_item};
+use tracing::instrument;
+
+use crate::tenant::{Tenant, TenantScoped};
+
+#[async_trait]
+pub trait WebhooksRepository: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    async fn get_webhook(&self, id: &str) -> Result<Webhook, GetWebhookError>;
+    async fn create_webhook(&self, webhook: Webhook) -> Result<Webhook, CreateWebhookError>;
+    async fn delete_webhook(&self, id: &str) -> Result<(), DeleteWebhookError>;
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>, ListWebhooksError>;
+    async fn update_webhook(&self, webhook: Webhook) -> Result<Webhook, UpdateWebhookError>;
+    /// Inserts or replaces a delivery, called again after every attempt
+    async fn put_delivery(&self, delivery: WebhookDelivery) -> Result<(), PutWebhookDeliveryError>;
+    /// Deliveries of one webhook in no particular order
+    async fn list_deliveries(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, ListWebhookDeliveriesError>;
+}
+
+/// Subscriptions are keyed as `webhook#<id>` and deliveries as `delivery#<id>`, prefixed with the
+/// tenant for every tenant but the default one
+#[derive(Debug, Clone)]
+pub struct DynamoWebhooksRepository {
+    pub dynamo_client: aws_sdk_dynamodb::Client,
+    pub table_name: String,
+    pub tenant: Tenant,
+}
+
+impl DynamoWebhooksRepository {
+    #[instrument]
+    pub fn new(dynamo_client: aws_sdk_dynamodb::Client, table_name: String) -> Self {
+        Self {
+            dynamo_client,
+            table_name,
+            tenant: Tenant::default(),
+        }
+    }
+
+    /// Reads every item of an entity type, optionally narrowed to a single webhook
+    #[instrument(skip(self))]
+    async fn scan_entities<T: DeserializeOwned>(
+        &self,
+        entity_type: &str,
+        webhook_id: Option<&str>,
+    ) -> Result<Vec<T>, ()> {
+        let mut filter_expression = format!(
+            "entity_type = :entity_type AND {}",
+            tenant_condition(&self.tenant)
+        );
+        let mut values = HashMap::from([
+            (
+                ":entity_type".to_string(),
+                AttributeValue::S(entity_type.to_string()),
+            ),
+            (TENANT_PLACEHOLDER.to_string(), tenant_value(&self.tenant)),
+        ]);
+
+        if let Some(webhook_id) = webhook_id {
+            filter_expression.push_str(" AND webhookId = :webhook_id");
+            values.insert(
+                ":webhook_id".to_string(),
+                AttributeValue::S(webhook_id.to_string()),
+            );
+        }
+
+        let mut entities = Vec::new();
+        let mut exclusive_start_key: Option<HashMap<String, AttributeValue>> = None;
+
+        loop {
+            let result = self
+                .dynamo_client
+                .scan()
+                .table_name(&self.table_name)
+                .filter_expression(&filter_expression)
+                .set_expression_attribute_values(Some(values.clone()))
+                .set_exclusive_start_key(exclusive_start_key)
+                .send()
+                .await
+                .map_err(|e| {
+                    tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                })?;
+
+            if let Some(items) = result.items {
+                let page: Vec<T> = from_items(items).map_err(|e| {
+                    tracing::error!("{}", e);
+                })?;
+                entities.extend(page);
+            }
+
+            match result.last_evaluated_key {
+                Some(key) => exclusive_start_key = Some(key),
+                None => return Ok(entities),
+            }
+        }
+    }
+}
+
+fn webhook_pk(id: &str) -> String {
+    format!("webhook#{id}")
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebhookDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    webhook: Webhook,
+}
+
+impl WebhookDynamoItem {
+    #[instrument]
+    pub fn new(tenant: &Tenant, webhook: &Webhook) -> Self {
+        Self {
+            pk: tenant_pk(tenant, &webhook_pk(&webhook.id)),
+            entity_type: "webhook".to_string(),
+            webhook: webhook.clone(),
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct WebhookDeliveryDynamoItem {
+    pk: String,
+    entity_type: String,
+
+    #[serde(flatten)]
+    delivery: WebhookDelivery,
+}
+
+impl WebhookDeliveryDynamoItem {
+    #[instrument]
+    pub fn new(tenant: &Tenant, delivery: &WebhookDelivery) -> Self {
+        Self {
+            pk: tenant_pk(tenant, &format!("delivery#{}", delivery.id)),
+            entity_type: "webhook_delivery".to_string(),
+            delivery: delivery.clone(),
+        }
+    }
+}
+
+impl TenantScoped for DynamoWebhooksRepository {
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            tenant: tenant.clone(),
+            ..self.clone()
+        }
+    }
+}
+
+#[async_trait]
+impl WebhooksRepository for DynamoWebhooksRepository {
+    #[instrument(skip(self))]
+    async fn get_webhook(&self, id: &str) -> Result<Webhook, GetWebhookError> {
+        let result = self
+            .dynamo_client
+            .get_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &webhook_pk(id))),
+            )
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                GetWebhookError::UnexpectedError
+            })?;
+
+        if let Some(item) = result.item {
+            let webhook: Webhook = from_item(item)?;
+
+            Ok(webhook)
+        } else {
+            Err(GetWebhookError::ResourceNotFound(id.to_string()))
+        }
+    }
+
+    #[instrument(skip(self))]
+    async fn create_webhook(&self, webhook: Webhook) -> Result<Webhook, CreateWebhookError> {
+        let item = to_item(WebhookDynamoItem::new(&self.tenant, &webhook))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_not_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    PutItemError::ConditionalCheckFailedException(_) => {
+                        CreateWebhookError::ResourceAlreadyExists(webhook.id.clone())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        CreateWebhookError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(webhook)
+    }
+
+    #[instrument(skip(self))]
+    async fn delete_webhook(&self, id: &str) -> Result<(), DeleteWebhookError> {
+        self.dynamo_client
+            .delete_item()
+            .table_name(&self.table_name)
+            .key(
+                "pk",
+                AttributeValue::S(tenant_pk(&self.tenant, &webhook_pk(id))),
+            )
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    DeleteItemError::ConditionalCheckFailedException(_) => {
+                        DeleteWebhookError::ResourceNotFound(id.to_string())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        DeleteWebhookError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_webhooks(&self) -> Result<Vec<Webhook>, ListWebhooksError> {
+        self.scan_entities("webhook", None)
+            .await
+            .map_err(|_| ListWebhooksError::UnexpectedError)
+    }
+
+    #[instrument(skip(self))]
+    async fn update_webhook(&self, webhook: Webhook) -> Result<Webhook, UpdateWebhookError> {
+        let item = to_item(WebhookDynamoItem::new(&self.tenant, &webhook))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .condition_expression("attribute_exists(pk)")
+            .send()
+            .await
+            .map_err(|e| {
+                let err = e.into_service_error();
+
+                match err {
+                    PutItemError::ConditionalCheckFailedException(_) => {
+                        UpdateWebhookError::ResourceNotFound(webhook.id.clone())
+                    }
+                    _ => {
+                        tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(&err));
+                        UpdateWebhookError::UnexpectedError
+                    }
+                }
+            })?;
+
+        Ok(webhook)
+    }
+
+    #[instrument(skip(self))]
+    async fn put_delivery(&self, delivery: WebhookDelivery) -> Result<(), PutWebhookDeliveryError> {
+        let item = to_item(WebhookDeliveryDynamoItem::new(&self.tenant, &delivery))?;
+
+        self.dynamo_client
+            .put_item()
+            .table_name(&self.table_name)
+            .set_item(Some(item))
+            .send()
+            .await
+            .map_err(|e| {
+                tracing::error!("DynamoDB SDK Error: {}", DisplayErrorContext(e));
+                PutWebhookDeliveryError::UnexpectedError
+            })?;
+
+        Ok(())
+    }
+
+    #[instrument(skip(self))]
+    async fn list_deliveries(
+        &self,
+        webhook_id: &str,
+    ) -> Result<Vec<WebhookDelivery>, ListWebhookDeliveriesError> {
+        self.scan_entities("webhook_delivery", Some(webhook_id))
+            .await
+            .map_err(|_| ListWebhookDeliveriesError::UnexpectedError)
+    }
+}
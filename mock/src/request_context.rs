@@ -0,0 +1,63 @@
+use axum::http::HeaderMap;
+use lambda_http::request::RequestContext as LambdaRequestContext;
+use tokio::time::Instant;
+use uuid::Uuid;
+
+use crate::{api_keys_service::Principal, tenant::Tenant};
+
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+pub const ACTOR_HEADER: &str = "x-actor";
+
+/// Who made a request and how to find it in the logs, set by the auth middleware
+#[derive(Debug, Clone)]
+pub struct RequestContext {
+    /// Subject of the API key or token the request was authenticated with
+    pub actor: String,
+    /// Whoever the caller says it acts for with `X-Actor`, recorded but never trusted
+    pub on_behalf_of: Option<String>,
+    pub request_id: String,
+    /// Tenant of the API key the request was made with
+    pub tenant: Tenant,
+    /// When the request was authenticated, every webhook delivery it causes shares one deadline
+    /// counted from here
+    pub received_at: Instant,
+}
+
+impl RequestContext {
+    /// Prefers a caller supplied `X-Request-Id`, then the API Gateway request ID and finally a
+    /// random one so every request can be traced
+    pub fn from_headers(
+        headers: &HeaderMap,
+        lambda_context: Option<&LambdaRequestContext>,
+        principal: &Principal,
+    ) -> Self {
+        let header = |name: &str| {
+            headers
+                .get(name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+
+        let request_id = header(REQUEST_ID_HEADER)
+            .or_else(|| lambda_context.and_then(lambda_request_id))
+            .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+        Self {
+            actor: principal.subject.clone(),
+            on_behalf_of: header(ACTOR_HEADER),
+            request_id,
+            tenant: principal.tenant.clone(),
+            received_at: Instant::now(),
+        }
+    }
+}
+
+fn lambda_request_id(context: &LambdaRequestContext) -> Option<String> {
+    match context {
+        LambdaRequestContext::ApiGatewayV1(context) => context.request_id.clone(),
+        LambdaRequestContext::ApiGatewayV2(context) => context.request_id.clone(),
+        _ => None,
+    }
+}
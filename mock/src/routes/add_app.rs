@@ -1,11 +1,14 @@
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{extract::State, http::StatusCode, Extension, Json};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     api_error::ApiError,
     apps_service::AppsServiceTrait,
     backend::Backend,
-    repository::{AddAppError, AddAppRequest, App},
+    contracts_service::ContractsServiceTrait,
+    repository::{AddAppError, App},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
 };
 
 #[utoipa::path(
@@ -25,11 +28,16 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn add_app<AS: AppsServiceTrait>(
-    State(state): State<Backend<AS>>,
+pub async fn add_app<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
     body: Json<AddAppHttpRequestBody>,
 ) -> Result<(StatusCode, Json<App>), ApiError> {
-    let app = state.apps_service.add_app(body.0).await?;
+    let app = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .add_app(body.0, context)
+        .await?;
 
     Ok((StatusCode::CREATED, Json(app)))
 }
@@ -50,11 +58,3 @@ impl From<AddAppError> for ApiError {
         }
     }
 }
-
-impl From<AddAppHttpRequestBody> for AddAppRequest {
-    fn from(value: AddAppHttpRequestBody) -> Self {
-        Self {
-            id_app: value.id_app,
-        }
-    }
-}
@@ -0,0 +1,172 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::{Deserialize, Serialize};
+use validator::{Validate, ValidationErrors};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{App, BulkAppOperationError},
+    request_context::RequestContext,
+    routes::{AddAppHttpRequestBody, CreateAppHttpRequestBody, UpdateAppHttpRequestBody},
+    users_service::UsersServiceTrait,
+};
+
+/// DynamoDB accepts at most 100 writes in a single transaction
+const MAX_OPERATIONS: u64 = 100;
+
+#[utoipa::path(
+    post,
+    path = "/v1.0/apps/bulk",
+    request_body = BulkAppsHttpRequestBody,
+    responses(
+        (status = OK, description = "Operations were processed, see each result for its outcome", body = BulkAppsHttpResponse),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn bulk_apps<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<BulkAppsHttpRequestBody>,
+) -> Result<(StatusCode, Json<BulkAppsHttpResponse>), ApiError> {
+    body.validate()?;
+    let creates: Vec<bool> = body
+        .operations
+        .iter()
+        .map(|operation| {
+            matches!(
+                operation,
+                BulkAppOperation::Add { .. } | BulkAppOperation::Create { .. }
+            )
+        })
+        .collect();
+
+    let results = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .bulk_apps(body.0.operations, body.0.transactional, context)
+        .await;
+
+    let results: Vec<BulkAppResult> = results
+        .into_iter()
+        .zip(creates)
+        .enumerate()
+        .map(|(index, (result, create))| match result {
+            Ok(app) => BulkAppResult {
+                index,
+                status: if create {
+                    StatusCode::CREATED.as_u16()
+                } else {
+                    StatusCode::OK.as_u16()
+                },
+                app,
+                error: None,
+            },
+            Err(e) => {
+                let error = ApiError::from(e);
+
+                BulkAppResult {
+                    index,
+                    status: error.status_code().as_u16(),
+                    app: None,
+                    error: Some(error.to_string()),
+                }
+            }
+        })
+        .collect();
+
+    let failed = results
+        .iter()
+        .filter(|result| result.error.is_some())
+        .count();
+
+    Ok((
+        StatusCode::OK,
+        Json(BulkAppsHttpResponse {
+            succeeded: results.len() - failed,
+            failed,
+            results,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct BulkAppsHttpRequestBody {
+    /// Apply every operation or none of them
+    #[serde(default)]
+    pub transactional: bool,
+    #[validate(length(min = 1, max = "MAX_OPERATIONS"))]
+    pub operations: Vec<BulkAppOperation>,
+}
+
+/// A single operation, taking the same body as the matching single app route
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+#[serde(tag = "op", rename_all = "lowercase")]
+pub enum BulkAppOperation {
+    Add {
+        app: AddAppHttpRequestBody,
+    },
+    Create {
+        app: CreateAppHttpRequestBody,
+    },
+    Update {
+        id: u16,
+        /// Only update the app while it is still at this version, like `If-Match`
+        version: Option<u64>,
+        app: UpdateAppHttpRequestBody,
+    },
+    Delete {
+        id: u16,
+    },
+}
+
+impl BulkAppOperation {
+    /// Runs the same checks as the matching single app route
+    pub fn validate(&self) -> Result<(), ValidationErrors> {
+        match self {
+            Self::Add { .. } | Self::Delete { .. } => Ok(()),
+            Self::Create { app } => app.validate(),
+            Self::Update { app, .. } => app.validate(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BulkAppsHttpResponse {
+    pub results: Vec<BulkAppResult>,
+    pub succeeded: usize,
+    pub failed: usize,
+}
+
+/// Outcome of the operation at `index`, `status` is what the single app route would have
+/// answered with
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct BulkAppResult {
+    pub index: usize,
+    pub status: u16,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub app: Option<App>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<BulkAppOperationError> for ApiError {
+    fn from(value: BulkAppOperationError) -> Self {
+        match value {
+            BulkAppOperationError::Add(e) => e.into(),
+            BulkAppOperationError::Create(e) => e.into(),
+            BulkAppOperationError::Update(e) => e.into(),
+            BulkAppOperationError::Delete(e) => e.into(),
+            BulkAppOperationError::InvalidRequest(msg) => Self::ValidationError(msg),
+            BulkAppOperationError::DuplicateApp(_) => Self::ValidationError(value.to_string()),
+            BulkAppOperationError::NotApplied(_) => Self::FailedDependency(value.to_string()),
+        }
+    }
+}
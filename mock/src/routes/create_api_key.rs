@@ -0,0 +1,71 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{ApiKeyScope, CreateApiKeyError, CreatedApiKey},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+/// Creates a key for the caller's tenant, the response is the only place its secret appears
+#[utoipa::path(
+    post,
+    path = "/v1.0/admin/api-keys",
+    request_body = CreateApiKeyHttpRequestBody,
+    responses(
+        (status = CREATED, description = "Successfully created API key", body = CreatedApiKey),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn create_api_key<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<CreateApiKeyHttpRequestBody>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), ApiError> {
+    body.validate()?;
+    let api_key = state
+        .api_keys_service
+        .create_api_key(&context.tenant, body.0)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(api_key)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateApiKeyHttpRequestBody {
+    #[validate(length(min = 1, max = 100))]
+    pub name: String,
+    #[validate(length(min = 1))]
+    pub scopes: Vec<ApiKeyScope>,
+    /// The key never expires when left out
+    #[serde(rename = "expiresAt")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<CreateApiKeyError> for ApiError {
+    fn from(value: CreateApiKeyError) -> Self {
+        match value {
+            CreateApiKeyError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            CreateApiKeyError::InvalidExpiry(_) => Self::ValidationError(value.to_string()),
+            CreateApiKeyError::ResourceAlreadyExists(id) => Self::ResourceAlreadyExists(id),
+            CreateApiKeyError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -1,12 +1,18 @@
-use axum::{extract::State, http::StatusCode, Json};
+use std::collections::BTreeMap;
+
+use axum::{extract::State, http::StatusCode, Extension, Json};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use validator::Validate;
 
 use crate::{
     api_error::ApiError,
     apps_service::AppsServiceTrait,
     backend::Backend,
+    contracts_service::ContractsServiceTrait,
     repository::{App, AppCategory, AppState, CreateAppError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
 };
 
 #[utoipa::path(
@@ -26,12 +32,17 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn create_app<AS: AppsServiceTrait>(
-    State(state): State<Backend<AS>>,
+pub async fn create_app<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
     body: Json<CreateAppHttpRequestBody>,
 ) -> Result<(StatusCode, Json<App>), ApiError> {
     body.validate()?;
-    let app = state.apps_service.create_app(body.0).await?;
+    let app = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .create_app(body.0, context)
+        .await?;
 
     Ok((StatusCode::CREATED, Json(app)))
 }
@@ -45,6 +56,9 @@ pub struct CreateAppHttpRequestBody {
     pub category: AppCategory,
     pub description: Option<String>,
     pub tags: Option<String>,
+    /// Custom field values keyed by field key, see `/v1.0/apps/fields`
+    #[schema(value_type = Option<Object>)]
+    pub fields: Option<BTreeMap<String, Value>>,
 }
 
 impl From<CreateAppError> for ApiError {
@@ -52,6 +66,7 @@ impl From<CreateAppError> for ApiError {
         match value {
             CreateAppError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
             CreateAppError::ResourceAlreadyExists { name } => Self::ResourceAlreadyExists(name),
+            CreateAppError::InvalidCustomField(msg) => Self::ValidationError(msg),
             CreateAppError::UnexpectedError => Self::InternalServerError,
         }
     }
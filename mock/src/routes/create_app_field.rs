@@ -0,0 +1,71 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{AppField, AppFieldType, CreateAppFieldError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1.0/apps/fields",
+    request_body = CreateAppFieldHttpRequestBody,
+    responses(
+        (status = CREATED, description = "Successfully created app field", body = AppField),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = CONFLICT, description = "Field already exists", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn create_app_field<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<CreateAppFieldHttpRequestBody>,
+) -> Result<(StatusCode, Json<AppField>), ApiError> {
+    body.validate()?;
+    let field = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .create_app_field(body.0)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(field)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateAppFieldHttpRequestBody {
+    /// Used as the property name in `App.fields`, can't be changed later
+    pub key: String,
+    #[validate(length(min = 1))]
+    pub name: String,
+    #[serde(rename = "fieldType")]
+    pub field_type: AppFieldType,
+    /// Required for dropdown fields
+    pub options: Option<Vec<String>>,
+}
+
+impl From<CreateAppFieldError> for ApiError {
+    fn from(value: CreateAppFieldError) -> Self {
+        match value {
+            CreateAppFieldError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            CreateAppFieldError::InvalidField(msg) => Self::ValidationError(msg),
+            CreateAppFieldError::ResourceAlreadyExists(key) => Self::ResourceAlreadyExists(key),
+            CreateAppFieldError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
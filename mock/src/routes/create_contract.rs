@@ -0,0 +1,85 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{Contract, CreateContractError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1.0/contracts",
+    request_body = CreateContractHttpRequestBody,
+    responses(
+        (status = CREATED, description = "Successfully created contract", body = Contract),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn create_contract<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<CreateContractHttpRequestBody>,
+) -> Result<(StatusCode, Json<Contract>), ApiError> {
+    body.validate()?;
+    let contract = state
+        .contracts_service
+        .for_tenant(&context.tenant)
+        .create_contract(body.0)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(contract)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateContractHttpRequestBody {
+    #[serde(rename = "appId")]
+    pub app_id: u16,
+    #[validate(range(min = 0.0))]
+    pub amount: f64,
+    /// ISO 4217 code, case insensitive
+    #[validate(length(equal = 3))]
+    pub currency: String,
+    #[serde(rename = "startDate")]
+    pub start_date: NaiveDate,
+    #[serde(rename = "endDate")]
+    pub end_date: Option<NaiveDate>,
+    #[serde(rename = "renewalDate")]
+    pub renewal_date: Option<NaiveDate>,
+    /// Defaults to `false`
+    #[serde(rename = "autoRenew")]
+    pub auto_renew: Option<bool>,
+    #[validate(length(min = 1))]
+    pub owner: String,
+}
+
+impl From<CreateContractError> for ApiError {
+    fn from(value: CreateContractError) -> Self {
+        match value {
+            CreateContractError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            CreateContractError::AppNotFound(_) | CreateContractError::InvalidContract(_) => {
+                Self::ValidationError(value.to_string())
+            }
+            CreateContractError::ResourceAlreadyExists(id) => {
+                Self::ResourceAlreadyExists(id.to_string())
+            }
+            CreateContractError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
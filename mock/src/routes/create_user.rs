@@ -0,0 +1,69 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{CreateUserError, User, UserLifecycleStatus},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1.0/users",
+    request_body = CreateUserHttpRequestBody,
+    responses(
+        (status = CREATED, description = "Successfully created user", body = User),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = CONFLICT, description = "User already exists", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn create_user<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<CreateUserHttpRequestBody>,
+) -> Result<(StatusCode, Json<User>), ApiError> {
+    body.validate()?;
+    let user = state
+        .users_service
+        .for_tenant(&context.tenant)
+        .create_user(body.0)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(user)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateUserHttpRequestBody {
+    #[serde(rename = "firstName")]
+    pub first_name: String,
+    #[serde(rename = "lastName")]
+    pub last_name: String,
+    #[validate(email)]
+    pub email: String,
+    /// Defaults to `active`
+    #[serde(rename = "lifecycleStatus")]
+    pub lifecycle_status: Option<UserLifecycleStatus>,
+    #[serde(rename = "isExternal")]
+    pub is_external: Option<bool>,
+}
+
+impl From<CreateUserError> for ApiError {
+    fn from(value: CreateUserError) -> Self {
+        match value {
+            CreateUserError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            CreateUserError::ResourceAlreadyExists { email } => Self::ResourceAlreadyExists(email),
+            CreateUserError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,71 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{CreateWebhookError, Webhook, WebhookEventType},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    post,
+    path = "/v1.0/webhooks",
+    request_body = CreateWebhookHttpRequestBody,
+    responses(
+        (status = CREATED, description = "Successfully created webhook", body = Webhook),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = CONFLICT, description = "Webhook already exists", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn create_webhook<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<CreateWebhookHttpRequestBody>,
+) -> Result<(StatusCode, Json<Webhook>), ApiError> {
+    body.validate()?;
+    let webhook = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .create_webhook(body.0)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct CreateWebhookHttpRequestBody {
+    #[validate(url)]
+    pub url: String,
+    #[validate(length(min = 1))]
+    pub events: Vec<WebhookEventType>,
+    /// Generated when left out, either way it is returned in the response
+    #[validate(length(min = 16))]
+    pub secret: Option<String>,
+    /// Defaults to `true`
+    pub active: Option<bool>,
+}
+
+impl From<CreateWebhookError> for ApiError {
+    fn from(value: CreateWebhookError) -> Self {
+        match value {
+            CreateWebhookError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            CreateWebhookError::ResourceAlreadyExists(id) => Self::ResourceAlreadyExists(id),
+            CreateWebhookError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, repository::DeleteApiKeyError,
+    request_context::RequestContext, users_service::UsersServiceTrait,
+};
+
+/// Revokes the key, requests made with it are rejected right away
+#[utoipa::path(
+    delete,
+    path = "/v1.0/admin/api-keys/{id}",
+    params(DeleteApiKeyPathContent),
+    responses(
+        (status = OK, description = "Successfully deleted API key", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_api_key<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(DeleteApiKeyPathContent { id }): Path<DeleteApiKeyPathContent>,
+) -> Result<(StatusCode, Json<String>), ApiError> {
+    state
+        .api_keys_service
+        .delete_api_key(&context.tenant, &id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(format!("API key {} deleted", id))))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct DeleteApiKeyPathContent {
+    pub id: String,
+}
+
+impl From<DeleteApiKeyError> for ApiError {
+    fn from(value: DeleteApiKeyError) -> Self {
+        match value {
+            DeleteApiKeyError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            DeleteApiKeyError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            DeleteApiKeyError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -1,15 +1,17 @@
 use axum::{
     extract::{Path, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
     api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
-    repository::DeleteAppError,
+    contracts_service::ContractsServiceTrait, repository::DeleteAppError,
+    request_context::RequestContext, users_service::UsersServiceTrait,
 };
 
+/// Also removes the app's user links and contracts
 #[utoipa::path(
     delete,
     path = "/v1.0/apps/{id}",
@@ -26,11 +28,16 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn delete_app<AS: AppsServiceTrait>(
-    State(state): State<Backend<AS>>,
+pub async fn delete_app<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
     Path(DeleteAppPathContent { id }): Path<DeleteAppPathContent>,
+    Extension(context): Extension<RequestContext>,
 ) -> Result<(StatusCode, Json<String>), ApiError> {
-    state.apps_service.delete_app(id).await?;
+    state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .delete_app(id, context)
+        .await?;
 
     Ok((StatusCode::OK, Json(format!("App {} deleted", id))))
 }
@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, repository::DeleteAppFieldError,
+    request_context::RequestContext, users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/v1.0/apps/fields/{key}",
+    params(DeleteAppFieldPathContent),
+    responses(
+        (status = OK, description = "Successfully deleted app field", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_app_field<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(DeleteAppFieldPathContent { key }): Path<DeleteAppFieldPathContent>,
+) -> Result<(StatusCode, Json<String>), ApiError> {
+    state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .delete_app_field(&key, context)
+        .await?;
+
+    Ok((StatusCode::OK, Json(format!("Field {} deleted", key))))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct DeleteAppFieldPathContent {
+    pub key: String,
+}
+
+impl From<DeleteAppFieldError> for ApiError {
+    fn from(value: DeleteAppFieldError) -> Self {
+        match value {
+            DeleteAppFieldError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            DeleteAppFieldError::ResourceNotFound(key) => Self::ResourceNotFound(key),
+            DeleteAppFieldError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
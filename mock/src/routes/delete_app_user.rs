@@ -0,0 +1,67 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, repository::DeleteAppUserError,
+    request_context::RequestContext, users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/v1.0/apps/{id}/users/{user_id}",
+    params(DeleteAppUserPathContent),
+    responses(
+        (status = OK, description = "Successfully unlinked user from app", body = String),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_app_user<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(DeleteAppUserPathContent { id, user_id }): Path<DeleteAppUserPathContent>,
+) -> Result<(StatusCode, Json<String>), ApiError> {
+    state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .delete_app_user(id, user_id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(format!("User {} removed from app {}", user_id, id)),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct DeleteAppUserPathContent {
+    pub id: u16,
+    pub user_id: u32,
+}
+
+impl From<DeleteAppUserError> for ApiError {
+    fn from(value: DeleteAppUserError) -> Self {
+        match value {
+            DeleteAppUserError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            DeleteAppUserError::ResourceNotFound { .. } => {
+                Self::ResourceNotFound(value.to_string())
+            }
+            DeleteAppUserError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
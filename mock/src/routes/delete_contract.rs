@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, repository::DeleteContractError,
+    request_context::RequestContext, users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/v1.0/contracts/{id}",
+    params(DeleteContractPathContent),
+    responses(
+        (status = OK, description = "Successfully deleted contract", body = String),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_contract<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(DeleteContractPathContent { id }): Path<DeleteContractPathContent>,
+) -> Result<(StatusCode, Json<String>), ApiError> {
+    state
+        .contracts_service
+        .for_tenant(&context.tenant)
+        .delete_contract(id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(format!("Contract {} deleted", id))))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct DeleteContractPathContent {
+    pub id: u32,
+}
+
+impl From<DeleteContractError> for ApiError {
+    fn from(value: DeleteContractError) -> Self {
+        match value {
+            DeleteContractError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            DeleteContractError::ResourceNotFound(id) => Self::ResourceNotFound(id.to_string()),
+            DeleteContractError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
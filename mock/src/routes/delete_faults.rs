@@ -0,0 +1,33 @@
+use axum::{extract::State, http::StatusCode, Json};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, faults::FaultConfig,
+    users_service::UsersServiceTrait,
+};
+
+/// Stops injecting faults
+#[utoipa::path(
+    delete,
+    path = "/v1.0/admin/faults",
+    responses(
+        (status = OK, description = "Successfully removed faults", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope or isn't of the default tenant", body = String),
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_faults<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+) -> Result<(StatusCode, Json<String>), ApiError> {
+    state.faults.set_config(FaultConfig::default());
+
+    Ok((StatusCode::OK, Json("Faults removed".to_string())))
+}
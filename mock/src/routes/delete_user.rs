@@ -0,0 +1,57 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, repository::DeleteUserError,
+    request_context::RequestContext, users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/v1.0/users/{id}",
+    params(DeleteUserPathContent),
+    responses(
+        (status = OK, description = "Successfully deleted user", body = String),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_user<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(DeleteUserPathContent { id }): Path<DeleteUserPathContent>,
+) -> Result<(StatusCode, Json<String>), ApiError> {
+    state
+        .users_service
+        .for_tenant(&context.tenant)
+        .delete_user(id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(format!("User {} deleted", id))))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct DeleteUserPathContent {
+    pub id: u32,
+}
+
+impl From<DeleteUserError> for ApiError {
+    fn from(value: DeleteUserError) -> Self {
+        match value {
+            DeleteUserError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            DeleteUserError::ResourceNotFound(id) => Self::ResourceNotFound(id.to_string()),
+            DeleteUserError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, repository::DeleteWebhookError,
+    request_context::RequestContext, users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    delete,
+    path = "/v1.0/webhooks/{id}",
+    params(DeleteWebhookPathContent),
+    responses(
+        (status = OK, description = "Successfully deleted webhook", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn delete_webhook<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(DeleteWebhookPathContent { id }): Path<DeleteWebhookPathContent>,
+) -> Result<(StatusCode, Json<String>), ApiError> {
+    state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .delete_webhook(&id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(format!("Webhook {} deleted", id))))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct DeleteWebhookPathContent {
+    pub id: String,
+}
+
+impl From<DeleteWebhookError> for ApiError {
+    fn from(value: DeleteWebhookError) -> Self {
+        match value {
+            DeleteWebhookError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            DeleteWebhookError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            DeleteWebhookError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::sync::Arc;
+
+use axum::{
+    extract::{Query, State},
+    http::{header, HeaderName, StatusCode},
+    Extension,
+};
+use futures_util::StreamExt;
+use serde::{Deserialize, Serialize};
+use tracing::error;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    export::{export_apps as export_stream, ExportFormat, MAX_EXPORT_BYTES},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+/// `Content-Type` and `Content-Disposition` of the export
+type ExportHeaders = [(HeaderName, String); 2];
+
+/// Every app including custom fields and user counts, the catalog is read a page at a time.
+/// Lambda buffers responses and caps them at 6 MB, so exports over 5 MB are refused and the
+/// catalog has to be paged through `/v1.0/apps` instead
+#[utoipa::path(
+    get,
+    path = "/v1.0/apps/export",
+    params(ExportAppsQueryParams),
+    responses(
+        (status = OK, description = "Successfully exported apps", content(
+            ("application/json" = Vec<App>),
+            ("application/x-ndjson" = String),
+            ("text/csv" = String),
+        )),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = PAYLOAD_TOO_LARGE, description = "Export is larger than 5 MB", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn export_apps<
+    AS: AppsServiceTrait + 'static,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Query(params): Query<ExportAppsQueryParams>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, ExportHeaders, Vec<u8>), ApiError> {
+    let format = params.format.unwrap_or_default();
+    let apps_service = state.apps_service.for_tenant(&context.tenant);
+
+    // CSV needs every column up front, so the custom field definitions are read first
+    let field_keys = match format {
+        ExportFormat::Csv => apps_service
+            .list_app_fields()
+            .await?
+            .into_iter()
+            .map(|field| field.key)
+            .collect(),
+        _ => Vec::new(),
+    };
+
+    let headers = [
+        (header::CONTENT_TYPE, format.content_type().to_string()),
+        (
+            header::CONTENT_DISPOSITION,
+            format!("attachment; filename=\"apps.{}\"", format.extension()),
+        ),
+    ];
+
+    let mut export = Box::pin(export_stream(Arc::new(apps_service), format, field_keys));
+    let mut body = Vec::new();
+
+    while let Some(chunk) = export.next().await {
+        let chunk = chunk.map_err(|e| {
+            error!("{}", e);
+            ApiError::InternalServerError
+        })?;
+        body.extend_from_slice(&chunk);
+
+        if body.len() > MAX_EXPORT_BYTES {
+            return Err(ApiError::ResponseTooLarge(format!(
+                "Export is larger than {} MB, page through /v1.0/apps instead",
+                MAX_EXPORT_BYTES / 1024 / 1024
+            )));
+        }
+    }
+
+    Ok((StatusCode::OK, headers, body))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct ExportAppsQueryParams {
+    /// Defaults to `json`
+    pub format: Option<ExportFormat>,
+}
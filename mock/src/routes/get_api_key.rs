@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{ApiKey, GetApiKeyError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/admin/api-keys/{id}",
+    params(GetApiKeyPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved API key", body = ApiKey),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_api_key<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(GetApiKeyPathContent { id }): Path<GetApiKeyPathContent>,
+) -> Result<(StatusCode, Json<ApiKey>), ApiError> {
+    let api_key = state
+        .api_keys_service
+        .get_api_key(&context.tenant, &id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(api_key)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct GetApiKeyPathContent {
+    pub id: String,
+}
+
+impl From<GetApiKeyError> for ApiError {
+    fn from(value: GetApiKeyError) -> Self {
+        match value {
+            GetApiKeyError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            GetApiKeyError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            GetApiKeyError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
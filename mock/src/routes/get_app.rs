@@ -1,23 +1,30 @@
 use axum::{
-    extract::{Path, State},
+    extract::{Path, Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 
 use crate::{
     api_error::ApiError,
     apps_service::AppsServiceTrait,
     backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    etag::{etag, ETagHeader},
+    field_selection::FieldSelection,
     repository::{App, GetAppError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
 };
 
 #[utoipa::path(
     get,
     path = "/v1.0/apps/{id}",
-    params(GetAppPathContent),
+    params(GetAppPathContent, GetAppQueryParams),
     responses(
-        (status = OK, description = "Successfully retrieved app", body = App),
+        (status = OK, description = "Successfully retrieved app", body = App,
+            headers(("ETag" = String, description = "Current version of the app, send it back as `If-Match` when updating"))),
         (status = BAD_REQUEST, description = "Bad Request", body = String),
         (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
         (status = NOT_FOUND, description = "Not found", body = String),
@@ -28,13 +35,24 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn get_app<AS: AppsServiceTrait>(
-    State(state): State<Backend<AS>>,
+pub async fn get_app<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
     Path(GetAppPathContent { id }): Path<GetAppPathContent>,
-) -> Result<(StatusCode, Json<App>), ApiError> {
-    let app = state.apps_service.get_app(id).await?;
+    Query(params): Query<GetAppQueryParams>,
+) -> Result<(StatusCode, ETagHeader, Json<Value>), ApiError> {
+    let app: App = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .get_app(id)
+        .await?;
+    let fields = FieldSelection::parse(params.fields.as_deref());
 
-    Ok((StatusCode::OK, Json(app)))
+    Ok((
+        StatusCode::OK,
+        etag(app.version),
+        Json(fields.project(&app)?),
+    ))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
@@ -42,6 +60,14 @@ pub struct GetAppPathContent {
     pub id: u16,
 }
 
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+#[into_params(parameter_in = Query)]
+pub struct GetAppQueryParams {
+    /// Comma separated list of fields to return, `id` is always included. Custom fields are
+    /// selected as `fields.<key>`, `fields` returns all of them
+    pub fields: Option<String>,
+}
+
 impl From<GetAppError> for ApiError {
     fn from(value: GetAppError) -> Self {
         match value {
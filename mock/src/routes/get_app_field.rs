@@ -0,0 +1,64 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{AppField, GetAppFieldError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/apps/fields/{key}",
+    params(GetAppFieldPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved app field", body = AppField),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_app_field<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(GetAppFieldPathContent { key }): Path<GetAppFieldPathContent>,
+) -> Result<(StatusCode, Json<AppField>), ApiError> {
+    let field = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .get_app_field(&key)
+        .await?;
+
+    Ok((StatusCode::OK, Json(field)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct GetAppFieldPathContent {
+    pub key: String,
+}
+
+impl From<GetAppFieldError> for ApiError {
+    fn from(value: GetAppFieldError) -> Self {
+        match value {
+            GetAppFieldError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            GetAppFieldError::ResourceNotFound(key) => Self::ResourceNotFound(key),
+            GetAppFieldError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{Contract, GetContractError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/contracts/{id}",
+    params(GetContractPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved contract", body = Contract),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_contract<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(GetContractPathContent { id }): Path<GetContractPathContent>,
+) -> Result<(StatusCode, Json<Contract>), ApiError> {
+    let contract = state
+        .contracts_service
+        .for_tenant(&context.tenant)
+        .get_contract(id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(contract)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct GetContractPathContent {
+    pub id: u32,
+}
+
+impl From<GetContractError> for ApiError {
+    fn from(value: GetContractError) -> Self {
+        match value {
+            GetContractError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            GetContractError::ResourceNotFound(id) => Self::ResourceNotFound(id.to_string()),
+            GetContractError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
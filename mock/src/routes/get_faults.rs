@@ -0,0 +1,27 @@
+use axum::{extract::State, http::StatusCode, Json};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, faults::FaultConfig,
+    users_service::UsersServiceTrait,
+};
+
+/// Faults currently injected into requests
+#[utoipa::path(
+    get,
+    path = "/v1.0/admin/faults",
+    responses(
+        (status = OK, description = "Successfully retrieved faults", body = FaultConfig),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope or isn't of the default tenant", body = String),
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_faults<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+) -> Result<(StatusCode, Json<FaultConfig>), ApiError> {
+    Ok((StatusCode::OK, Json(state.faults.config())))
+}
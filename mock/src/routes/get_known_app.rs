@@ -0,0 +1,53 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend, catalog::KnownApp,
+    contracts_service::ContractsServiceTrait, repository::GetKnownAppError,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/apps/known/{id}",
+    params(GetKnownAppPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved known app", body = KnownApp),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_known_app<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Path(GetKnownAppPathContent { id }): Path<GetKnownAppPathContent>,
+) -> Result<(StatusCode, Json<KnownApp>), ApiError> {
+    let app = state.apps_service.get_known_app(id).await?;
+
+    Ok((StatusCode::OK, Json(app)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct GetKnownAppPathContent {
+    pub id: u16,
+}
+
+impl From<GetKnownAppError> for ApiError {
+    fn from(value: GetKnownAppError) -> Self {
+        match value {
+            GetKnownAppError::ResourceNotFound(id) => Self::ResourceNotFound(id.to_string()),
+        }
+    }
+}
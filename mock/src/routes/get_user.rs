@@ -0,0 +1,61 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{GetUserError, User},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/users/{id}",
+    params(GetUserPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved user", body = User),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_user<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(GetUserPathContent { id }): Path<GetUserPathContent>,
+) -> Result<(StatusCode, Json<User>), ApiError> {
+    let user = state
+        .users_service
+        .for_tenant(&context.tenant)
+        .get_user(id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(user)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct GetUserPathContent {
+    pub id: u32,
+}
+
+impl From<GetUserError> for ApiError {
+    fn from(value: GetUserError) -> Self {
+        match value {
+            GetUserError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            GetUserError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            GetUserError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
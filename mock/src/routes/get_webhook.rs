@@ -0,0 +1,60 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{GetWebhookError, RedactedWebhook},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/webhooks/{id}",
+    params(GetWebhookPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved webhook", body = RedactedWebhook),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn get_webhook<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(GetWebhookPathContent { id }): Path<GetWebhookPathContent>,
+) -> Result<(StatusCode, Json<RedactedWebhook>), ApiError> {
+    let webhook = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .get_webhook(&id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(webhook.into())))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct GetWebhookPathContent {
+    pub id: String,
+}
+
+impl From<GetWebhookError> for ApiError {
+    fn from(value: GetWebhookError) -> Self {
+        match value {
+            GetWebhookError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            GetWebhookError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            GetWebhookError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use std::collections::BTreeMap;
+
+use axum::{
+    extract::{Multipart, Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    import::{read_sheet, ImportFormat, ImportRowResult, ImportRowStatus},
+    repository::ImportAppsError,
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+/// Upload a CSV or XLSX file as the `file` part. The first row holds the column headers and
+/// every following row becomes a custom app
+#[utoipa::path(
+    post,
+    path = "/v1.0/apps/import",
+    params(ImportAppsQueryParams),
+    request_body(content = ImportAppsForm, content_type = "multipart/form-data"),
+    responses(
+        (status = OK, description = "Successfully processed the file", body = ImportAppsHttpResponse),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = UNSUPPORTED_MEDIA_TYPE, description = "File is neither CSV nor XLSX", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state, multipart))]
+pub async fn import_apps<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    Query(params): Query<ImportAppsQueryParams>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    mut multipart: Multipart,
+) -> Result<(StatusCode, Json<ImportAppsHttpResponse>), ApiError> {
+    let invalid =
+        |e: axum::extract::multipart::MultipartError| ApiError::ValidationError(e.body_text());
+
+    let mut sheet = None;
+    let mut mapping = BTreeMap::new();
+
+    while let Some(field) = multipart.next_field().await.map_err(invalid)? {
+        match field.name() {
+            Some("file") => {
+                let format = ImportFormat::detect(field.content_type(), field.file_name())
+                    .ok_or_else(|| {
+                        ApiError::UnsupportedMediaType("Upload a .csv or .xlsx file".into())
+                    })?;
+                let bytes = field.bytes().await.map_err(invalid)?;
+
+                sheet = Some(read_sheet(format, &bytes).map_err(ApiError::ValidationError)?);
+            }
+            Some("mapping") => {
+                let text = field.text().await.map_err(invalid)?;
+
+                mapping = serde_json::from_str(&text).map_err(|e| {
+                    ApiError::ValidationError(format!("Invalid column mapping: {e}"))
+                })?;
+            }
+            _ => {}
+        }
+    }
+
+    let sheet = sheet.ok_or_else(|| ApiError::ValidationError("Missing file part".into()))?;
+    let rows = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .import_apps(sheet, mapping, params.dry_run, context)
+        .await?;
+
+    let count = |status| rows.iter().filter(|row| row.status == status).count();
+
+    Ok((
+        StatusCode::OK,
+        Json(ImportAppsHttpResponse {
+            dry_run: params.dry_run,
+            created: count(ImportRowStatus::Created),
+            valid: count(ImportRowStatus::Valid),
+            duplicates: count(ImportRowStatus::Duplicate),
+            invalid: count(ImportRowStatus::Invalid),
+            failed: count(ImportRowStatus::Failed),
+            rows,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct ImportAppsQueryParams {
+    /// Check every row and report what would happen without creating any apps
+    #[serde(rename = "dryRun", default)]
+    pub dry_run: bool,
+}
+
+/// Only used to document the multipart body
+#[allow(dead_code)]
+#[derive(utoipa::ToSchema)]
+pub struct ImportAppsForm {
+    #[schema(value_type = String, format = Binary)]
+    pub file: Vec<u8>,
+    /// JSON object from app attribute (`name`, `state`, `url`, `category`, `description`,
+    /// `tags` or `fields.<key>`) to column header. Attributes left out are read from a column
+    /// with the same name
+    #[schema(value_type = Option<Object>)]
+    pub mapping: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ImportAppsHttpResponse {
+    #[serde(rename = "dryRun")]
+    pub dry_run: bool,
+    pub rows: Vec<ImportRowResult>,
+    pub created: usize,
+    pub valid: usize,
+    pub duplicates: usize,
+    pub invalid: usize,
+    pub failed: usize,
+}
+
+impl From<ImportAppsError> for ApiError {
+    fn from(value: ImportAppsError) -> Self {
+        match value {
+            ImportAppsError::InvalidMapping(msg) => Self::ValidationError(msg),
+            ImportAppsError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
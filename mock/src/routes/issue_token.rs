@@ -0,0 +1,153 @@
+use axum::{
+    extract::{rejection::FormRejection, State},
+    http::{header, HeaderMap, StatusCode},
+    response::{IntoResponse, Response},
+    Form, Json,
+};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use percent_encoding::percent_decode_str;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::{
+    apps_service::AppsServiceTrait, backend::Backend, contracts_service::ContractsServiceTrait,
+    jwt::join_scopes, repository::IssueTokenError, users_service::UsersServiceTrait,
+};
+
+/// OAuth 2.0 client-credentials grant. The client authenticates with HTTP Basic or with
+/// `client_id` and `client_secret` in the form, and gets a JWT to send as bearer token
+#[utoipa::path(
+    post,
+    path = "/oauth/token",
+    request_body(content = IssueTokenForm, content_type = "application/x-www-form-urlencoded"),
+    responses(
+        (status = OK, description = "Successfully issued token", body = IssueTokenHttpResponse),
+        (status = BAD_REQUEST, description = "Invalid request, grant type or scope", body = String),
+        (status = UNAUTHORIZED, description = "Unknown client or wrong secret", body = String),
+        (status = TOO_MANY_REQUESTS, description = "Rate limit of the client exceeded", body = String),
+        (status = SERVICE_UNAVAILABLE, description = "JWT_SECRET is not set", body = String),
+    )
+)]
+#[tracing::instrument(skip(state, headers, form))]
+pub async fn issue_token<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    State(state): State<Backend<AS, US, CS>>,
+    headers: HeaderMap,
+    form: Result<Form<IssueTokenForm>, FormRejection>,
+) -> Result<Response, IssueTokenError> {
+    let Form(form) = form.map_err(|e| IssueTokenError::InvalidRequest(e.body_text()))?;
+
+    if form.grant_type != "client_credentials" {
+        return Err(IssueTokenError::UnsupportedGrantType(form.grant_type));
+    }
+
+    let (client_id, client_secret) = match basic_credentials(&headers)? {
+        Some(credentials) => credentials,
+        None => match (form.client_id, form.client_secret) {
+            (Some(id), Some(secret)) => (id, secret),
+            _ => return Err(IssueTokenError::InvalidClient),
+        },
+    };
+
+    // Limited by the client it claims to be, so guessing a secret counts against the limit too
+    let decision = state
+        .rate_limiter
+        .as_ref()
+        .map(|limiter| limiter.check(&format!("oauth/{client_id}")));
+    if let Some(rejection) = decision.as_ref().and_then(|decision| decision.rejection()) {
+        return Ok(rejection);
+    }
+
+    let jwt = state
+        .jwt
+        .as_ref()
+        .ok_or(IssueTokenError::SigningUnavailable)?;
+    let token = jwt.issue_token(&client_id, &client_secret, form.scope.as_deref())?;
+
+    let body = IssueTokenHttpResponse {
+        access_token: token.access_token,
+        token_type: "Bearer".to_string(),
+        expires_in: token.expires_in,
+        scope: join_scopes(&token.scopes),
+    };
+
+    let mut response = ([(header::CACHE_CONTROL, "no-store")], Json(body)).into_response();
+    if let Some(decision) = decision {
+        decision.insert_headers(response.headers_mut());
+    }
+
+    Ok(response)
+}
+
+/// `Authorization: Basic base64(client_id:client_secret)`, when present. Both are form
+/// encoded before they are joined (RFC 6749 section 2.3.1), so a `:` in the ID arrives as `%3A`
+fn basic_credentials(headers: &HeaderMap) -> Result<Option<(String, String)>, IssueTokenError> {
+    let Some(encoded) = headers
+        .get(header::AUTHORIZATION)
+        .and_then(|header| header.to_str().ok())
+        .and_then(|header| header.strip_prefix("Basic "))
+    else {
+        return Ok(None);
+    };
+
+    let decoded = STANDARD
+        .decode(encoded.trim())
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .ok_or(IssueTokenError::InvalidClient)?;
+
+    let (id, secret) = decoded
+        .split_once(':')
+        .ok_or(IssueTokenError::InvalidClient)?;
+
+    Ok(Some((form_decode(id)?, form_decode(secret)?)))
+}
+
+fn form_decode(value: &str) -> Result<String, IssueTokenError> {
+    percent_decode_str(&value.replace('+', " "))
+        .decode_utf8()
+        .map(|decoded| decoded.into_owned())
+        .map_err(|_| IssueTokenError::InvalidClient)
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IssueTokenForm {
+    /// Only `client_credentials` is supported
+    pub grant_type: String,
+    pub client_id: Option<String>,
+    pub client_secret: Option<String>,
+    /// Space separated, every scope of the client when left out
+    pub scope: Option<String>,
+}
+
+/// Shaped as in RFC 6749 rather than like the rest of the API
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct IssueTokenHttpResponse {
+    pub access_token: String,
+    pub token_type: String,
+    pub expires_in: u32,
+    pub scope: String,
+}
+
+/// OAuth clients expect the RFC 6749 error codes rather than the usual `ApiError` body
+impl IntoResponse for IssueTokenError {
+    fn into_response(self) -> Response {
+        let (status, code) = match self {
+            IssueTokenError::InvalidRequest(_) => (StatusCode::BAD_REQUEST, "invalid_request"),
+            IssueTokenError::InvalidClient => (StatusCode::UNAUTHORIZED, "invalid_client"),
+            IssueTokenError::UnsupportedGrantType(_) => {
+                (StatusCode::BAD_REQUEST, "unsupported_grant_type")
+            }
+            IssueTokenError::InvalidScope(_) => (StatusCode::BAD_REQUEST, "invalid_scope"),
+            IssueTokenError::SigningUnavailable => {
+                (StatusCode::SERVICE_UNAVAILABLE, "temporarily_unavailable")
+            }
+        };
+
+        let body = json!({
+            "error": code,
+            "error_description": self.to_string(),
+        });
+
+        (status, [(header::CACHE_CONTROL, "no-store")], Json(body)).into_response()
+    }
+}
@@ -0,0 +1,65 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{ApiKey, ListApiKeysError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+/// Keys of the caller's tenant, secrets are never included
+#[utoipa::path(
+    get,
+    path = "/v1.0/admin/api-keys",
+    responses(
+        (status = OK, description = "Successfully retrieved API keys", body = ListApiKeysHttpResponse),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_api_keys<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<ListApiKeysHttpResponse>), ApiError> {
+    let api_keys = state
+        .api_keys_service
+        .list_api_keys(&context.tenant)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListApiKeysHttpResponse {
+            count: api_keys.len(),
+            api_keys,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListApiKeysHttpResponse {
+    #[serde(rename = "apiKeys")]
+    pub api_keys: Vec<ApiKey>,
+    pub count: usize,
+}
+
+impl From<ListApiKeysError> for ApiError {
+    fn from(value: ListApiKeysError) -> Self {
+        match value {
+            ListApiKeysError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            ListApiKeysError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
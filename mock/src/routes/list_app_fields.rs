@@ -0,0 +1,63 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{AppField, ListAppFieldsError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/apps/fields",
+    responses(
+        (status = OK, description = "Successfully retrieved app fields", body = ListAppFieldsHttpResponse),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_app_fields<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<ListAppFieldsHttpResponse>), ApiError> {
+    let fields = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .list_app_fields()
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListAppFieldsHttpResponse {
+            count: fields.len(),
+            fields,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListAppFieldsHttpResponse {
+    pub fields: Vec<AppField>,
+    pub count: usize,
+}
+
+impl From<ListAppFieldsError> for ApiError {
+    fn from(value: ListAppFieldsError) -> Self {
+        match value {
+            ListAppFieldsError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            ListAppFieldsError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,77 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::{AppUserDetails, AppsServiceTrait},
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::ListAppUsersError,
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/apps/{id}/users",
+    params(ListAppUsersPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved app users", body = ListAppUsersHttpResponse),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_app_users<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(ListAppUsersPathContent { id }): Path<ListAppUsersPathContent>,
+) -> Result<(StatusCode, Json<ListAppUsersHttpResponse>), ApiError> {
+    let users = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .list_app_users(id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListAppUsersHttpResponse {
+            count: users.len(),
+            users,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct ListAppUsersPathContent {
+    pub id: u16,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListAppUsersHttpResponse {
+    pub users: Vec<AppUserDetails>,
+    pub count: usize,
+}
+
+impl From<ListAppUsersError> for ApiError {
+    fn from(value: ListAppUsersError) -> Self {
+        match value {
+            ListAppUsersError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            ListAppUsersError::ResourceNotFound(resource) => Self::ResourceNotFound(resource),
+            ListAppUsersError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -1,17 +1,31 @@
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use validator::Validate;
 
 use crate::{
     api_error::ApiError,
     apps_service::AppsServiceTrait,
     backend::Backend,
-    repository::{App, ListAppsError},
+    contracts_service::ContractsServiceTrait,
+    field_selection::FieldSelection,
+    repository::{AppCategory, AppState, AppsFilter, AppsPage, ListAppsError},
+    request_context::RequestContext,
+    sorting::{AppSortField, SortOrder},
+    users_service::UsersServiceTrait,
 };
 
 #[utoipa::path(
     get,
     path = "/v1.0/apps",
+    params(ListAppsQueryParams),
     responses(
-        (status = OK, description = "Successfully retrieved apps", body = Vec<App>),
+        (status = OK, description = "Successfully retrieved apps", body = ListAppsHttpResponse),
         (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
         (status = BAD_REQUEST, description = "Bad Request", body = String),
         (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
@@ -21,17 +35,137 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn list_apps<AS: AppsServiceTrait>(
-    State(state): State<Backend<AS>>,
-) -> Result<(StatusCode, Json<Vec<App>>), ApiError> {
-    let apps = state.apps_service.list_apps().await?;
+pub async fn list_apps<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    Query(params): Query<ListAppsQueryParams>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<ListAppsHttpResponse>), ApiError> {
+    params.validate()?;
+    let fields = FieldSelection::parse(params.fields.as_deref());
+    let page = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .list_apps(params)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListAppsHttpResponse::new(page, &fields)?),
+    ))
+}
+
+/// Without `size` or `cursor` the whole catalog is returned in a single response
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, utoipa::IntoParams)]
+pub struct ListAppsQueryParams {
+    /// Maximum number of apps to return
+    #[validate(range(min = 1, max = 1000))]
+    pub size: Option<u16>,
+    /// `nextCursor` from the previous page
+    pub cursor: Option<String>,
+    /// Comma separated list of fields to return, `id` is always included. Custom fields are
+    /// selected as `fields.<key>`, `fields` returns all of them
+    pub fields: Option<String>,
+    pub state: Option<AppState>,
+    pub category: Option<AppCategory>,
+    #[serde(rename = "isCustom")]
+    pub is_custom: Option<bool>,
+    #[serde(rename = "isHidden")]
+    pub is_hidden: Option<bool>,
+    /// Comma separated list of tags, apps must have all of them
+    pub tags: Option<String>,
+    #[serde(rename = "primaryOwner")]
+    pub primary_owner: Option<String>,
+    /// Comma separated list of `key:value` pairs, apps must hold every value
+    #[serde(rename = "customFields")]
+    pub custom_fields: Option<String>,
+    #[serde(rename = "creationTimeFrom")]
+    pub creation_time_from: Option<DateTime<Utc>>,
+    #[serde(rename = "creationTimeTo")]
+    pub creation_time_to: Option<DateTime<Utc>>,
+    #[serde(rename = "lastUpdatedAtFrom")]
+    pub last_updated_at_from: Option<DateTime<Utc>>,
+    #[serde(rename = "lastUpdatedAtTo")]
+    pub last_updated_at_to: Option<DateTime<Utc>>,
+    /// Sorting reads the whole catalog, without it pages follow storage order
+    pub sort: Option<AppSortField>,
+    /// Defaults to `asc`
+    pub order: Option<SortOrder>,
+}
+
+impl From<&ListAppsQueryParams> for AppsFilter {
+    fn from(value: &ListAppsQueryParams) -> Self {
+        let tags = value
+            .tags
+            .as_deref()
+            .map(|tags| {
+                tags.split(',')
+                    .map(str::trim)
+                    .filter(|tag| !tag.is_empty())
+                    .map(str::to_string)
+                    .collect()
+            })
+            .unwrap_or_default();
 
-    Ok((StatusCode::OK, Json(apps)))
+        let custom_fields = value
+            .custom_fields
+            .as_deref()
+            .map(|pairs| {
+                pairs
+                    .split(',')
+                    .filter_map(|pair| pair.split_once(':'))
+                    .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        Self {
+            state: value.state.clone(),
+            category: value.category.clone(),
+            is_custom: value.is_custom,
+            is_hidden: value.is_hidden,
+            tags,
+            primary_owner: value.primary_owner.clone(),
+            creation_time_from: value.creation_time_from,
+            creation_time_to: value.creation_time_to,
+            last_updated_at_from: value.last_updated_at_from,
+            last_updated_at_to: value.last_updated_at_to,
+            custom_fields,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListAppsHttpResponse {
+    /// Apps projected down to the requested `fields`
+    #[schema(value_type = Vec<App>)]
+    pub apps: Vec<Value>,
+    pub count: usize,
+    #[serde(rename = "nextCursor")]
+    pub next_cursor: Option<String>,
+}
+
+impl ListAppsHttpResponse {
+    pub fn new(page: AppsPage, fields: &FieldSelection) -> Result<Self, ApiError> {
+        let apps = page
+            .apps
+            .iter()
+            .map(|app| fields.project(app))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(Self {
+            count: apps.len(),
+            apps,
+            next_cursor: page.next_cursor,
+        })
+    }
 }
 
 impl From<ListAppsError> for ApiError {
     fn from(value: ListAppsError) -> Self {
         match value {
+            ListAppsError::InvalidCursor(cursor) => {
+                Self::ValidationError(format!("Invalid cursor: {cursor}"))
+            }
             ListAppsError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
             ListAppsError::UnexpectedError => Self::InternalServerError,
         }
@@ -0,0 +1,85 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{AuditAction, AuditEvent, ListAuditEventsError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/audit",
+    params(ListAuditEventsQueryParams),
+    responses(
+        (status = OK, description = "Successfully retrieved audit events", body = ListAuditEventsHttpResponse),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_audit_events<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Query(params): Query<ListAuditEventsQueryParams>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<ListAuditEventsHttpResponse>), ApiError> {
+    let events = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .list_audit_events(params)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListAuditEventsHttpResponse {
+            count: events.len(),
+            events,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct ListAuditEventsQueryParams {
+    /// Only return events for this app
+    #[serde(rename = "appId")]
+    pub app_id: Option<u16>,
+    /// Only return events for this kind of change
+    pub action: Option<AuditAction>,
+    /// Only return events at or after this time
+    pub from: Option<DateTime<Utc>>,
+    /// Only return events at or before this time
+    pub to: Option<DateTime<Utc>>,
+}
+
+/// Events are oldest first
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListAuditEventsHttpResponse {
+    pub events: Vec<AuditEvent>,
+    pub count: usize,
+}
+
+impl From<ListAuditEventsError> for ApiError {
+    fn from(value: ListAuditEventsError) -> Self {
+        match value {
+            ListAuditEventsError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            ListAuditEventsError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
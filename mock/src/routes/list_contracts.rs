@@ -0,0 +1,84 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{Contract, ListContractsError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/contracts",
+    params(ListContractsQueryParams),
+    responses(
+        (status = OK, description = "Successfully retrieved contracts", body = ListContractsHttpResponse),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_contracts<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Query(params): Query<ListContractsQueryParams>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<ListContractsHttpResponse>), ApiError> {
+    let contracts = state
+        .contracts_service
+        .for_tenant(&context.tenant)
+        .list_contracts(params)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListContractsHttpResponse {
+            count: contracts.len(),
+            contracts,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct ListContractsQueryParams {
+    /// Only return contracts for this app
+    #[serde(rename = "appId")]
+    pub app_id: Option<u16>,
+    /// Only return contracts renewing on or after this date
+    #[serde(rename = "renewalFrom")]
+    pub renewal_from: Option<NaiveDate>,
+    /// Only return contracts renewing on or before this date
+    #[serde(rename = "renewalTo")]
+    pub renewal_to: Option<NaiveDate>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListContractsHttpResponse {
+    pub contracts: Vec<Contract>,
+    pub count: usize,
+}
+
+impl From<ListContractsError> for ApiError {
+    fn from(value: ListContractsError) -> Self {
+        match value {
+            ListContractsError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            ListContractsError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
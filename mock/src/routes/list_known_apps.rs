@@ -1,16 +1,32 @@
-use axum::{extract::State, http::StatusCode, Json};
+use axum::{
+    extract::{Query, State},
+    http::{HeaderMap, HeaderName, HeaderValue, StatusCode},
+    Json,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
 
 use crate::{
     api_error::ApiError,
-    apps_service::{AppsServiceTrait, KnownApp},
+    apps_service::AppsServiceTrait,
     backend::Backend,
+    catalog::KnownApp,
+    contracts_service::ContractsServiceTrait,
+    repository::{AppCategory, ListKnownAppsError},
+    users_service::UsersServiceTrait,
 };
 
+/// Carries the cursor of the next page, the body stays a plain list of apps
+pub const NEXT_CURSOR_HEADER: HeaderName = HeaderName::from_static("x-next-cursor");
+
 #[utoipa::path(
     get,
     path = "/v1.0/apps/known",
+    params(ListKnownAppsQueryParams),
     responses(
-        (status = OK, description = "Successfully retrieved apps", body = Vec<KnownApp>),
+        (status = OK, description = "Successfully retrieved apps", body = Vec<KnownApp>,
+            headers(("X-Next-Cursor" = String, description = "Cursor of the next page, missing on the last page"))),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
         (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
     ),
     security(
@@ -18,10 +34,48 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn list_known_apps<AS: AppsServiceTrait>(
-    State(state): State<Backend<AS>>,
-) -> Result<(StatusCode, Json<Vec<KnownApp>>), ApiError> {
-    let apps = state.apps_service.list_known_apps().await;
+pub async fn list_known_apps<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Query(params): Query<ListKnownAppsQueryParams>,
+    State(state): State<Backend<AS, US, CS>>,
+) -> Result<(StatusCode, HeaderMap, Json<Vec<KnownApp>>), ApiError> {
+    params.validate()?;
+    let page = state.apps_service.list_known_apps(params).await?;
+
+    let mut headers = HeaderMap::new();
+
+    if let Some(cursor) = page
+        .next_cursor
+        .and_then(|cursor| HeaderValue::from_str(&cursor).ok())
+    {
+        headers.insert(NEXT_CURSOR_HEADER, cursor);
+    }
+
+    Ok((StatusCode::OK, headers, Json(page.apps)))
+}
+
+/// Without `size` or `cursor` the whole catalog is returned in a single response
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, utoipa::IntoParams)]
+pub struct ListKnownAppsQueryParams {
+    /// Matched case insensitively against any part of the name
+    pub name: Option<String>,
+    pub category: Option<AppCategory>,
+    /// Maximum number of apps to return
+    #[validate(range(min = 1, max = 1000))]
+    pub size: Option<u16>,
+    /// `X-Next-Cursor` from the previous page
+    pub cursor: Option<String>,
+}
 
-    Ok((StatusCode::OK, Json(apps)))
+impl From<ListKnownAppsError> for ApiError {
+    fn from(value: ListKnownAppsError) -> Self {
+        match value {
+            ListKnownAppsError::InvalidCursor(cursor) => {
+                Self::ValidationError(format!("Invalid cursor: {cursor}"))
+            }
+        }
+    }
 }
@@ -0,0 +1,66 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::{AppsServiceTrait, UserAppDetails},
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/users/{id}/apps",
+    params(ListUserAppsPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved user apps", body = ListUserAppsHttpResponse),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_user_apps<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(ListUserAppsPathContent { id }): Path<ListUserAppsPathContent>,
+) -> Result<(StatusCode, Json<ListUserAppsHttpResponse>), ApiError> {
+    let apps = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .list_user_apps(id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListUserAppsHttpResponse {
+            count: apps.len(),
+            apps,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct ListUserAppsPathContent {
+    pub id: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListUserAppsHttpResponse {
+    pub apps: Vec<UserAppDetails>,
+    pub count: usize,
+}
@@ -0,0 +1,72 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{ListUsersError, User},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/users",
+    params(ListUsersQueryParams),
+    responses(
+        (status = OK, description = "Successfully retrieved users", body = ListUsersHttpResponse),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_users<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    Query(params): Query<ListUsersQueryParams>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<ListUsersHttpResponse>), ApiError> {
+    let users = state
+        .users_service
+        .for_tenant(&context.tenant)
+        .list_users(params)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListUsersHttpResponse {
+            count: users.len(),
+            users,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct ListUsersQueryParams {
+    /// Only return the user with this email, matched case insensitively
+    pub email: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListUsersHttpResponse {
+    pub users: Vec<User>,
+    pub count: usize,
+}
+
+impl From<ListUsersError> for ApiError {
+    fn from(value: ListUsersError) -> Self {
+        match value {
+            ListUsersError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            ListUsersError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{ListWebhookDeliveriesError, WebhookDelivery},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/webhooks/{id}/deliveries",
+    params(ListWebhookDeliveriesPathContent),
+    responses(
+        (status = OK, description = "Successfully retrieved webhook deliveries", body = ListWebhookDeliveriesHttpResponse),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_webhook_deliveries<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(ListWebhookDeliveriesPathContent { id }): Path<ListWebhookDeliveriesPathContent>,
+) -> Result<(StatusCode, Json<ListWebhookDeliveriesHttpResponse>), ApiError> {
+    let deliveries = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .list_webhook_deliveries(&id)
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListWebhookDeliveriesHttpResponse {
+            count: deliveries.len(),
+            deliveries,
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct ListWebhookDeliveriesPathContent {
+    pub id: String,
+}
+
+/// Deliveries are newest first
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListWebhookDeliveriesHttpResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub count: usize,
+}
+
+impl From<ListWebhookDeliveriesError> for ApiError {
+    fn from(value: ListWebhookDeliveriesError) -> Self {
+        match value {
+            ListWebhookDeliveriesError::ValidationError(msg) => {
+                Self::ValidationError(msg.to_string())
+            }
+            ListWebhookDeliveriesError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            ListWebhookDeliveriesError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use axum::{extract::State, http::StatusCode, Extension, Json};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{ListWebhooksError, RedactedWebhook},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    get,
+    path = "/v1.0/webhooks",
+    responses(
+        (status = OK, description = "Successfully retrieved webhooks", body = ListWebhooksHttpResponse),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn list_webhooks<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<ListWebhooksHttpResponse>), ApiError> {
+    let webhooks = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .list_webhooks()
+        .await?;
+
+    Ok((
+        StatusCode::OK,
+        Json(ListWebhooksHttpResponse {
+            count: webhooks.len(),
+            webhooks: webhooks.into_iter().map(RedactedWebhook::from).collect(),
+        }),
+    ))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct ListWebhooksHttpResponse {
+    pub webhooks: Vec<RedactedWebhook>,
+    pub count: usize,
+}
+
+impl From<ListWebhooksError> for ApiError {
+    fn from(value: ListWebhooksError) -> Self {
+        match value {
+            ListWebhooksError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            ListWebhooksError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -1,17 +1,97 @@
 pub mod add_app;
+pub mod bulk_apps;
+pub mod create_api_key;
 pub mod create_app;
+pub mod create_app_field;
+pub mod create_contract;
+pub mod create_user;
+pub mod create_webhook;
+pub mod delete_api_key;
 pub mod delete_app;
+pub mod delete_app_field;
+pub mod delete_app_user;
+pub mod delete_contract;
+pub mod delete_faults;
+pub mod delete_user;
+pub mod delete_webhook;
+pub mod export_apps;
+pub mod get_api_key;
 pub mod get_app;
+pub mod get_app_field;
+pub mod get_contract;
+pub mod get_faults;
+pub mod get_known_app;
+pub mod get_user;
+pub mod get_webhook;
+pub mod import_apps;
+pub mod issue_token;
+pub mod list_api_keys;
+pub mod list_app_fields;
+pub mod list_app_users;
 pub mod list_apps;
+pub mod list_audit_events;
+pub mod list_contracts;
 pub mod list_known_apps;
+pub mod list_user_apps;
+pub mod list_users;
+pub mod list_webhook_deliveries;
+pub mod list_webhooks;
+pub mod patch_app;
+pub mod put_app_user;
+pub mod rotate_api_key;
 pub mod search_apps;
 pub mod update_app;
+pub mod update_app_field;
+pub mod update_contract;
+pub mod update_faults;
+pub mod update_user;
+pub mod update_webhook;
 
 pub use add_app::*;
+pub use bulk_apps::*;
+pub use create_api_key::*;
 pub use create_app::*;
+pub use create_app_field::*;
+pub use create_contract::*;
+pub use create_user::*;
+pub use create_webhook::*;
+pub use delete_api_key::*;
 pub use delete_app::*;
+pub use delete_app_field::*;
+pub use delete_app_user::*;
+pub use delete_contract::*;
+pub use delete_faults::*;
+pub use delete_user::*;
+pub use delete_webhook::*;
+pub use export_apps::*;
+pub use get_api_key::*;
 pub use get_app::*;
+pub use get_app_field::*;
+pub use get_contract::*;
+pub use get_faults::*;
+pub use get_known_app::*;
+pub use get_user::*;
+pub use get_webhook::*;
+pub use import_apps::*;
+pub use issue_token::*;
+pub use list_api_keys::*;
+pub use list_app_fields::*;
+pub use list_app_users::*;
 pub use list_apps::*;
+pub use list_audit_events::*;
+pub use list_contracts::*;
 pub use list_known_apps::*;
+pub use list_user_apps::*;
+pub use list_users::*;
+pub use list_webhook_deliveries::*;
+pub use list_webhooks::*;
+pub use patch_app::*;
+pub use put_app_user::*;
+pub use rotate_api_key::*;
 pub use search_apps::*;
 pub use update_app::*;
+pub use update_app_field::*;
+pub use update_contract::*;
+pub use update_faults::*;
+pub use update_user::*;
+pub use update_webhook::*;
@@ -0,0 +1,96 @@
+use axum::{
+    body::Bytes,
+    extract::{Path, State},
+    http::{header, HeaderMap, StatusCode},
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    app_patch::{AppPatch, JSON_PATCH_CONTENT_TYPE, MERGE_PATCH_CONTENT_TYPE},
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    etag::{etag, parse_if_match, ETagHeader},
+    repository::App,
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+/// Changes `name`, `state`, `url`, `category`, `description`, `tags` and `fields`. Send a JSON
+/// Merge Patch with `Content-Type: application/merge-patch+json`, where `null` clears an
+/// attribute, or a JSON Patch with `Content-Type: application/json-patch+json`
+#[utoipa::path(
+    patch,
+    path = "/v1.0/apps/{id}",
+    params(
+        PatchAppPathContent,
+        ("If-Match" = Option<String>, Header, description = "Only patch the app while it is still at this `ETag`"),
+    ),
+    request_body(
+        content = Object,
+        content_type = "application/merge-patch+json",
+        description = "JSON Merge Patch, or a JSON Patch array when sent as `application/json-patch+json`"
+    ),
+    responses(
+        (status = OK, description = "Successfully patched app", body = App,
+            headers(("ETag" = String, description = "New version of the app"))),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = PRECONDITION_FAILED, description = "App was changed since it was read", body = String),
+        (status = UNSUPPORTED_MEDIA_TYPE, description = "Content-Type is not a patch format", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state, body))]
+pub async fn patch_app<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    Path(PatchAppPathContent { id }): Path<PatchAppPathContent>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    headers: HeaderMap,
+    body: Bytes,
+) -> Result<(StatusCode, ETagHeader, Json<App>), ApiError> {
+    let patch = parse_patch(&headers, &body)?;
+    let expected_version = parse_if_match(&headers)?;
+    let app = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .patch_app(patch, id, expected_version, context)
+        .await?;
+
+    Ok((StatusCode::OK, etag(app.version), Json(app)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct PatchAppPathContent {
+    pub id: u16,
+}
+
+/// Picks the patch format from `Content-Type`, parameters such as `charset` are ignored
+fn parse_patch(headers: &HeaderMap, body: &[u8]) -> Result<AppPatch, ApiError> {
+    let content_type = headers
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.split(';').next())
+        .map(|value| value.trim().to_ascii_lowercase())
+        .unwrap_or_default();
+
+    let invalid = |e: serde_json::Error| ApiError::ValidationError(e.to_string());
+
+    match content_type.as_str() {
+        MERGE_PATCH_CONTENT_TYPE => serde_json::from_slice(body)
+            .map(AppPatch::Merge)
+            .map_err(invalid),
+        JSON_PATCH_CONTENT_TYPE => serde_json::from_slice(body)
+            .map(AppPatch::Json)
+            .map_err(invalid),
+        _ => Err(ApiError::UnsupportedMediaType(format!(
+            "Expected {MERGE_PATCH_CONTENT_TYPE} or {JSON_PATCH_CONTENT_TYPE}"
+        ))),
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{AppUser, LicenseStatus, PutAppUserError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    put,
+    path = "/v1.0/apps/{id}/users/{user_id}",
+    params(PutAppUserPathContent),
+    request_body = PutAppUserHttpRequestBody,
+    responses(
+        (status = OK, description = "Successfully linked user to app", body = AppUser),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn put_app_user<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Path(PutAppUserPathContent { id, user_id }): Path<PutAppUserPathContent>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<PutAppUserHttpRequestBody>,
+) -> Result<(StatusCode, Json<AppUser>), ApiError> {
+    let app_user = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .put_app_user(body.0, id, user_id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(app_user)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct PutAppUserPathContent {
+    pub id: u16,
+    pub user_id: u32,
+}
+
+/// Omitted fields keep their current value, new links default to an active license
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::ToSchema)]
+pub struct PutAppUserHttpRequestBody {
+    #[serde(rename = "licenseStatus")]
+    pub license_status: Option<LicenseStatus>,
+    #[serde(rename = "lastUsedAt")]
+    pub last_used_at: Option<DateTime<Utc>>,
+}
+
+impl From<PutAppUserError> for ApiError {
+    fn from(value: PutAppUserError) -> Self {
+        match value {
+            PutAppUserError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            PutAppUserError::ResourceNotFound(resource) => Self::ResourceNotFound(resource),
+            PutAppUserError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{CreatedApiKey, RotateApiKeyError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+/// Replaces the key with a new one that has the same name, scopes and expiry
+#[utoipa::path(
+    post,
+    path = "/v1.0/admin/api-keys/{id}/rotate",
+    params(RotateApiKeyPathContent),
+    request_body = RotateApiKeyHttpRequestBody,
+    responses(
+        (status = CREATED, description = "Successfully rotated API key", body = CreatedApiKey),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn rotate_api_key<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    Path(RotateApiKeyPathContent { id }): Path<RotateApiKeyPathContent>,
+    body: Json<RotateApiKeyHttpRequestBody>,
+) -> Result<(StatusCode, Json<CreatedApiKey>), ApiError> {
+    body.validate()?;
+    let api_key = state
+        .api_keys_service
+        .rotate_api_key(&context.tenant, &id, body.0)
+        .await?;
+
+    Ok((StatusCode::CREATED, Json(api_key)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct RotateApiKeyPathContent {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct RotateApiKeyHttpRequestBody {
+    /// How long the old key keeps working, at most a week. It stops working right away when
+    /// left out
+    #[serde(rename = "gracePeriodSeconds")]
+    #[validate(range(max = 604800))]
+    pub grace_period_seconds: Option<u32>,
+}
+
+impl From<RotateApiKeyError> for ApiError {
+    fn from(value: RotateApiKeyError) -> Self {
+        match value {
+            RotateApiKeyError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            RotateApiKeyError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
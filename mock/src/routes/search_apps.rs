@@ -1,12 +1,18 @@
 use axum::{
     extract::{Query, State},
     http::StatusCode,
-    Json,
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
 
 use crate::{
-    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend, repository::App,
+    api_error::ApiError,
+    apps_service::{AppsServiceTrait, ScoredApp},
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    request_context::RequestContext,
+    sorting::{AppSortField, SortOrder},
+    users_service::UsersServiceTrait,
 };
 
 #[utoipa::path(
@@ -14,7 +20,7 @@ use crate::{
     path = "/v1.0/apps/search",
     params(SearchAppsQueryParams),
     responses(
-        (status = OK, description = "List of apps matching query", body = Vec<App>),
+        (status = OK, description = "Apps matching query, best match first", body = Vec<ScoredApp>),
         (status = BAD_REQUEST, description = "Bad Request", body = String),
         (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
         (status = NOT_FOUND, description = "Not found", body = String),
@@ -25,16 +31,26 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn search_apps<AS: AppsServiceTrait>(
+pub async fn search_apps<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
     Query(params): Query<SearchAppsQueryParams>,
-    State(state): State<Backend<AS>>,
-) -> Result<(StatusCode, Json<Vec<App>>), ApiError> {
-    let apps = state.apps_service.search_apps(params).await?;
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+) -> Result<(StatusCode, Json<Vec<ScoredApp>>), ApiError> {
+    let apps = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .search_apps(params)
+        .await?;
 
     Ok((StatusCode::OK, Json(apps)))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
 pub struct SearchAppsQueryParams {
+    /// Matched case insensitively against name, url, description and tags
     pub query: String,
+    /// Results are ordered by score unless a sort field is given
+    pub sort: Option<AppSortField>,
+    /// Defaults to `asc`
+    pub order: Option<SortOrder>,
 }
@@ -1,29 +1,41 @@
+use std::collections::BTreeMap;
+
 use axum::{
     extract::{Path, State},
-    http::StatusCode,
-    Json,
+    http::{HeaderMap, StatusCode},
+    Extension, Json,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use validator::Validate;
 
 use crate::{
     api_error::ApiError,
     apps_service::AppsServiceTrait,
     backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    etag::{etag, parse_if_match, ETagHeader},
     repository::{App, AppCategory, AppState, UpdateAppError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
 };
 
 #[utoipa::path(
     put,
     path = "/v1.0/apps/{id}",
-    params(UpdateAppPathContent),
+    params(
+        UpdateAppPathContent,
+        ("If-Match" = Option<String>, Header, description = "Only update the app while it is still at this `ETag`"),
+    ),
     request_body = UpdateAppHttpRequestBody,
     responses(
-        (status = OK, description = "Successfully updated app", body = App),
+        (status = OK, description = "Successfully updated app", body = App,
+            headers(("ETag" = String, description = "New version of the app"))),
         (status = BAD_REQUEST, description = "Bad Request", body = String),
         (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
         (status = NOT_FOUND, description = "Not found", body = String),
         (status = CONFLICT, description = "App already exists", body = String),
+        (status = PRECONDITION_FAILED, description = "App was changed since it was read", body = String),
         (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
     ),
     security(
@@ -31,15 +43,22 @@ use crate::{
     )
 )]
 #[tracing::instrument(skip(state))]
-pub async fn update_app<AS: AppsServiceTrait>(
+pub async fn update_app<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
     Path(UpdateAppPathContent { id }): Path<UpdateAppPathContent>,
-    State(state): State<Backend<AS>>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    headers: HeaderMap,
     body: Json<UpdateAppHttpRequestBody>,
-) -> Result<(StatusCode, Json<App>), ApiError> {
+) -> Result<(StatusCode, ETagHeader, Json<App>), ApiError> {
     body.validate()?;
-    let app = state.apps_service.update_app(body.0, id).await?;
+    let expected_version = parse_if_match(&headers)?;
+    let app = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .update_app(body.0, id, expected_version, context)
+        .await?;
 
-    Ok((StatusCode::OK, Json(app)))
+    Ok((StatusCode::OK, etag(app.version), Json(app)))
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
@@ -54,8 +73,14 @@ pub struct UpdateAppHttpRequestBody {
     #[validate(url)]
     pub url: Option<String>,
     pub category: Option<AppCategory>,
+    /// Left unchanged when omitted, use `PATCH` to clear it
     pub description: Option<String>,
+    /// Left unchanged when omitted, use `PATCH` to clear it
     pub tags: Option<String>,
+    /// Custom field values to change, fields that are left out keep their value and `null`
+    /// clears one
+    #[schema(value_type = Option<Object>)]
+    pub fields: Option<BTreeMap<String, Value>>,
 }
 
 impl From<UpdateAppError> for ApiError {
@@ -64,6 +89,9 @@ impl From<UpdateAppError> for ApiError {
             UpdateAppError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
             UpdateAppError::UnexpectedError => Self::InternalServerError,
             UpdateAppError::ResourceNotFound(msg) => Self::ResourceNotFound(msg.to_string()),
+            UpdateAppError::VersionConflict(_) => Self::PreconditionFailed(value.to_string()),
+            UpdateAppError::InvalidCustomField(msg) => Self::ValidationError(msg),
+            UpdateAppError::InvalidPatch(_) => Self::ValidationError(value.to_string()),
         }
     }
 }
@@ -0,0 +1,79 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{AppField, UpdateAppFieldError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    put,
+    path = "/v1.0/apps/fields/{key}",
+    params(UpdateAppFieldPathContent),
+    request_body = UpdateAppFieldHttpRequestBody,
+    responses(
+        (status = OK, description = "Successfully updated app field", body = AppField),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn update_app_field<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Path(UpdateAppFieldPathContent { key }): Path<UpdateAppFieldPathContent>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<UpdateAppFieldHttpRequestBody>,
+) -> Result<(StatusCode, Json<AppField>), ApiError> {
+    body.validate()?;
+    let field = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .update_app_field(body.0, &key)
+        .await?;
+
+    Ok((StatusCode::OK, Json(field)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct UpdateAppFieldPathContent {
+    pub key: String,
+}
+
+/// The field type can't be changed
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateAppFieldHttpRequestBody {
+    #[validate(length(min = 1))]
+    pub name: Option<String>,
+    /// Replaces the options of a dropdown field
+    pub options: Option<Vec<String>>,
+}
+
+impl From<UpdateAppFieldError> for ApiError {
+    fn from(value: UpdateAppFieldError) -> Self {
+        match value {
+            UpdateAppFieldError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            UpdateAppFieldError::InvalidField(msg) => Self::ValidationError(msg),
+            UpdateAppFieldError::ResourceNotFound(key) => Self::ResourceNotFound(key),
+            UpdateAppFieldError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
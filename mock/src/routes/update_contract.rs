@@ -0,0 +1,106 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use chrono::NaiveDate;
+use serde::{Deserialize, Deserializer, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{Contract, UpdateContractError},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    put,
+    path = "/v1.0/contracts/{id}",
+    params(UpdateContractPathContent),
+    request_body = UpdateContractHttpRequestBody,
+    responses(
+        (status = OK, description = "Successfully updated contract", body = Contract),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn update_contract<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Path(UpdateContractPathContent { id }): Path<UpdateContractPathContent>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<UpdateContractHttpRequestBody>,
+) -> Result<(StatusCode, Json<Contract>), ApiError> {
+    body.validate()?;
+    let contract = state
+        .contracts_service
+        .for_tenant(&context.tenant)
+        .update_contract(body.0, id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(contract)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct UpdateContractPathContent {
+    pub id: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateContractHttpRequestBody {
+    #[serde(rename = "appId")]
+    pub app_id: Option<u16>,
+    #[validate(range(min = 0.0))]
+    pub amount: Option<f64>,
+    #[validate(length(equal = 3))]
+    pub currency: Option<String>,
+    #[serde(rename = "startDate")]
+    pub start_date: Option<NaiveDate>,
+    /// `null` clears the end date, leaving it out keeps it
+    #[serde(rename = "endDate", default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub end_date: Option<Option<NaiveDate>>,
+    /// `null` clears the renewal date, leaving it out keeps it
+    #[serde(rename = "renewalDate", default, deserialize_with = "nullable")]
+    #[schema(value_type = Option<NaiveDate>)]
+    pub renewal_date: Option<Option<NaiveDate>>,
+    #[serde(rename = "autoRenew")]
+    pub auto_renew: Option<bool>,
+    #[validate(length(min = 1))]
+    pub owner: Option<String>,
+}
+
+/// Tells a member set to `null`, `Some(None)`, apart from one that was left out, `None`
+fn nullable<'de, D, T>(deserializer: D) -> Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+impl From<UpdateContractError> for ApiError {
+    fn from(value: UpdateContractError) -> Self {
+        match value {
+            UpdateContractError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            UpdateContractError::ResourceNotFound(id) => Self::ResourceNotFound(id.to_string()),
+            UpdateContractError::AppNotFound(_) | UpdateContractError::InvalidContract(_) => {
+                Self::ValidationError(value.to_string())
+            }
+            UpdateContractError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,38 @@
+use axum::{extract::State, http::StatusCode, Json};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError, apps_service::AppsServiceTrait, backend::Backend,
+    contracts_service::ContractsServiceTrait, faults::FaultConfig,
+    users_service::UsersServiceTrait,
+};
+
+/// Replaces every fault rule. Setting the same seed again replays the same faults
+#[utoipa::path(
+    put,
+    path = "/v1.0/admin/faults",
+    request_body = FaultConfig,
+    responses(
+        (status = OK, description = "Successfully updated faults", body = FaultConfig),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = FORBIDDEN, description = "The API key lacks the admin scope or isn't of the default tenant", body = String),
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn update_faults<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    State(state): State<Backend<AS, US, CS>>,
+    body: Json<FaultConfig>,
+) -> Result<(StatusCode, Json<FaultConfig>), ApiError> {
+    body.validate()?;
+    state.faults.set_config(body.0);
+
+    Ok((StatusCode::OK, Json(state.faults.config())))
+}
@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{UpdateUserError, User, UserLifecycleStatus},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    put,
+    path = "/v1.0/users/{id}",
+    params(UpdateUserPathContent),
+    request_body = UpdateUserHttpRequestBody,
+    responses(
+        (status = OK, description = "Successfully updated user", body = User),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = CONFLICT, description = "Email already in use", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn update_user<AS: AppsServiceTrait, US: UsersServiceTrait, CS: ContractsServiceTrait>(
+    Path(UpdateUserPathContent { id }): Path<UpdateUserPathContent>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<UpdateUserHttpRequestBody>,
+) -> Result<(StatusCode, Json<User>), ApiError> {
+    body.validate()?;
+    let user = state
+        .users_service
+        .for_tenant(&context.tenant)
+        .update_user(body.0, id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(user)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Copy, utoipa::IntoParams)]
+pub struct UpdateUserPathContent {
+    pub id: u32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateUserHttpRequestBody {
+    #[serde(rename = "firstName")]
+    pub first_name: Option<String>,
+    #[serde(rename = "lastName")]
+    pub last_name: Option<String>,
+    #[validate(email)]
+    pub email: Option<String>,
+    #[serde(rename = "lifecycleStatus")]
+    pub lifecycle_status: Option<UserLifecycleStatus>,
+    #[serde(rename = "isExternal")]
+    pub is_external: Option<bool>,
+}
+
+impl From<UpdateUserError> for ApiError {
+    fn from(value: UpdateUserError) -> Self {
+        match value {
+            UpdateUserError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            UpdateUserError::ResourceNotFound(id) => Self::ResourceNotFound(id.to_string()),
+            UpdateUserError::ResourceAlreadyExists { email } => Self::ResourceAlreadyExists(email),
+            UpdateUserError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Extension, Json,
+};
+use serde::{Deserialize, Serialize};
+use validator::Validate;
+
+use crate::{
+    api_error::ApiError,
+    apps_service::AppsServiceTrait,
+    backend::Backend,
+    contracts_service::ContractsServiceTrait,
+    repository::{RedactedWebhook, UpdateWebhookError, WebhookEventType},
+    request_context::RequestContext,
+    users_service::UsersServiceTrait,
+};
+
+#[utoipa::path(
+    put,
+    path = "/v1.0/webhooks/{id}",
+    params(UpdateWebhookPathContent),
+    request_body = UpdateWebhookHttpRequestBody,
+    responses(
+        (status = OK, description = "Successfully updated webhook", body = RedactedWebhook),
+        (status = BAD_REQUEST, description = "Bad Request", body = String),
+        (status = UNAUTHORIZED, description = "UNAUTHORIZED", body = String),
+        (status = NOT_FOUND, description = "Not found", body = String),
+        (status = INTERNAL_SERVER_ERROR, description = "Internal server error")
+    ),
+    security(
+        ("authorization" = []),
+    )
+)]
+#[tracing::instrument(skip(state))]
+pub async fn update_webhook<
+    AS: AppsServiceTrait,
+    US: UsersServiceTrait,
+    CS: ContractsServiceTrait,
+>(
+    Path(UpdateWebhookPathContent { id }): Path<UpdateWebhookPathContent>,
+    State(state): State<Backend<AS, US, CS>>,
+    Extension(context): Extension<RequestContext>,
+    body: Json<UpdateWebhookHttpRequestBody>,
+) -> Result<(StatusCode, Json<RedactedWebhook>), ApiError> {
+    body.validate()?;
+    let webhook = state
+        .apps_service
+        .for_tenant(&context.tenant)
+        .update_webhook(body.0, &id)
+        .await?;
+
+    Ok((StatusCode::OK, Json(webhook.into())))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, utoipa::IntoParams)]
+pub struct UpdateWebhookPathContent {
+    pub id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, utoipa::ToSchema)]
+pub struct UpdateWebhookHttpRequestBody {
+    #[validate(url)]
+    pub url: Option<String>,
+    /// Replaces the subscribed events
+    #[validate(length(min = 1))]
+    pub events: Option<Vec<WebhookEventType>>,
+    #[validate(length(min = 16))]
+    pub secret: Option<String>,
+    pub active: Option<bool>,
+}
+
+impl From<UpdateWebhookError> for ApiError {
+    fn from(value: UpdateWebhookError) -> Self {
+        match value {
+            UpdateWebhookError::ValidationError(msg) => Self::ValidationError(msg.to_string()),
+            UpdateWebhookError::ResourceNotFound(id) => Self::ResourceNotFound(id),
+            UpdateWebhookError::UnexpectedError => Self::InternalServerError,
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use crate::repository::App;
+
+/// How good a match is decides the rank first, the attribute it is in only breaks ties. Each
+/// kind of match is worth more than the next one plus the largest attribute bonus
+const EXACT_SCORE: f32 = 0.8;
+const PREFIX_SCORE: f32 = 0.6;
+const SUBSTRING_SCORE: f32 = 0.4;
+/// Upper bound for fuzzy matches, reduced by every edit needed to reach the query
+const FUZZY_SCORE: f32 = 0.2;
+
+/// Added to a match depending on where it was found, a name match ranks above the same kind
+/// of match anywhere else
+const NAME_BONUS: f32 = 0.15;
+const TAGS_BONUS: f32 = 0.1;
+const URL_BONUS: f32 = 0.05;
+const DESCRIPTION_BONUS: f32 = 0.0;
+
+/// Shorter queries are within one edit of far too many words to be matched fuzzily
+const MIN_FUZZY_QUERY_LENGTH: usize = 4;
+
+/// Scores `app` against `query` between 0 and 1, 0 meaning no match. Matching is case
+/// insensitive and ranks exact > prefix > substring > fuzzy, then name > tags > URL >
+/// description
+pub fn score_app(app: &App, query: &str) -> f32 {
+    let query = query.trim().to_lowercase();
+
+    if query.is_empty() {
+        return 0.0;
+    }
+
+    let name = with_bonus(score_text(&app.name, &query, true), NAME_BONUS);
+    let tags = with_bonus(
+        app.tag_list()
+            .iter()
+            .map(|tag| score_text(tag, &query, true))
+            .fold(0.0, f32::max),
+        TAGS_BONUS,
+    );
+    let url = with_bonus(score_text(strip_scheme(&app.url), &query, false), URL_BONUS);
+    let description = with_bonus(
+        app.description
+            .as_deref()
+            .map(|description| score_text(description, &query, false))
+            .unwrap_or_default(),
+        DESCRIPTION_BONUS,
+    );
+
+    [name, tags, url, description]
+        .into_iter()
+        .fold(0.0, f32::max)
+}
+
+/// No match stays at 0 rather than getting the bonus
+fn with_bonus(score: f32, bonus: f32) -> f32 {
+    if score > 0.0 {
+        score + bonus
+    } else {
+        0.0
+    }
+}
+
+/// `query` is expected to already be lowercase
+fn score_text(text: &str, query: &str, fuzzy: bool) -> f32 {
+    let text = text.trim().to_lowercase();
+
+    if text == query {
+        EXACT_SCORE
+    } else if text.starts_with(query) {
+        PREFIX_SCORE
+    } else if text.contains(query) {
+        SUBSTRING_SCORE
+    } else if fuzzy {
+        score_fuzzy(&text, query)
+    } else {
+        0.0
+    }
+}
+
+/// Compares the query to the whole text and to each word in it, allowing roughly one typo per
+/// four characters
+fn score_fuzzy(text: &str, query: &str) -> f32 {
+    let query_length = query.chars().count();
+    if query_length < MIN_FUZZY_QUERY_LENGTH {
+        return 0.0;
+    }
+
+    let max_distance = query_length / 4;
+
+    std::iter::once(text)
+        .chain(text.split_whitespace())
+        .map(|candidate| edit_distance(candidate, query))
+        .filter(|distance| *distance <= max_distance)
+        .map(|distance| FUZZY_SCORE * (1.0 - distance as f32 / (query_length + 1) as f32))
+        .fold(0.0, f32::max)
+}
+
+fn strip_scheme(url: &str) -> &str {
+    let url = url
+        .strip_prefix("https://")
+        .or_else(|| url.strip_prefix("http://"))
+        .unwrap_or(url);
+
+    url.strip_prefix("www.").unwrap_or(url)
+}
+
+/// Levenshtein distance between two strings, counted in characters
+fn edit_distance(left: &str, right: &str) -> usize {
+    let right: Vec<char> = right.chars().collect();
+    let mut previous: Vec<usize> = (0..=right.len()).collect();
+
+    for (i, left_char) in left.chars().enumerate() {
+        let mut current = vec![i + 1; right.len() + 1];
+
+        for (j, right_char) in right.iter().enumerate() {
+            let substitution = previous[j] + usize::from(left_char != *right_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+
+        previous = current;
+    }
+
+    previous[right.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn app(name: &str, tags: Option<&str>, url: &str, description: Option<&str>) -> App {
+        App {
+            name: name.to_string(),
+            tags: tags.map(str::to_string),
+            url: url.to_string(),
+            description: description.map(str::to_string),
+            ..Default::default()
+        }
+    }
+
+    fn named(name: &str) -> App {
+        app(name, None, "https://example.com", None)
+    }
+
+    #[test]
+    fn ranks_kind_of_match_before_attribute() {
+        let query = "crm";
+        let ranked = [
+            app("CRM", None, "https://example.com", None),
+            app("Tool", Some("crm"), "https://example.com", None),
+            app("Tool", None, "https://example.com", Some("crm")),
+            app("CRM Suite", None, "https://example.com", None),
+            app("Tool", Some("crm-lite"), "https://example.com", None),
+            app("Tool", None, "https://crm.example.com", None),
+            app("Tool", None, "https://example.com", Some("crm suite")),
+            app("Best CRM", None, "https://example.com", None),
+            app("Tool", Some("best-crm"), "https://example.com", None),
+            app("Tool", None, "https://example.com/crm", None),
+            app("Tool", None, "https://example.com", Some("a crm")),
+        ];
+
+        let scores: Vec<f32> = ranked.iter().map(|app| score_app(app, query)).collect();
+
+        for (i, pair) in scores.windows(2).enumerate() {
+            assert!(
+                pair[0] > pair[1],
+                "{} ranks {} and {} ranks {}",
+                ranked[i].name,
+                pair[0],
+                ranked[i + 1].name,
+                pair[1]
+            );
+        }
+    }
+
+    #[test]
+    fn exact_tag_ranks_above_name_prefix() {
+        let tagged = app("Tool", Some("Slack"), "https://example.com", None);
+
+        assert!(score_app(&tagged, "slack") > score_app(&named("Slack Connect"), "slack"));
+    }
+
+    #[test]
+    fn exact_description_ranks_above_url_prefix() {
+        let described = app("Tool", None, "https://example.com", Some("Notion"));
+        let linked = app("Tool", None, "https://notion.so", None);
+
+        assert!(score_app(&described, "notion") > score_app(&linked, "notion"));
+    }
+
+    #[test]
+    fn fuzzy_match_ranks_below_every_substring_match() {
+        let fuzzy = score_app(&named("Slack"), "slck");
+        let substring = score_app(
+            &app("Tool", None, "https://example.com", Some("works with slck")),
+            "slck",
+        );
+
+        assert!(fuzzy > 0.0);
+        assert!(substring > fuzzy);
+    }
+
+    #[test]
+    fn short_queries_are_not_matched_fuzzily() {
+        assert_eq!(score_app(&named("Zoom"), "zom"), 0.0);
+        assert_eq!(score_app(&named("Box"), "bx"), 0.0);
+        assert!(score_app(&named("Zoom"), "zoon") > 0.0);
+    }
+
+    #[test]
+    fn matching_ignores_case_and_url_scheme() {
+        assert_eq!(
+            score_app(&named("GitHub"), "github"),
+            EXACT_SCORE + NAME_BONUS
+        );
+        assert_eq!(
+            score_app(&app("Tool", None, "https://www.figma.com", None), "figma"),
+            PREFIX_SCORE + URL_BONUS
+        );
+    }
+
+    #[test]
+    fn scores_stay_between_zero_and_one() {
+        assert_eq!(score_app(&named("Slack"), "   "), 0.0);
+        assert_eq!(score_app(&named("Slack"), "jira"), 0.0);
+        assert!(score_app(&named("Slack"), "slack") <= 1.0);
+    }
+}
@@ -0,0 +1,68 @@
+use std::cmp::Ordering;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use utoipa::ToSchema;
+
+use crate::repository::App;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub enum AppSortField {
+    Name,
+    State,
+    Category,
+    CreationTime,
+    LastUpdatedAt,
+    LastUsageTime,
+}
+
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum SortOrder {
+    #[default]
+    Asc,
+    Desc,
+}
+
+/// Sorts by `field`, ties are broken by name and then ID so the order is always stable.
+/// States and categories sort alphabetically by the name the API uses for them. Apps that were
+/// never used sort before used ones in ascending order
+pub fn sort_apps<T>(
+    items: &mut [T],
+    field: AppSortField,
+    order: SortOrder,
+    app: impl Fn(&T) -> &App,
+) {
+    items.sort_by(|a, b| {
+        let ordering = compare_apps(app(a), app(b), field);
+
+        match order {
+            SortOrder::Asc => ordering,
+            SortOrder::Desc => ordering.reverse(),
+        }
+    });
+}
+
+fn compare_apps(a: &App, b: &App, field: AppSortField) -> Ordering {
+    let ordering = match field {
+        AppSortField::Name => Ordering::Equal,
+        AppSortField::State => serialized_name(&a.state).cmp(&serialized_name(&b.state)),
+        AppSortField::Category => serialized_name(&a.category).cmp(&serialized_name(&b.category)),
+        AppSortField::CreationTime => a.creation_time.cmp(&b.creation_time),
+        AppSortField::LastUpdatedAt => a.last_updated_at.cmp(&b.last_updated_at),
+        AppSortField::LastUsageTime => a.last_usage_time.cmp(&b.last_usage_time),
+    };
+
+    ordering
+        .then_with(|| a.name.to_lowercase().cmp(&b.name.to_lowercase()))
+        .then_with(|| a.id.cmp(&b.id))
+}
+
+/// `Sales & Marketing` rather than the variant name `SalesAndMarketing`
+fn serialized_name<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(Value::String(name)) => name,
+        _ => String::new(),
+    }
+}
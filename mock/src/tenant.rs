@@ -0,0 +1,109 @@
+use std::{collections::HashMap, fmt};
+
+use serde::{Deserialize, Serialize};
+
+/// Tenant of the key set with `TORII_API_KEY` or `TORII_SECRET`
+pub const DEFAULT_TENANT: &str = "default";
+
+/// Team whose data a request reads and writes, resolved from the API key by the auth middleware
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct Tenant(String);
+
+impl Tenant {
+    /// IDs end up in storage keys, so they are limited to lowercase letters, digits, `-` and `_`
+    pub fn new(id: &str) -> Result<Self, String> {
+        let valid = !id.is_empty()
+            && id.len() <= 64
+            && id
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+
+        if valid {
+            Ok(Self(id.to_string()))
+        } else {
+            Err(format!(
+                "Tenant {id} must be 1 to 64 lowercase letters, digits, - or _"
+            ))
+        }
+    }
+
+    pub fn is_default(&self) -> bool {
+        self.0 == DEFAULT_TENANT
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Default for Tenant {
+    fn default() -> Self {
+        Self(DEFAULT_TENANT.to_string())
+    }
+}
+
+impl fmt::Display for Tenant {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl TryFrom<String> for Tenant {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        Self::new(&value)
+    }
+}
+
+impl From<Tenant> for String {
+    fn from(value: Tenant) -> Self {
+        value.0
+    }
+}
+
+/// Repositories and services are created once at startup and narrowed down to a single tenant
+/// for every request
+pub trait TenantScoped {
+    /// A copy that only reads and writes the data of `tenant`
+    fn for_tenant(&self, tenant: &Tenant) -> Self;
+}
+
+/// Which tenant every accepted API key belongs to
+#[derive(Debug, Clone, Default)]
+pub struct TenantKeys {
+    keys: HashMap<String, Tenant>,
+}
+
+impl TenantKeys {
+    /// `default_key` belongs to the default tenant
+    pub fn new(default_key: String) -> Self {
+        Self {
+            keys: HashMap::from([(default_key, Tenant::default())]),
+        }
+    }
+
+    /// Adds the tenants of `json`, an object from tenant ID to its API keys such as
+    /// `{"team-a": ["key-1", "key-2"]}`. A key can only belong to one tenant
+    pub fn with_tenants(mut self, json: &str) -> Result<Self, String> {
+        let tenants: HashMap<Tenant, Vec<String>> =
+            serde_json::from_str(json).map_err(|e| format!("Invalid tenants: {e}"))?;
+
+        for (tenant, keys) in tenants {
+            for key in keys {
+                if let Some(existing) = self.keys.insert(key, tenant.clone()) {
+                    return Err(format!(
+                        "An API key of tenant {tenant} already belongs to tenant {existing}"
+                    ));
+                }
+            }
+        }
+
+        Ok(self)
+    }
+
+    pub fn tenant(&self, api_key: &str) -> Option<&Tenant> {
+        self.keys.get(api_key)
+    }
+}
@@ -0,0 +1,205 @@
+use crate::{
+    repository::{
+        AppUsersRepository, CreateUserError, DeleteUserError, GetUserError, ListUsersError,
+        UpdateUserError, User, UsersRepository,
+    },
+    routes::{CreateUserHttpRequestBody, ListUsersQueryParams, UpdateUserHttpRequestBody},
+    tenant::{Tenant, TenantScoped},
+};
+use async_trait::async_trait;
+use chrono::Utc;
+use tracing::error;
+
+#[async_trait]
+pub trait UsersServiceTrait: TenantScoped + std::fmt::Debug + Send + Sync + Clone {
+    async fn get_user(&self, id: u32) -> Result<User, GetUserError>;
+    async fn create_user(
+        &self,
+        request: CreateUserHttpRequestBody,
+    ) -> Result<User, CreateUserError>;
+    async fn delete_user(&self, id: u32) -> Result<(), DeleteUserError>;
+    async fn list_users(&self, params: ListUsersQueryParams) -> Result<Vec<User>, ListUsersError>;
+    async fn update_user(
+        &self,
+        request: UpdateUserHttpRequestBody,
+        id: u32,
+    ) -> Result<User, UpdateUserError>;
+}
+
+#[derive(Debug, Clone)]
+pub struct UsersService<R, M>
+where
+    R: UsersRepository,
+    M: AppUsersRepository,
+{
+    pub repo: R,
+    pub app_users_repo: M,
+}
+
+impl<R, M> UsersService<R, M>
+where
+    R: UsersRepository,
+    M: AppUsersRepository,
+{
+    pub fn new(repo: R, app_users_repo: M) -> Self {
+        Self {
+            repo,
+            app_users_repo,
+        }
+    }
+}
+
+impl<R, M> TenantScoped for UsersService<R, M>
+where
+    R: UsersRepository,
+    M: AppUsersRepository,
+{
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            repo: self.repo.for_tenant(tenant),
+            app_users_repo: self.app_users_repo.for_tenant(tenant),
+        }
+    }
+}
+
+#[async_trait]
+impl<R, M> UsersServiceTrait for UsersService<R, M>
+where
+    R: UsersRepository,
+    M: AppUsersRepository,
+{
+    #[tracing::instrument(skip(self))]
+    async fn get_user(&self, id: u32) -> Result<User, GetUserError> {
+        self.repo.get_user(id).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn create_user(
+        &self,
+        request: CreateUserHttpRequestBody,
+    ) -> Result<User, CreateUserError> {
+        let email = request.email.to_lowercase();
+
+        match self.repo.get_user_by_email(&email).await {
+            Ok(_) => return Err(CreateUserError::ResourceAlreadyExists { email }),
+            Err(GetUserError::ResourceNotFound(_)) => {}
+            Err(e) => {
+                error!("{}", e);
+                return Err(CreateUserError::UnexpectedError);
+            }
+        }
+
+        let id = self
+            .repo
+            .get_user_id()
+            .await
+            .map_err(|_| CreateUserError::UnexpectedError)?;
+
+        let now = Utc::now();
+        let user = User {
+            id,
+            first_name: request.first_name,
+            last_name: request.last_name,
+            email,
+            lifecycle_status: request.lifecycle_status.unwrap_or_default(),
+            is_external: request.is_external.unwrap_or_default(),
+            creation_time: now,
+            last_updated_at: now,
+        };
+
+        self.repo.create_user(user).await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn delete_user(&self, id: u32) -> Result<(), DeleteUserError> {
+        self.repo.delete_user(id).await?;
+
+        // Same as app deletion, orphaned links are skipped when listing an app's users
+        match self.app_users_repo.list_user_apps(id).await {
+            Ok(app_users) => {
+                for app_user in app_users {
+                    if let Err(e) = self
+                        .app_users_repo
+                        .delete_app_user(app_user.app_id, app_user.user_id)
+                        .await
+                    {
+                        error!("{}", e);
+                    }
+                }
+            }
+            Err(e) => error!("{}", e),
+        }
+
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn list_users(&self, params: ListUsersQueryParams) -> Result<Vec<User>, ListUsersError> {
+        let Some(email) = params.email else {
+            return self.repo.list_users().await;
+        };
+
+        match self.repo.get_user_by_email(&email.to_lowercase()).await {
+            Ok(user) => Ok(vec![user]),
+            Err(GetUserError::ResourceNotFound(_)) => Ok(Vec::new()),
+            Err(e) => {
+                error!("{}", e);
+                Err(ListUsersError::UnexpectedError)
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn update_user(
+        &self,
+        request: UpdateUserHttpRequestBody,
+        id: u32,
+    ) -> Result<User, UpdateUserError> {
+        let mut user = self.get_user(id).await.map_err(|e| match e {
+            GetUserError::ResourceNotFound(_) => UpdateUserError::ResourceNotFound(id),
+            _ => {
+                error!("{}", e);
+                UpdateUserError::UnexpectedError
+            }
+        })?;
+
+        if let Some(email) = request.email {
+            let email = email.to_lowercase();
+
+            if email != user.email {
+                match self.repo.get_user_by_email(&email).await {
+                    Ok(existing) if existing.id != id => {
+                        return Err(UpdateUserError::ResourceAlreadyExists { email });
+                    }
+                    Ok(_) | Err(GetUserError::ResourceNotFound(_)) => {}
+                    Err(e) => {
+                        error!("{}", e);
+                        return Err(UpdateUserError::UnexpectedError);
+                    }
+                }
+            }
+
+            user.email = email
+        };
+
+        if let Some(first_name) = request.first_name {
+            user.first_name = first_name
+        };
+
+        if let Some(last_name) = request.last_name {
+            user.last_name = last_name
+        };
+
+        if let Some(lifecycle_status) = request.lifecycle_status {
+            user.lifecycle_status = lifecycle_status
+        };
+
+        if let Some(is_external) = request.is_external {
+            user.is_external = is_external
+        };
+
+        user.last_updated_at = Utc::now();
+
+        self.repo.update_user(user).await
+    }
+}
@@ -0,0 +1,286 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use futures_util::future::join_all;
+use hmac::{Hmac, Mac};
+use serde::Serialize;
+use serde_json::Value;
+use sha2::Sha256;
+use tokio::time::Instant;
+use tracing::{error, instrument, warn};
+use uuid::Uuid;
+
+use crate::{
+    repository::{
+        DeliveryAttempt, DeliveryStatus, Webhook, WebhookDelivery, WebhookEventType,
+        WebhooksRepository,
+    },
+    request_context::RequestContext,
+    tenant::{Tenant, TenantScoped},
+};
+
+pub const SIGNATURE_HEADER: &str = "x-torii-signature";
+pub const EVENT_HEADER: &str = "x-torii-event";
+pub const DELIVERY_HEADER: &str = "x-torii-delivery";
+
+/// How long a single attempt may take before it counts as failed
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Failed attempts are retried after `initial_backoff`, doubling every time up to `max_backoff`.
+/// Deliveries still failing `deadline` after the request arrived are given up on, as they hold
+/// up the request. The deadline covers every event the request dispatches, not each one
+#[derive(Debug, Clone)]
+pub struct RetryPolicy {
+    pub max_attempts: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub deadline: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_attempts: 3,
+            initial_backoff: Duration::from_millis(500),
+            max_backoff: Duration::from_secs(2),
+            deadline: Duration::from_secs(5),
+        }
+    }
+}
+
+impl RetryPolicy {
+    /// Delay before the attempt after `attempt`, which counts from 1
+    pub fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 2u32.saturating_pow(attempt.saturating_sub(1));
+
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+/// Body POSTed to subscribers
+#[derive(Debug, Clone, Serialize)]
+pub struct WebhookEvent {
+    pub id: String,
+    #[serde(rename = "type")]
+    pub event_type: WebhookEventType,
+    pub timestamp: DateTime<Utc>,
+    #[serde(rename = "requestId")]
+    pub request_id: String,
+    pub data: Value,
+}
+
+impl WebhookEvent {
+    pub fn new(event_type: WebhookEventType, context: &RequestContext, data: Value) -> Self {
+        Self {
+            id: Uuid::new_v4().to_string(),
+            event_type,
+            timestamp: Utc::now(),
+            request_id: context.request_id.clone(),
+            data,
+        }
+    }
+}
+
+/// Sends events to every active subscriber. Deliveries finish before the request that caused
+/// the event returns, Lambda freezes the runtime afterwards so background tasks would never run
+#[derive(Debug, Clone)]
+pub struct WebhookDispatcher<W>
+where
+    W: WebhooksRepository,
+{
+    repo: W,
+    client: reqwest::Client,
+    retry: RetryPolicy,
+}
+
+/// Only delivers to the subscribers of `tenant`, the HTTP client is shared
+impl<W> TenantScoped for WebhookDispatcher<W>
+where
+    W: WebhooksRepository,
+{
+    fn for_tenant(&self, tenant: &Tenant) -> Self {
+        Self {
+            repo: self.repo.for_tenant(tenant),
+            ..self.clone()
+        }
+    }
+}
+
+impl<W> WebhookDispatcher<W>
+where
+    W: WebhooksRepository,
+{
+    pub fn new(repo: W, retry: RetryPolicy) -> Self {
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Creation of webhook HTTP client should not fail");
+
+        Self {
+            repo,
+            client,
+            retry,
+        }
+    }
+
+    /// Delivers to every subscriber at once and waits for all of them, at most until
+    /// `RetryPolicy::deadline` after `context` was received. Once that has passed the deliveries
+    /// are logged as failed without being sent. Failures are only logged, an event that can't be
+    /// dispatched doesn't undo the change
+    #[instrument(skip(self, context, data))]
+    pub async fn dispatch(
+        &self,
+        context: &RequestContext,
+        event_type: WebhookEventType,
+        data: Value,
+    ) {
+        let event = WebhookEvent::new(event_type, context, data);
+
+        let webhooks = match self.repo.list_webhooks().await {
+            Ok(webhooks) => webhooks,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+
+        let body = match serde_json::to_string(&event) {
+            Ok(body) => body,
+            Err(e) => {
+                error!("{}", e);
+                return;
+            }
+        };
+
+        let deadline = context.received_at + self.retry.deadline;
+        let mut deliveries = Vec::new();
+
+        for webhook in webhooks
+            .into_iter()
+            .filter(|webhook| webhook.is_subscribed(event.event_type))
+        {
+            let now = Utc::now();
+            let delivery = WebhookDelivery {
+                id: Uuid::new_v4().to_string(),
+                webhook_id: webhook.id.clone(),
+                event_id: event.id.clone(),
+                event_type: event.event_type,
+                url: webhook.url.clone(),
+                status: DeliveryStatus::Pending,
+                attempts: Vec::new(),
+                creation_time: now,
+                last_updated_at: now,
+            };
+
+            if let Err(e) = self.repo.put_delivery(delivery.clone()).await {
+                error!("{}", e);
+            }
+
+            deliveries.push(self.deliver(webhook, delivery, &body, deadline));
+        }
+
+        join_all(deliveries).await;
+    }
+
+    /// Keeps attempting until the receiver answers with a 2xx or the attempts or the time run
+    /// out, the delivery log is updated after every attempt
+    #[instrument(skip(self, webhook, body, deadline), fields(webhook_id = %webhook.id))]
+    async fn deliver(
+        &self,
+        webhook: Webhook,
+        mut delivery: WebhookDelivery,
+        body: &str,
+        deadline: Instant,
+    ) {
+        for attempt in 1..=self.retry.max_attempts {
+            let timeout = deadline
+                .saturating_duration_since(Instant::now())
+                .min(REQUEST_TIMEOUT);
+
+            if timeout.is_zero() {
+                warn!("Request deadline passed before delivery {}", delivery.id);
+                delivery.status = DeliveryStatus::Failed;
+                delivery.last_updated_at = Utc::now();
+
+                if let Err(e) = self.repo.put_delivery(delivery).await {
+                    error!("{}", e);
+                }
+
+                return;
+            }
+
+            let result = self.send(&webhook, &delivery, body, timeout).await;
+            let backoff = self.retry.backoff(attempt);
+            let succeeded = matches!(result, Ok(status) if status.is_success());
+
+            delivery.attempts.push(DeliveryAttempt {
+                attempted_at: Utc::now(),
+                status_code: result.as_ref().ok().map(|status| status.as_u16()),
+                error: result.err().map(|e| e.to_string()),
+            });
+            delivery.last_updated_at = Utc::now();
+
+            if succeeded {
+                delivery.status = DeliveryStatus::Succeeded;
+            } else if attempt == self.retry.max_attempts || Instant::now() + backoff >= deadline {
+                warn!(
+                    "Giving up on delivery {} after {attempt} attempts",
+                    delivery.id
+                );
+                delivery.status = DeliveryStatus::Failed;
+            }
+
+            if let Err(e) = self.repo.put_delivery(delivery.clone()).await {
+                error!("{}", e);
+            }
+
+            if delivery.status != DeliveryStatus::Pending {
+                return;
+            }
+
+            tokio::time::sleep(backoff).await;
+        }
+    }
+
+    async fn send(
+        &self,
+        webhook: &Webhook,
+        delivery: &WebhookDelivery,
+        body: &str,
+        timeout: Duration,
+    ) -> Result<reqwest::StatusCode, reqwest::Error> {
+        let timestamp = Utc::now().timestamp();
+
+        let response = self
+            .client
+            .post(&webhook.url)
+            .header(reqwest::header::CONTENT_TYPE, "application/json")
+            .header(EVENT_HEADER, delivery.event_type.to_string())
+            .header(DELIVERY_HEADER, &delivery.id)
+            .header(
+                SIGNATURE_HEADER,
+                format!(
+                    "t={timestamp},v1={}",
+                    sign(&webhook.secret, timestamp, body)
+                ),
+            )
+            .body(body.to_string())
+            .timeout(timeout)
+            .send()
+            .await?;
+
+        Ok(response.status())
+    }
+}
+
+/// Hex encoded HMAC-SHA256 of `<timestamp>.<body>`, the timestamp is included so receivers can
+/// reject replayed deliveries
+pub fn sign(secret: &str, timestamp: i64, body: &str) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any size");
+    mac.update(format!("{timestamp}.{body}").as_bytes());
+
+    hex::encode(mac.finalize().into_bytes())
+}
@@ -139,7 +139,8 @@ class ToriiApp(BaseModel):
     url: str
     image_url: Optional[str] = Field(alias="imageUrl")
     category: AppCategory
-    users: Optional[str] = None
+    users: Optional[int] = None
+    fields: dict[str, Any] = Field(default_factory=dict)
     description: Optional[str] = None
     tags: Optional[str] = None
     creation_time: datetime.datetime = Field(alias="creationTime")
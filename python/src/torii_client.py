@@ -37,21 +37,32 @@ class ToriiClient:
         self.http = requests.Session()
         self.http.headers.update(self.headers)
 
-    def list_apps(self) -> list[ToriiApp]:
+    def list_apps(self, page_size: int = 100) -> list[ToriiApp]:
         """
-        Retrieves a list of all apps.
+        Retrieves a list of all apps, following `nextCursor` until every page has been read.
+
+        Args:
+            page_size (int): The number of apps to request per page.
 
         Returns:
             list[App]: A list of apps from the Torii API.
         """
-        response = self.http.get(f"{self.base_url}/v1.0/apps")
-        response.raise_for_status()
-        logger.debug(f"Response", {"response", response})
-
         ta = TypeAdapter(list[ToriiApp])
-        apps = ta.validate_python(response.json())
+        apps: list[ToriiApp] = []
+        params: dict[str, str | int] = {"size": page_size}
 
-        return apps
+        while True:
+            response = self.http.get(f"{self.base_url}/v1.0/apps", params=params)
+            response.raise_for_status()
+            logger.debug(f"Response", {"response", response})
+
+            page = response.json()
+            apps.extend(ta.validate_python(page["apps"]))
+
+            if not page.get("nextCursor"):
+                return apps
+
+            params["cursor"] = page["nextCursor"]
 
     def add_app(self, app_id: int) -> ToriiApp:
         """
@@ -111,7 +122,8 @@ class ToriiClient:
 
     def search_apps(self, query: str) -> list[ToriiApp]:
         """
-        Searches for apps with a name containing the query string.
+        Searches for apps matching the query string by name, url, description or tags,
+        best match first.
 
         Args:
             query (str): The search query.
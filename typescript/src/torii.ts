@@ -10,23 +10,34 @@ import { buildMapFromProperty } from './utils'
 // static type safety guaranteed by using this.
 
 export async function listToriiApps(): Promise<ToriiApp[]> {
-    const { data, error, response } = await torii.GET( '/v1.0/apps' )
+    const apps: ToriiApp[] = []
+    let cursor: string | undefined
 
-    logger.debug( 'Torii response', { data: response } )
+    // Page through the catalog until Torii stops returning a `nextCursor`
+    do {
+        const { data, error, response } = await torii.GET( '/v1.0/apps', {
+            params: { query: { size: 100, cursor } }
+        } )
 
-    if ( !( response.status >= 200 && response.status < 300 ) ) {
-        logger.error( 'Error listing apps from Torii:', { errorMessage: response.statusText } )
-        throw new Error( response.statusText )
-    }
+        logger.debug( 'Torii response', { data: response } )
 
-    if ( error ) {
-        logger.error( 'Error:', { errorMessage: error } )
-        throw new Error( error )
-    }
+        if ( !( response.status >= 200 && response.status < 300 ) ) {
+            logger.error( 'Error listing apps from Torii:', { errorMessage: response.statusText } )
+            throw new Error( response.statusText )
+        }
 
-    // The API returns an empty list if nothing is found, so this is
-    // guaranteed to not be `undefined`
-    return data!
+        if ( error ) {
+            logger.error( 'Error:', { errorMessage: error } )
+            throw new Error( error )
+        }
+
+        // The API returns an empty page if nothing is found, so this is
+        // guaranteed to not be `undefined`
+        apps.push( ...data!.apps )
+        cursor = data!.nextCursor ?? undefined
+    } while ( cursor )
+
+    return apps
 }
 
 export async function createToriiApp( app: components[ 'schemas' ][ 'CreateAppHttpRequestBody' ] ) {
@@ -92,12 +92,16 @@ export interface components {
             /** Format: int32 */
             idApp: number;
         };
+        /** @enum {string} */
+        AppSortField: "name" | "state" | "category" | "creationTime" | "lastUpdatedAt" | "lastUsageTime";
         App: {
             addedBy: string;
             category: components["schemas"]["AppCategory"];
             /** Format: date-time */
             creationTime: string;
             description?: string | null;
+            /** @description Values of custom fields keyed by `AppField.key` */
+            fields?: { [key: string]: unknown };
             /** Format: int32 */
             id: number;
             imageUrl?: string | null;
@@ -113,7 +117,11 @@ export interface components {
             state: components["schemas"]["AppState"];
             tags?: string | null;
             url: string;
-            users?: string | null;
+            /**
+             * Format: int32
+             * @description Number of users with an active license, computed from the app's users when read
+             */
+            users?: number | null;
         };
         /** @enum {string} */
 <<<<<<< HEAD
@@ -142,6 +150,18 @@ export interface components {
             name: string;
             url: string;
         };
+        ListAppsHttpResponse: {
+            /** @description Apps projected down to the requested `fields` */
+            apps: components["schemas"]["App"][];
+            count: number;
+            nextCursor?: string | null;
+        };
+        /** @enum {string} */
+        SortOrder: "asc" | "desc";
+        ScoredApp: components["schemas"]["App"] & {
+            /** Format: float */
+            score: number;
+        };
         UpdateAppHttpRequestBody: {
             category?: components["schemas"]["AppCategory"] | null;
             description?: string | null;
@@ -161,7 +181,29 @@ export type $defs = Record<string, never>;
 export interface operations {
     list_apps: {
         parameters: {
-            query?: never;
+            query?: {
+                /** @description Maximum number of apps to return */
+                size?: number | null;
+                /** @description `nextCursor` from the previous page */
+                cursor?: string | null;
+                /** @description Comma separated list of fields to return, `id` is always included */
+                fields?: string | null;
+                state?: components["schemas"]["AppState"] | null;
+                category?: components["schemas"]["AppCategory"] | null;
+                isCustom?: boolean | null;
+                isHidden?: boolean | null;
+                /** @description Comma separated list of tags, apps must have all of them */
+                tags?: string | null;
+                primaryOwner?: string | null;
+                creationTimeFrom?: string | null;
+                creationTimeTo?: string | null;
+                lastUpdatedAtFrom?: string | null;
+                lastUpdatedAtTo?: string | null;
+                /** @description Sorting reads the whole catalog, without it pages follow storage order */
+                sort?: components["schemas"]["AppSortField"] | null;
+                /** @description Defaults to `asc` */
+                order?: components["schemas"]["SortOrder"] | null;
+            };
             header?: never;
             path?: never;
             cookie?: never;
@@ -174,7 +216,7 @@ export interface operations {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["App"][];
+                    "application/json": components["schemas"]["ListAppsHttpResponse"];
                 };
             };
             /** @description Bad Request */
@@ -370,7 +412,12 @@ export interface operations {
     search_apps: {
         parameters: {
             query: {
+                /** @description Matched case insensitively against name, url, description and tags */
                 query: string;
+                /** @description Results are ordered by score unless a sort field is given */
+                sort?: components["schemas"]["AppSortField"] | null;
+                /** @description Defaults to `asc` */
+                order?: components["schemas"]["SortOrder"] | null;
             };
             header?: never;
             path?: never;
@@ -378,13 +425,13 @@ export interface operations {
         };
         requestBody?: never;
         responses: {
-            /** @description List of apps matching query */
+            /** @description Apps matching query, best match first */
             200: {
                 headers: {
                     [name: string]: unknown;
                 };
                 content: {
-                    "application/json": components["schemas"]["App"][];
+                    "application/json": components["schemas"]["ScoredApp"][];
                 };
             };
             /** @description Bad Request */
@@ -425,7 +472,10 @@ export interface operations {
     };
     get_app: {
         parameters: {
-            query?: never;
+            query?: {
+                /** @description Comma separated list of fields to return, `id` is always included */
+                fields?: string | null;
+            };
             header?: never;
             path: {
                 id: number;